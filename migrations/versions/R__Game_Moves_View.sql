@@ -4,8 +4,10 @@ CREATE OR REPLACE VIEW game_moves AS (
         json_agg(
             json_build_object(
                 'cell_id', cell_id,
+                'created_at', created_at,
                 'move_type', move_type
             )
+            ORDER BY created_at, move_sequence_id
         ) AS moves
     FROM game_move
     GROUP BY game_id
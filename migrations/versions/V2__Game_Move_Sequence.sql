@@ -0,0 +1,3 @@
+-- Moves stored together share a `created_at`, the sequence keeps them in the
+-- order they were made
+ALTER TABLE game_move ADD COLUMN move_sequence_id bigserial;
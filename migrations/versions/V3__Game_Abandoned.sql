@@ -0,0 +1,2 @@
+-- A game is lost once it is abandoned, whatever its cells show
+ALTER TABLE game ADD COLUMN abandoned_at timestamptz;
@@ -0,0 +1,2 @@
+-- Seed the mines were placed with, unknown for games created before seeds
+ALTER TABLE game ADD COLUMN seed bigint;
@@ -1,2 +1,3 @@
 export { GameCompleteError } from './game-complete.exception';
+export { InvalidCursorError } from './invalid-cursor.exception';
 export { NoRecordError } from './no-record.exception';
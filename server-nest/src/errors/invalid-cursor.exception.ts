@@ -0,0 +1,13 @@
+export class InvalidCursorError extends Error {
+  /**
+   * Indicates the given `cursor` does not point at a page of records.
+   * @param cursor which could not be read.
+   */
+  constructor(private readonly cursor: string) {
+    super();
+  }
+
+  get message(): string {
+    return `Cursor "${this.cursor}" does not point at a page.`;
+  }
+}
@@ -10,6 +10,14 @@ export enum GameMoveType {
   REMOVE_FLAG = 'REMOVE_FLAG',
 }
 
+/**
+ * Represent whether a move sent along with others was applied to the game.
+ */
+export enum GameMoveOutcome {
+  APPLIED = 'APPLIED',
+  REJECTED = 'REJECTED',
+}
+
 /**
  * Define the structure of data required to apply a "move" or "action" to a
  * game.
@@ -17,6 +25,13 @@ export enum GameMoveType {
 export interface GameMove {
   /** Identifier of the `Cell` to which the action applies. */
   cellId: CellId;
+  /** When the action was taken. */
+  createdAt: Date;
   /** Type of action taken on the given `Cell`. */
   type: GameMoveType;
 }
+
+/**
+ * The part of a `GameMove` which determines its effect on the state of play.
+ */
+export type GameAction = Pick<GameMove, 'cellId' | 'type'>;
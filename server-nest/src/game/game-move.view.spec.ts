@@ -0,0 +1,47 @@
+import { Game } from './game.model';
+import { GameMoveType } from './game-move.model';
+import { GameMoveView, serializeGameMoves } from './game-move.view';
+
+describe(serializeGameMoves, () => {
+  describe('new Game', () => {
+    it('is empty', () => {
+      const game = new Game({ rows: 2, columns: 3 });
+      expect(serializeGameMoves(game)).toHaveLength(0);
+    });
+  });
+
+  describe('Game with moves', () => {
+    let game: Game;
+    let subject: GameMoveView[];
+
+    beforeEach(() => {
+      game = new Game({ rows: 2, columns: 3 })
+        .flagCoordinates(2, 1)
+        .unflagCoordinates(2, 1)
+        .flagCoordinates(1, 0);
+      subject = serializeGameMoves(game);
+    });
+
+    it('lists moves in the order they were made', () => {
+      expect(subject.map((move) => move.type)).toEqual([
+        GameMoveType.FLAG,
+        GameMoveType.REMOVE_FLAG,
+        GameMoveType.FLAG,
+      ]);
+    });
+
+    it('locates moves by row and column', () => {
+      expect(subject.map(({ row, column }) => [row, column])).toEqual([
+        [1, 2],
+        [1, 2],
+        [0, 1],
+      ]);
+    });
+
+    it('has when each move was made', () => {
+      expect(subject[0].createdAt).toEqual(
+        game.moves[0].createdAt.toISOString()
+      );
+    });
+  });
+});
@@ -0,0 +1,31 @@
+import { CellId } from './cell.model';
+import { Game } from './game.model';
+import { GameMoveType } from './game-move.model';
+
+export interface GameMoveView {
+  column: number;
+  createdAt: string;
+  row: number;
+  type: GameMoveType;
+}
+
+/**
+ * List the moves made in `game` in the order they were made, locating each by
+ * the row and column of its `Cell`.
+ * @param game whose moves are listed.
+ * @returns the moves of `game`.
+ */
+export function serializeGameMoves(game: Game): GameMoveView[] {
+  const cellIndexes = new Map<CellId, number>(
+    game.cells.map((cell, index) => [cell.id, index])
+  );
+  return game.moves.map((move) => {
+    const index = cellIndexes.get(move.cellId);
+    return {
+      column: index % game.columns,
+      createdAt: move.createdAt.toISOString(),
+      row: Math.floor(index / game.columns),
+      type: move.type,
+    };
+  });
+}
@@ -0,0 +1,91 @@
+import { Cell } from './cell.model';
+import { Game, GameStatus } from './game.model';
+import { GameMoveType } from './game-move.model';
+import {
+  GAME_SUMMARY_PAGE_SIZE,
+  serializeGameSummary,
+  serializeGameSummaryPage,
+} from './game-summary.view';
+
+function createGame(createdAt: string): Game {
+  return new Game({ rows: 3, columns: 3, createdAt: new Date(createdAt) });
+}
+
+describe(serializeGameSummary, () => {
+  it('is updated when created without moves', () => {
+    const game = createGame('2021-02-01T00:00:00.000Z');
+    const subject = serializeGameSummary(game);
+    expect(subject).toEqual({
+      columns: 3,
+      createdAt: '2021-02-01T00:00:00.000Z',
+      id: game.id,
+      moveCount: 0,
+      rows: 3,
+      status: GameStatus.OPEN,
+      updatedAt: '2021-02-01T00:00:00.000Z',
+    });
+  });
+
+  it('is updated by the last move', () => {
+    const cells = Array.from({ length: 9 }, () => new Cell({ isMine: false }));
+    const next = new Game({
+      rows: 3,
+      columns: 3,
+      createdAt: new Date('2021-02-01T00:00:00.000Z'),
+      cells,
+      moves: [
+        {
+          cellId: cells[0].id,
+          createdAt: new Date('2021-02-02T00:00:00.000Z'),
+          type: GameMoveType.FLAG,
+        },
+      ],
+    });
+    const subject = serializeGameSummary(next);
+    expect(subject).toHaveProperty('moveCount', 1);
+    expect(subject).toHaveProperty('updatedAt', '2021-02-02T00:00:00.000Z');
+  });
+});
+
+describe(serializeGameSummaryPage, () => {
+  const older = createGame('2021-02-01T00:00:00.000Z');
+  const newer = createGame('2021-02-02T00:00:00.000Z');
+
+  it('lists the newest game first', () => {
+    const subject = serializeGameSummaryPage([older, newer], {});
+    expect(subject.items.map((item) => item.id)).toEqual([newer.id, older.id]);
+  });
+
+  it('lists the oldest game first', () => {
+    const subject = serializeGameSummaryPage([newer, older], {
+      sort: 'createdAt',
+    });
+    expect(subject.items.map((item) => item.id)).toEqual([older.id, newer.id]);
+  });
+
+  it('leaves out the cursor on the last page', () => {
+    const subject = serializeGameSummaryPage([older, newer], {});
+    expect(subject).not.toHaveProperty('nextCursor');
+  });
+
+  it('pages with the cursor', () => {
+    const games = Array.from({ length: GAME_SUMMARY_PAGE_SIZE + 1 }, () =>
+      createGame('2021-02-01T00:00:00.000Z')
+    );
+    const first = serializeGameSummaryPage(games, {});
+    expect(first.items).toHaveLength(GAME_SUMMARY_PAGE_SIZE);
+    const second = serializeGameSummaryPage(games, {
+      cursor: first.nextCursor,
+    });
+    expect(second.items.map((item) => item.id)).toEqual([
+      games[GAME_SUMMARY_PAGE_SIZE].id,
+    ]);
+    expect(second).not.toHaveProperty('nextCursor');
+  });
+
+  it('throws for a cursor which is not a page', () => {
+    expect(() => serializeGameSummaryPage([older], { cursor: 'foo' })).toThrow(
+      'does not point at a page'
+    );
+  });
+});
@@ -0,0 +1,84 @@
+import { InvalidCursorError } from '../errors';
+import { GameSummaryQueryDto } from './game.dto';
+import { Game, GameId, GameStatus } from './game.model';
+
+/** Number of summaries listed on each page. */
+export const GAME_SUMMARY_PAGE_SIZE = 20;
+
+export interface GameSummaryView {
+  columns: number;
+  createdAt: string;
+  id: GameId;
+  moveCount: number;
+  rows: number;
+  status: GameStatus;
+  updatedAt: string;
+}
+
+export interface GameSummaryPageView {
+  items: GameSummaryView[];
+  nextCursor?: string;
+}
+
+/**
+ * Describe `game` without its board. A game is updated when it is abandoned or
+ * its last move is made, or when it is created if neither has happened.
+ * @param game to describe.
+ * @returns the summary of `game`.
+ */
+export function serializeGameSummary(game: Game): GameSummaryView {
+  const lastMove = game.moves[game.moves.length - 1];
+  const updatedAt =
+    game.abandonedAt ?? (lastMove ? lastMove.createdAt : game.createdAt);
+  return {
+    columns: game.columns,
+    createdAt: game.createdAt.toISOString(),
+    id: game.id,
+    moveCount: game.moves.length,
+    rows: game.rows,
+    status: game.gameStatus,
+    updatedAt: updatedAt.toISOString(),
+  };
+}
+
+function encodeCursor(offset: number): string {
+  return Buffer.from(String(offset)).toString('base64');
+}
+
+function decodeCursor(cursor: string): number {
+  const offset = Number(Buffer.from(cursor, 'base64').toString());
+  if (!Number.isInteger(offset) || offset < 0) {
+    throw new InvalidCursorError(cursor);
+  }
+  return offset;
+}
+
+/**
+ * List a page of summaries of `games` matching `query`, newest first unless
+ * `query.sort` says otherwise. Games created or updated at the same time are
+ * kept in the order they are given.
+ * @param games to summarise.
+ * @param query choosing the status, order and page of the summaries.
+ * @returns the page of summaries and the cursor of the next page, if any.
+ * @throws InvalidCursorError if `query.cursor` does not point at a page.
+ */
+export function serializeGameSummaryPage(
+  games: Game[],
+  query: GameSummaryQueryDto
+): GameSummaryPageView {
+  const { cursor, sort = '-createdAt', status } = query;
+  const offset = typeof cursor === 'undefined' ? 0 : decodeCursor(cursor);
+  const field = sort.replace(/^-/, '') as 'createdAt' | 'updatedAt';
+  const direction = sort.startsWith('-') ? -1 : 1;
+  const summaries = games
+    .map(serializeGameSummary)
+    .filter((summary) => !status || summary.status === status)
+    .sort((a, b) => direction * a[field].localeCompare(b[field]));
+  const end = offset + GAME_SUMMARY_PAGE_SIZE;
+  const items = summaries.slice(offset, end);
+  if (end < summaries.length) {
+    return { items, nextCursor: encodeCursor(end) };
+  } else {
+    return { items };
+  }
+}
@@ -1,4 +1,6 @@
 import {
+  BadRequestException,
+  ConflictException,
   NotFoundException,
   UnprocessableEntityException,
 } from '@nestjs/common';
@@ -7,7 +9,8 @@ import { GameController } from './game.controller';
 import { GameMoveDto } from './game.dto';
 import { BaseGameService, GameService } from './game.service';
 import { MemGameService } from './game.mem-service';
-import { GameMoveType } from './game-move.model';
+import { GameStatus } from './game.model';
+import { GameMoveOutcome, GameMoveType } from './game-move.model';
 
 describe(GameController, () => {
   let controller: GameController;
@@ -43,10 +46,66 @@ describe(GameController, () => {
       expect(result.id).toBeDefined();
     });
 
-    it('only returns an id', async () => {
+    it('places the given number of mines', async () => {
+      const { id } = await controller.create({
+        rows: 16,
+        columns: 30,
+        mines: 99,
+      });
+      const result = await controller.findOne(id);
+      expect(result).toHaveProperty('mines', 99);
+    });
+
+    it.each([
+      { mines: 0 },
+      { mines: 9 },
+      { mines: 1.5 },
+      { density: 0 },
+      { density: 1 },
+      { mines: 1, density: 0.5 },
+    ])('throws BadRequestException for %j', async (mines) => {
+      try {
+        await controller.create({ rows: 3, columns: 3, ...mines });
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(BadRequestException);
+      }
+    });
+
+    it('only returns an id and seed', async () => {
       const result = await controller.create({ rows: 3, columns: 3 });
-      expect(Object.keys(result)).toEqual(['id']);
+      expect(Object.keys(result)).toEqual(['id', 'seed']);
+    });
+
+    it('returns the given seed', async () => {
+      const result = await controller.create({ rows: 3, columns: 3, seed: 7 });
+      expect(result).toHaveProperty('seed', 7);
     });
+
+    it('places the same mines for the same seed', async () => {
+      const input = { rows: 10, columns: 10, mines: 20, seed: 1234 };
+      const first = await controller.create(input);
+      const second = await controller.create(input);
+      const [firstGame, secondGame] = await Promise.all([
+        service.findById(first.id),
+        service.findById(second.id),
+      ]);
+      expect(secondGame.cells.map((cell) => cell.isMine)).toEqual(
+        firstGame.cells.map((cell) => cell.isMine)
+      );
+    });
+
+    it.each([-1, 1.5, 4294967296])(
+      'throws BadRequestException for seed %d',
+      async (seed) => {
+        try {
+          await controller.create({ rows: 3, columns: 3, seed });
+          fail('Expected an error to be thrown');
+        } catch (error) {
+          expect(error).toBeInstanceOf(BadRequestException);
+        }
+      }
+    );
   });
 
   describe('GET /', () => {
@@ -68,6 +127,44 @@ describe(GameController, () => {
     });
   });
 
+  describe('GET /summaries', () => {
+    it('is an empty page', async () => {
+      const result = await controller.findSummaries({});
+      expect(result).toEqual({ items: [] });
+    });
+
+    it('summarises a created game', async () => {
+      const { id } = await controller.create({ rows: 3, columns: 4 });
+      const result = await controller.findSummaries({});
+      expect(result.items).toEqual([
+        expect.objectContaining({
+          columns: 4,
+          id,
+          moveCount: 0,
+          rows: 3,
+          status: GameStatus.OPEN,
+        }),
+      ]);
+    });
+
+    it('filters by status', async () => {
+      await controller.create({ rows: 3, columns: 3 });
+      const result = await controller.findSummaries({
+        status: GameStatus.WON,
+      });
+      expect(result.items).toHaveLength(0);
+    });
+
+    it('throws BadRequestException for an invalid cursor', async () => {
+      try {
+        await controller.findSummaries({ cursor: 'foo' });
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(BadRequestException);
+      }
+    });
+  });
+
   describe('GET /:id', () => {
     it('throws NotFoundException for non-existing id', async () => {
       try {
@@ -88,6 +185,40 @@ describe(GameController, () => {
     });
   });
 
+  describe('GET /:id/moves', () => {
+    it('throws NotFoundException for non-existing id', async () => {
+      try {
+        await controller.findMoves('foo');
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NotFoundException);
+      }
+    });
+
+    it('is empty for a new Game', async () => {
+      const { id } = await controller.create({ rows: 3, columns: 3 });
+      const result = await controller.findMoves(id);
+      expect(result).toHaveLength(0);
+    });
+
+    it('lists moves after they are made', async () => {
+      const { id } = await controller.create({ rows: 3, columns: 3 });
+      await controller.addMove(id, {
+        column: 2,
+        row: 1,
+        type: GameMoveType.FLAG,
+      });
+      const result = await controller.findMoves(id);
+      expect(result).toEqual([
+        expect.objectContaining({
+          column: 2,
+          row: 1,
+          type: GameMoveType.FLAG,
+        }),
+      ]);
+    });
+  });
+
   describe('PATCH /:id', () => {
     const alpha: GameMoveDto = { column: 0, type: GameMoveType.OPEN, row: 0 };
     const type = GameMoveType.OPEN;
@@ -140,4 +271,81 @@ describe(GameController, () => {
       expect(result).toHaveProperty('status');
     });
   });
+
+  describe('POST /:id/moves', () => {
+    const open = (column: number): GameMoveDto => ({
+      column,
+      row: 0,
+      type: GameMoveType.OPEN,
+    });
+
+    it('throws NotFoundException for non-existing id', async () => {
+      try {
+        await controller.addMoves('foo', { moves: [open(0)] });
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NotFoundException);
+      }
+    });
+
+    it('has an outcome for each move', async () => {
+      const { id } = await controller.create({ rows: 10, columns: 10 });
+      const result = await controller.addMoves(id, {
+        moves: [open(11), { ...open(0), type: GameMoveType.FLAG }],
+      });
+      expect(result.outcomes).toEqual([
+        GameMoveOutcome.REJECTED,
+        GameMoveOutcome.APPLIED,
+      ]);
+      expect(result.game).toHaveProperty('id', id);
+      expect(result.game.board[0][0]).toEqual('F');
+    });
+  });
+
+  describe('POST /:id/abandon', () => {
+    it('throws NotFoundException for non-existing id', async () => {
+      try {
+        await controller.abandon('foo');
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NotFoundException);
+      }
+    });
+
+    it('marks the game as lost', async () => {
+      const { id } = await controller.create({ rows: 3, columns: 3 });
+      const result = await controller.abandon(id);
+      expect(result).toHaveProperty('id', id);
+      expect(result).toHaveProperty('status', GameStatus.LOST);
+    });
+
+    it('throws ConflictException once abandoned', async () => {
+      const { id } = await controller.create({ rows: 3, columns: 3 });
+      await controller.abandon(id);
+      try {
+        await controller.abandon(id);
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(ConflictException);
+      }
+    });
+  });
+
+  describe('DELETE /:id', () => {
+    it('throws NotFoundException for non-existing id', async () => {
+      try {
+        await controller.remove('foo');
+        fail('Expected an error to be thrown');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NotFoundException);
+      }
+    });
+
+    it('removes the game', async () => {
+      const { id } = await controller.create({ rows: 3, columns: 3 });
+      await controller.remove(id);
+      const result = await controller.findAll();
+      expect(result).not.toContain(id);
+    });
+  });
 });
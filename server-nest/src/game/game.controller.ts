@@ -1,7 +1,9 @@
 import {
+  BadRequestException,
   Body,
   ConflictException,
   Controller,
+  Delete,
   Get,
   Header,
   HttpCode,
@@ -9,15 +11,37 @@ import {
   Param,
   Patch,
   Post,
+  Query,
   UnprocessableEntityException,
   UsePipes,
 } from '@nestjs/common';
-import { GameCompleteError, NoRecordError } from '../errors';
+import {
+  GameCompleteError,
+  InvalidCursorError,
+  NoRecordError,
+} from '../errors';
 import { IoValidationPipe } from '../io-validation.pipe';
-import { CreateGameDto, GameMoveDto } from './game.dto';
+import {
+  CreateGameDto,
+  findInvalidMines,
+  findInvalidSeed,
+  GameMoveBatchDto,
+  GameMoveDto,
+  GameSummaryQueryDto,
+} from './game.dto';
 import { Game, GameId } from './game.model';
 import { GameService } from './game.service';
-import { GameView, serializeGame } from './game.view';
+import {
+  GameMoveBatchView,
+  GameView,
+  serializeGame,
+  serializeGameMoveBatch,
+} from './game.view';
+import { GameMoveView, serializeGameMoves } from './game-move.view';
+import {
+  GameSummaryPageView,
+  serializeGameSummaryPage,
+} from './game-summary.view';
 
 @Controller('game')
 export class GameController {
@@ -26,9 +50,15 @@ export class GameController {
   @Post()
   @HttpCode(201)
   @UsePipes(new IoValidationPipe(CreateGameDto))
-  async create(@Body() data: CreateGameDto): Promise<Pick<Game, 'id'>> {
+  async create(
+    @Body() data: CreateGameDto
+  ): Promise<Pick<Game, 'id' | 'seed'>> {
+    const invalid = findInvalidMines(data) ?? findInvalidSeed(data);
+    if (typeof invalid !== 'undefined') {
+      throw new BadRequestException(invalid, 'invalid_params');
+    }
     const game = await this.gameService.create(data);
-    return { id: game.id };
+    return { id: game.id, seed: game.seed };
   }
 
   @Get()
@@ -38,6 +68,24 @@ export class GameController {
     return games.map((game) => game.id);
   }
 
+  @Get('summaries')
+  @Header('Cache-Control', 'must-revalidate, max-age=60')
+  async findSummaries(
+    @Query(new IoValidationPipe(GameSummaryQueryDto, 'query'))
+    query: GameSummaryQueryDto
+  ): Promise<GameSummaryPageView> {
+    const games = await this.gameService.list();
+    try {
+      return serializeGameSummaryPage(games, query);
+    } catch (error) {
+      if (error instanceof InvalidCursorError) {
+        throw new BadRequestException(error.message, 'invalid_params');
+      } else {
+        throw error;
+      }
+    }
+  }
+
   @Get(':id')
   @Header('Cache-Control', 'must-revalidate, max-age=60')
   async findOne(@Param('id') id: GameId): Promise<GameView> {
@@ -48,6 +96,16 @@ export class GameController {
     return serializeGame(game);
   }
 
+  @Get(':id/moves')
+  @Header('Cache-Control', 'must-revalidate, max-age=60')
+  async findMoves(@Param('id') id: GameId): Promise<GameMoveView[]> {
+    const game = await this.gameService.findById(id);
+    if (typeof game === 'undefined' || game === null) {
+      throw new NotFoundException();
+    }
+    return serializeGameMoves(game);
+  }
+
   @Patch(':id')
   @UsePipes(new IoValidationPipe(GameMoveDto))
   async addMove(
@@ -73,4 +131,62 @@ export class GameController {
       }
     }
   }
+
+  @Post(':id/moves')
+  @HttpCode(200)
+  @UsePipes(new IoValidationPipe(GameMoveBatchDto))
+  async addMoves(
+    @Param('id') id: GameId,
+    @Body() batch: GameMoveBatchDto
+  ): Promise<GameMoveBatchView> {
+    try {
+      const result = await this.gameService.addMovesById(id, batch.moves);
+      return serializeGameMoveBatch(result);
+    } catch (error) {
+      if (error instanceof NoRecordError) {
+        throw new NotFoundException();
+      } else if (error instanceof GameCompleteError) {
+        throw new ConflictException({
+          statusCode: 409,
+          message: error.message,
+        });
+      } else {
+        throw error;
+      }
+    }
+  }
+
+  @Post(':id/abandon')
+  @HttpCode(200)
+  async abandon(@Param('id') id: GameId): Promise<GameView> {
+    try {
+      const next = await this.gameService.abandonById(id);
+      return serializeGame(next);
+    } catch (error) {
+      if (error instanceof NoRecordError) {
+        throw new NotFoundException();
+      } else if (error instanceof GameCompleteError) {
+        throw new ConflictException({
+          statusCode: 409,
+          message: error.message,
+        });
+      } else {
+        throw error;
+      }
+    }
+  }
+
+  @Delete(':id')
+  @HttpCode(204)
+  async remove(@Param('id') id: GameId): Promise<void> {
+    try {
+      await this.gameService.deleteById(id);
+    } catch (error) {
+      if (error instanceof NoRecordError) {
+        throw new NotFoundException();
+      } else {
+        throw error;
+      }
+    }
+  }
 }
@@ -1,17 +1,69 @@
 import * as io from 'io-ts';
+import { GameStatus } from './game.model';
 import { GameMoveType } from './game-move.model';
+import { MAX_SEED } from './random';
 
 /**
  * Represent the request body for creating a game instance as a runtime
- * verifiable type.
+ * verifiable type. Either the number of `mines` or their `density` can be
+ * given, leaving both out uses the default density. The same `seed`, size and
+ * mines always place the mines in the same cells.
  */
-export const CreateGameDto = io.type({
-  rows: io.number,
-  columns: io.number,
-});
+export const CreateGameDto = io.intersection([
+  io.type({
+    rows: io.number,
+    columns: io.number,
+  }),
+  io.partial({
+    mines: io.number,
+    density: io.number,
+    seed: io.number,
+  }),
+]);
 
 export type CreateGameDto = io.TypeOf<typeof CreateGameDto>;
 
+/**
+ * Describe what is wrong with the mines requested by `data`, if anything. The
+ * number of mines has to leave at least one cell free and the density has to
+ * be between 0 and 1.
+ * @param data requesting a new game.
+ * @returns why the mines can't be placed or `undefined` if they can.
+ */
+export function findInvalidMines(data: CreateGameDto): string | undefined {
+  const { columns, density, mines, rows } = data;
+  if (typeof mines !== 'undefined' && typeof density !== 'undefined') {
+    return 'Only one of mines and density can be given.';
+  } else if (
+    typeof mines !== 'undefined' &&
+    (!Number.isInteger(mines) || mines < 1 || mines >= rows * columns)
+  ) {
+    return `Mines must be between 1 and ${rows * columns - 1}.`;
+  } else if (
+    typeof density !== 'undefined' &&
+    !(density > 0 && density < 1)
+  ) {
+    return 'Density must be greater than 0 and less than 1.';
+  }
+  return undefined;
+}
+
+/**
+ * Describe what is wrong with the seed requested by `data`, if anything.
+ * @param data requesting a new game.
+ * @returns why the seed can't be used or `undefined` if it can.
+ */
+export function findInvalidSeed(data: CreateGameDto): string | undefined {
+  const { seed } = data;
+  if (
+    typeof seed !== 'undefined' &&
+    (!Number.isInteger(seed) || seed < 0 || seed > MAX_SEED)
+  ) {
+    return `Seed must be a whole number between 0 and ${MAX_SEED}.`;
+  }
+  return undefined;
+}
+
 /**
  * Represent the request body for opening a game cell as a runtime verifiable
  * type.
@@ -23,3 +75,30 @@ export const GameMoveDto = io.type({
 });
 
 export type GameMoveDto = io.TypeOf<typeof GameMoveDto>;
+
+/**
+ * Represent the request body for making several moves in a game at once, in
+ * the order they are given, as a runtime verifiable type.
+ */
+export const GameMoveBatchDto = io.type({
+  moves: io.array(GameMoveDto),
+});
+
+export type GameMoveBatchDto = io.TypeOf<typeof GameMoveBatchDto>;
+
+/**
+ * Represent the query for listing a page of game summaries as a runtime
+ * verifiable type.
+ */
+export const GameSummaryQueryDto = io.partial({
+  cursor: io.string,
+  sort: io.keyof({
+    createdAt: null,
+    '-createdAt': null,
+    updatedAt: null,
+    '-updatedAt': null,
+  }),
+  status: io.keyof(GameStatus),
+});
+
+export type GameSummaryQueryDto = io.TypeOf<typeof GameSummaryQueryDto>;
@@ -1,14 +1,38 @@
 import { Injectable } from '@nestjs/common';
-import { NoRecordError } from '../errors';
+import { GameCompleteError, NoRecordError } from '../errors';
 import { GameMoveDto } from './game.dto';
-import { Game, GameId, Props } from './game.model';
-import { BaseGameService } from './game.service';
-import { GameMoveType } from './game-move.model';
+import { Game, GameId, GameStatus, Props } from './game.model';
+import {
+  applyMove,
+  applyMoves,
+  BaseGameService,
+  GameMoveBatch,
+} from './game.service';
 
 @Injectable()
 export class MemGameService implements BaseGameService {
   private readonly games: Game[] = [];
 
+  /**
+   * Find the `Game` associated with `id` and give up on it, which marks it as
+   * lost, return the updated `Game`.
+   * @param id of the record to update.
+   * @returns the abandoned `Game`.
+   * @throws if `id` does not exist.
+   * @throws if `game` is already in a completed state.
+   */
+  async abandonById(id: GameId): Promise<Game> {
+    const current = await this.findById(id);
+    if (typeof current === 'undefined' || current === null) {
+      throw new NoRecordError(id, 'Game');
+    } else if (current.gameStatus !== GameStatus.OPEN) {
+      throw new GameCompleteError(current);
+    }
+    const next = current.abandon();
+    this.updateById(current.id, next);
+    return next;
+  }
+
   /**
    * Find the `Game` associated with `id`, add the move represented by the
    * column and row to be opened, return the updated `Game`.
@@ -23,20 +47,35 @@ export class MemGameService implements BaseGameService {
     if (typeof current === 'undefined' || current === null) {
       throw new NoRecordError(id, 'Game');
     }
-    const { column, row } = move;
-    let next: Game;
-    switch (move.type) {
-      case GameMoveType.FLAG:
-        next = current.flagCoordinates(column, row);
-        break;
-      case GameMoveType.OPEN:
-        next = current.openCoordinates(column, row);
-        break;
-    }
+    const next = applyMove(current, move);
     this.updateById(current.id, next);
     return next;
   }
 
+  /**
+   * Find the `Game` associated with `id` and make each of `moves` in order,
+   * return the updated `Game` with the outcome of each move.
+   * @param id of the record to update.
+   * @param moves containing details about the cells to interact.
+   * @returns the `Game` with the applied moves and the outcome of each move.
+   * @throws if `id` does not exist.
+   * @throws if `game` is already in a completed state.
+   */
+  async addMovesById(
+    id: GameId,
+    moves: GameMoveDto[]
+  ): Promise<GameMoveBatch> {
+    const current = await this.findById(id);
+    if (typeof current === 'undefined' || current === null) {
+      throw new NoRecordError(id, 'Game');
+    } else if (current.gameStatus !== GameStatus.OPEN) {
+      throw new GameCompleteError(current);
+    }
+    const batch = applyMoves(current, moves);
+    this.updateById(current.id, batch.game);
+    return batch;
+  }
+
   /**
    * Create a new record and store it.
    * @param data to be stored.
@@ -48,6 +87,19 @@ export class MemGameService implements BaseGameService {
     return game;
   }
 
+  /**
+   * Remove the record matching the given `id`.
+   * @param id of the record to remove.
+   * @throws if `id` does not exist.
+   */
+  async deleteById(id: GameId): Promise<void> {
+    const gameIndex = this.games.findIndex((game) => game.id === id);
+    if (gameIndex === -1) {
+      throw new NoRecordError(id, 'Game');
+    }
+    this.games.splice(gameIndex, 1);
+  }
+
   /**
    * List all stored Game records.
    * @returns all records.
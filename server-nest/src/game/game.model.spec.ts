@@ -1,6 +1,6 @@
 import { Cell } from './cell.model';
 import { CellView } from './cell.view';
-import { Game, GameStatus } from './game.model';
+import { Game, GameStatus, minesAtDensity } from './game.model';
 
 describe(Game, () => {
   describe(`
@@ -326,6 +326,59 @@ describe('new Game', () => {
   });
 });
 
+describe('new Game with mines', () => {
+  it('places exactly the given number of mines', () => {
+    const game = new Game({ rows: 16, columns: 30, mines: 99 });
+    expect(game.mineCount).toEqual(99);
+  });
+
+  it('can leave a single cell free', () => {
+    const game = new Game({ rows: 2, columns: 2, mines: 3 });
+    expect(game.mineCount).toEqual(3);
+  });
+
+  it('places the same mines for the same seed', () => {
+    const isMine = (game: Game) => game.cells.map((cell) => cell.isMine);
+    const props = { rows: 10, columns: 10, density: 0.3, seed: 1234 };
+    const game = new Game(props);
+    expect(game.seed).toEqual(1234);
+    expect(isMine(new Game(props))).toEqual(isMine(game));
+  });
+
+  it('picks a seed when not given one', () => {
+    const game = new Game({ rows: 2, columns: 2 });
+    expect(Number.isInteger(game.seed)).toBe(true);
+  });
+
+  it('places mines at the given density', () => {
+    const game = new Game({ rows: 10, columns: 10, density: 0.3 });
+    expect(game.mineCount).toEqual(30);
+  });
+
+  it('places one mine for a tiny density', () => {
+    const game = new Game({ rows: 2, columns: 2, density: 1e-7 });
+    expect(game.mineCount).toEqual(1);
+  });
+
+  it('leaves a cell free for a density near 1', () => {
+    const game = new Game({ rows: 2, columns: 2, density: 0.999999 });
+    expect(game.mineCount).toEqual(3);
+  });
+});
+
+describe('minesAtDensity', () => {
+  it('rounds to the nearest count', () => {
+    expect(minesAtDensity(480, 0.25)).toEqual(120);
+    expect(minesAtDensity(10, 0.26)).toEqual(3);
+  });
+
+  it('keeps at least one mine and one free cell', () => {
+    expect(minesAtDensity(4, 1e-7)).toEqual(1);
+    expect(minesAtDensity(4, 0.999999)).toEqual(3);
+    expect(minesAtDensity(1, 0.5)).toEqual(1);
+  });
+});
+
 describe('Game#findCell', () => {
   let cells: Cell[];
   let game: Game;
@@ -752,3 +805,23 @@ describe('Game#unflagCoordinates', () => {
     expect(() => game.unflagCoordinates(0, 4)).toThrowError(/rows/);
   });
 });
+
+describe('Game#abandon', () => {
+  it('is lost', () => {
+    const game = new Game({ rows: 2, columns: 2 });
+    const subject = game.abandon();
+    expect(subject.id).toEqual(game.id);
+    expect(subject.gameStatus).toEqual(GameStatus.LOST);
+  });
+
+  it('keeps the board', () => {
+    const game = new Game({ rows: 2, columns: 2 });
+    expect(game.abandon().board).toEqual(game.board);
+  });
+
+  it('stays lost after a move', () => {
+    const cells = [new Cell({ isMine: false }), new Cell({ isMine: true })];
+    const game = new Game({ rows: 1, columns: 2, cells }).abandon();
+    expect(game.flagCoordinates(1, 0).gameStatus).toEqual(GameStatus.LOST);
+  });
+});
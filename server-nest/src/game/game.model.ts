@@ -1,8 +1,9 @@
 import { v4 as uuid } from 'uuid';
 import { Cell, CellId, pickCellId } from './cell.model';
 import { CellView } from './cell.view';
-import { GameMove, GameMoveType } from './game-move.model';
+import { GameAction, GameMove, GameMoveType } from './game-move.model';
 import { OutOfBoundsException } from './out-of-bounds.exception';
+import { createRandom, randomSeed } from './random';
 
 export enum GameStatus {
   OPEN = 'OPEN',
@@ -10,11 +11,23 @@ export enum GameStatus {
   LOST = 'LOST',
 }
 
-interface GridProps {
+/**
+ * How the mines of a new board are placed, either exactly `mines` of them or
+ * `density` of the cells, rounded to leave at least one mine and one free cell.
+ */
+export interface MineProps {
+  mines?: number;
+  density?: number;
+}
+
+interface GridProps extends MineProps {
   columns: number;
   rows: number;
   moves?: GameMove[];
   id?: string;
+  createdAt?: Date;
+  abandonedAt?: Date;
+  seed?: number;
 }
 
 interface InitialCells extends GridProps {
@@ -29,18 +42,37 @@ export type GameId = string;
 
 export type Props = GridProps | InitialCells | InitialViews;
 
-function generateCells(cellCount: number, mineProbability = 0.25): Cell[] {
-  const cells = new Array(cellCount);
-  for (let i = 0; i < cellCount; i++) {
-    const isMine = Math.random() < mineProbability;
-    cells[i] = new Cell({ isMine });
-  }
-  const hasMine = cells.some((cell) => cell.isMine);
-  if (!hasMine) {
-    return generateCells(cellCount, mineProbability);
-  } else {
-    return cells;
+/**
+ * Share of cells which are mines when neither a count nor density is given.
+ */
+export const DEFAULT_MINE_DENSITY = 0.25;
+
+/**
+ * How many of `cellCount` cells are mines at `density`. There is always at
+ * least one mine and, on boards with more than one cell, one free cell.
+ */
+export function minesAtDensity(cellCount: number, density: number): number {
+  const mines = Math.round(density * cellCount);
+  return Math.max(1, Math.min(cellCount - 1, mines));
+}
+
+function generateCells(
+  cellCount: number,
+  props: MineProps,
+  random: () => number
+): Cell[] {
+  const { density = DEFAULT_MINE_DENSITY } = props;
+  const mines = props.mines ?? minesAtDensity(cellCount, density);
+  // Shuffle the first `mines` positions into place and make those mines
+  const positions = Array.from({ length: cellCount }, (_, index) => index);
+  for (let i = 0; i < mines; i++) {
+    const j = i + Math.floor(random() * (cellCount - i));
+    [positions[i], positions[j]] = [positions[j], positions[i]];
   }
+  const minePositions = new Set(positions.slice(0, mines));
+  return positions.map(
+    (_, index) => new Cell({ isMine: minePositions.has(index) })
+  );
 }
 
 function associateCells(props: InitialCells): void {
@@ -73,14 +105,25 @@ function associateCells(props: InitialCells): void {
 }
 
 export class Game {
+  readonly abandonedAt?: Date;
   readonly columns: number;
+  readonly createdAt: Date;
   readonly id: GameId;
   readonly moves: GameMove[];
   readonly rows: number;
+  /** Seed the mines were placed with, unknown for games created without one. */
+  readonly seed?: number;
   private views: CellView[];
 
   constructor(props: Props) {
-    const { rows, columns, moves = [] } = props;
+    const {
+      abandonedAt,
+      rows,
+      columns,
+      moves = [],
+      createdAt = new Date(),
+    } = props;
+    let seed = props.seed;
     // Assign views based on the contents of props
     if ('views' in props) {
       // InitialViews
@@ -92,9 +135,10 @@ export class Game {
       associateCells({ rows, columns, cells });
       this.views = Game.computeViews(moves, cells);
     } else {
-      // GridProps
+      // GridProps, the same seed always places the same mines
+      seed = seed ?? randomSeed();
       const cellCount = rows * columns;
-      const cells = generateCells(cellCount);
+      const cells = generateCells(cellCount, props, createRandom(seed));
       associateCells({ rows, columns, cells });
       this.views = Game.computeViews([], cells);
     }
@@ -103,9 +147,29 @@ export class Game {
     } else {
       this.id = uuid();
     }
+    this.abandonedAt = abandonedAt;
     this.columns = columns;
+    this.createdAt = createdAt;
     this.moves = moves;
     this.rows = rows;
+    this.seed = seed;
+  }
+
+  /**
+   * Give up on the game, which marks it as lost.
+   * @returns a `Game` which is lost.
+   */
+  abandon(): Game {
+    return new Game({
+      abandonedAt: new Date(),
+      rows: this.rows,
+      columns: this.columns,
+      createdAt: this.createdAt,
+      id: this.id,
+      moves: this.moves,
+      seed: this.seed,
+      views: this.views,
+    });
   }
 
   findCell(column: number, row: number): Cell {
@@ -137,13 +201,17 @@ export class Game {
     return this.views.map((cell) => cell.status);
   }
 
+  get mineCount(): number {
+    return this.cells.filter((cell) => cell.isMine).length;
+  }
+
   get cells(): Cell[] {
     return this.views.map((view) => view.cell);
   }
 
   get gameStatus(): GameStatus {
     // Get the correct result based on boolean reductions
-    if (this.isLost) {
+    if (this.isLost || typeof this.abandonedAt !== 'undefined') {
       return GameStatus.LOST;
     } else if (this.isWon) {
       return GameStatus.WON;
@@ -152,7 +220,8 @@ export class Game {
     }
   }
 
-  private copyWithGameMove(move: GameMove) {
+  private copyWithGameMove(action: GameAction) {
+    const move: GameMove = { ...action, createdAt: new Date() };
     const moves = [...this.moves, move];
     const flaggedCell = this.viewCache[move.cellId];
     if (typeof flaggedCell === 'undefined') {
@@ -160,27 +229,33 @@ export class Game {
     }
     const views = Game.computeViews(moves, this.cells);
     return new Game({
+      abandonedAt: this.abandonedAt,
       rows: this.rows,
       columns: this.columns,
+      createdAt: this.createdAt,
       id: this.id,
       moves,
+      seed: this.seed,
       views,
     });
   }
 
   /**
-   * Given a list of `GameMove` and `Cell` instances create a corresponding
+   * Given a list of `GameAction` and `Cell` instances create a corresponding
    * list of `CellView` representing the state of play.
    * @param moves used to determine the view of each `Cell`.
    * @param cells to be wrapped in `CellView`
    * @returns list of `CellView` instances representing the game state for the
    * `moves` and `cells`.
    */
-  private static computeViews(moves: GameMove[], cells: Cell[]): CellView[] {
-    const initialFlaggedMoves: GameMove[] = cells
+  private static computeViews(
+    moves: GameAction[],
+    cells: Cell[]
+  ): CellView[] {
+    const initialFlaggedMoves: GameAction[] = cells
       .filter((cell) => cell.initialState.isFlagged)
       .map((cell) => ({ type: GameMoveType.FLAG, cellId: cell.id }));
-    const initialOpenedMoves: GameMove[] = cells
+    const initialOpenedMoves: GameAction[] = cells
       .filter((cell) => cell.initialState.isOpen)
       .map((cell) => ({ type: GameMoveType.OPEN, cellId: cell.id }));
     const allMoves = initialFlaggedMoves.concat(initialOpenedMoves, moves);
@@ -217,12 +292,12 @@ export class Game {
   }
 
   /**
-   * Given a list of `GameMove` find the list of `CellId` which should have a
+   * Given a list of `GameAction` find the list of `CellId` which should have a
    * flagged state.
    * @param moves to analyze.
    * @returns the list of `CellId` corresponding to flagged `Cell`.
    */
-  private static getFlaggedCellIds(moves: GameMove[]): CellId[] {
+  private static getFlaggedCellIds(moves: GameAction[]): CellId[] {
     const flagged = moves.reduce((flaggedCells, move) => {
       switch (move.type) {
         case GameMoveType.FLAG:
@@ -252,12 +327,12 @@ export class Game {
   }
 
   /**
-   * Given a list of `GameMove` find the list of `CellId` which should have an
+   * Given a list of `GameAction` find the list of `CellId` which should have an
    * open state.
    * @param moves to analyze.
    * @returns the list of `CellId` corresponding to open `Cell`.
    */
-  private static getOpenedCellIds(moves: GameMove[]): CellId[] {
+  private static getOpenedCellIds(moves: GameAction[]): CellId[] {
     return moves
       .filter((move) => move.type === GameMoveType.OPEN)
       .map((move) => move.cellId);
@@ -4,14 +4,14 @@ import { v4 as uuid } from 'uuid';
 import { GameCompleteError, NoRecordError } from '../errors';
 import { Cell } from './cell.model';
 import { GameMoveDto } from './game.dto';
-import { Game } from './game.model';
+import { Game, GameStatus } from './game.model';
 import {
   GameService,
   GameCellRecord,
   GameMoveRecord,
   GameRecord,
 } from './game.service';
-import { GameMove, GameMoveType } from './game-move.model';
+import { GameMove, GameMoveOutcome, GameMoveType } from './game-move.model';
 
 const mockClient = {
   query: jest.fn(),
@@ -210,7 +210,11 @@ describe('GameService', () => {
             genGameRecord({
               id: game.id,
               moves: [
-                { cell_id: game.cells[0].id, move_type: GameMoveType.FLAG },
+                {
+                  cell_id: game.cells[0].id,
+                  created_at: new Date().toISOString(),
+                  move_type: GameMoveType.FLAG,
+                },
               ],
             }),
           ],
@@ -463,6 +467,7 @@ describe('GameService', () => {
                 moves: [
                   {
                     cell_id: gameRecord.cells.find((cell) => !cell.is_mine).id,
+                    created_at: new Date().toISOString(),
                     move_type: GameMoveType[move.type],
                   },
                 ],
@@ -480,6 +485,7 @@ describe('GameService', () => {
             ...makeGameRecord(game),
             moves: game.cells.map((cell) => ({
               cell_id: cell.id,
+              created_at: new Date().toISOString(),
               move_type: GameMoveType.OPEN,
             })),
           };
@@ -557,6 +563,205 @@ describe('GameService', () => {
       expect(result.board[0]).not.toEqual(game.board[0]);
     });
   });
+
+  describe('#abandonById', () => {
+    it('throws for unknown id', async () => {
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 0,
+          rows: [],
+        });
+      try {
+        await service.abandonById(uuid());
+        fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NoRecordError);
+      }
+    });
+
+    it('throws for an abandoned game', async () => {
+      const game = new Game({ columns: 2, rows: 2 }).abandon();
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 1,
+          rows: [makeGameRecord(game)],
+        });
+      try {
+        await service.abandonById(game.id);
+        fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).toBeInstanceOf(GameCompleteError);
+      }
+    });
+
+    it('marks the game as lost', async () => {
+      const game = new Game({ columns: 2, rows: 2 });
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 1,
+          rows: [makeGameRecord(game)],
+        })
+        // UPDATE game
+        .mockResolvedValueOnce({
+          rowCount: 1,
+          rows: [{ id: game.id }],
+        });
+      const result = await service.abandonById(game.id);
+      expect(result.gameStatus).toEqual(GameStatus.LOST);
+      expect(result.abandonedAt).toBeDefined();
+      expect(mockClient.query).toHaveBeenCalledTimes(2);
+    });
+  });
+
+  describe('#deleteById', () => {
+    it('throws for unknown id', async () => {
+      mockClient.query
+        .mockReset()
+        // BEGIN
+        .mockImplementationOnce(() => Promise.resolve())
+        // DELETE FROM game_move
+        .mockResolvedValueOnce({ rowCount: 0, rows: [] })
+        // DELETE FROM game_cell
+        .mockResolvedValueOnce({ rowCount: 0, rows: [] })
+        // DELETE FROM game
+        .mockResolvedValueOnce({ rowCount: 0, rows: [] })
+        // ROLLBACK
+        .mockImplementationOnce(() => Promise.resolve());
+      try {
+        await service.deleteById(uuid());
+        fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NoRecordError);
+      }
+      expect(mockClient.query).toHaveBeenCalledTimes(5);
+    });
+
+    it('removes the game', async () => {
+      const id = uuid();
+      mockClient.query
+        .mockReset()
+        // BEGIN
+        .mockImplementationOnce(() => Promise.resolve())
+        // DELETE FROM game_move
+        .mockResolvedValueOnce({ rowCount: 1, rows: [] })
+        // DELETE FROM game_cell
+        .mockResolvedValueOnce({ rowCount: 4, rows: [] })
+        // DELETE FROM game
+        .mockResolvedValueOnce({ rowCount: 1, rows: [{ id }] })
+        // COMMIT
+        .mockImplementationOnce(() => Promise.resolve());
+      await service.deleteById(id);
+      expect(mockClient.query).toHaveBeenCalledTimes(5);
+    });
+  });
+
+  describe('#addMovesById', () => {
+    const open = (column: number): GameMoveDto => ({
+      column,
+      row: 0,
+      type: GameMoveType.OPEN,
+    });
+
+    it('throws for unknown id', async () => {
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 0,
+          rows: [],
+        });
+      try {
+        await service.addMovesById(uuid(), [open(0)]);
+        fail('Should have thrown an error');
+      } catch (error) {
+        expect(error).toBeInstanceOf(NoRecordError);
+      }
+    });
+
+    it('rejects moves out of bounds', async () => {
+      const game = new Game({
+        columns: 2,
+        rows: 1,
+        cells: [new Cell({ isMine: false }), new Cell({ isMine: true })],
+      });
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 1,
+          rows: [makeGameRecord(game)],
+        })
+        // BEGIN
+        .mockImplementationOnce(() => Promise.resolve())
+        // INSERT INTO game_move
+        .mockImplementationOnce(() =>
+          Promise.resolve({
+            rowCount: 1,
+            rows: [{ move_id: uuid() }],
+          })
+        )
+        // COMMIT
+        .mockImplementationOnce(() => Promise.resolve());
+      const result = await service.addMovesById(game.id, [open(3), open(0)]);
+      expect(result.outcomes).toEqual([
+        GameMoveOutcome.REJECTED,
+        GameMoveOutcome.APPLIED,
+      ]);
+      expect(result.game.moves).toHaveLength(1);
+    });
+
+    it('rejects moves after the game is complete', async () => {
+      const game = new Game({
+        columns: 2,
+        rows: 1,
+        cells: [new Cell({ isMine: false }), new Cell({ isMine: true })],
+      });
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 1,
+          rows: [makeGameRecord(game)],
+        })
+        // BEGIN
+        .mockImplementationOnce(() => Promise.resolve())
+        // INSERT INTO game_move
+        .mockImplementationOnce(() =>
+          Promise.resolve({
+            rowCount: 1,
+            rows: [{ move_id: uuid() }],
+          })
+        )
+        // COMMIT
+        .mockImplementationOnce(() => Promise.resolve());
+      const result = await service.addMovesById(game.id, [open(1), open(0)]);
+      expect(result.outcomes).toEqual([
+        GameMoveOutcome.APPLIED,
+        GameMoveOutcome.REJECTED,
+      ]);
+      expect(result.game.gameStatus).toEqual(GameStatus.LOST);
+    });
+
+    it('stores nothing when no move is applied', async () => {
+      const game = new Game({ columns: 2, rows: 1 });
+      mockClient.query
+        .mockReset()
+        // SELECT FROM game (findById)
+        .mockResolvedValueOnce({
+          rowCount: 1,
+          rows: [makeGameRecord(game)],
+        });
+      const result = await service.addMovesById(game.id, [open(3)]);
+      expect(result.outcomes).toEqual([GameMoveOutcome.REJECTED]);
+      expect(mockClient.query).toHaveBeenCalledTimes(1);
+    });
+  });
 });
 
 const genGameCellRecord = (
@@ -575,6 +780,9 @@ const genGameRecord = (options?: Partial<GameRecord>): GameRecord => {
     id: options?.id ?? uuid(),
     cells,
     columns,
+    created_at: options?.created_at ?? new Date().toISOString(),
+    abandoned_at: options?.abandoned_at ?? null,
+    seed: options?.seed ?? null,
     rows,
     moves: options?.moves ?? [],
   };
@@ -587,6 +795,7 @@ const makeGameCellRecord = (cell: Cell): GameCellRecord => ({
 
 const makeGameMoveRecord = (move: GameMove): GameMoveRecord => ({
   cell_id: move.cellId,
+  created_at: move.createdAt.toISOString(),
   move_type: move.type,
 });
 
@@ -594,6 +803,9 @@ const makeGameRecord = (game: Game): GameRecord => ({
   id: game.id,
   cells: game.cells.map((c) => makeGameCellRecord(c)),
   columns: game.columns,
+  created_at: game.createdAt.toISOString(),
+  abandoned_at: game.abandonedAt?.toISOString() ?? null,
+  seed: game.seed?.toString() ?? null,
   moves: game.moves.map((m) => makeGameMoveRecord(m)),
   rows: game.rows,
 });
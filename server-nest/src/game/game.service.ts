@@ -5,7 +5,8 @@ import { GameCompleteError, NoRecordError } from '../errors';
 import { Cell } from './cell.model';
 import { GameMoveDto } from './game.dto';
 import { Game, GameId, GameStatus, Props } from './game.model';
-import { GameMoveType } from './game-move.model';
+import { GameMove, GameMoveOutcome, GameMoveType } from './game-move.model';
+import { OutOfBoundsException } from './out-of-bounds.exception';
 
 /**
  * Throws with the given message if called, intended for use in exhaustiveness
@@ -15,7 +16,83 @@ function assertNever(message: string): never {
   throw new Error(message);
 }
 
+/**
+ * Result of making several moves in a `Game` at once.
+ */
+export interface GameMoveBatch {
+  /** The `Game` with every applied move. */
+  game: Game;
+  /** Whether each move, in the order they were given, was applied. */
+  outcomes: GameMoveOutcome[];
+}
+
+/**
+ * Apply `move` to `game`.
+ * @param game to which the move is applied.
+ * @param move containing details about the cell to interact.
+ * @returns a `Game` with the move applied.
+ * @throws if `(column, row)` can not be changed.
+ */
+export function applyMove(game: Game, move: GameMoveDto): Game {
+  const { column, row } = move;
+  switch (move.type) {
+    case GameMoveType.FLAG:
+      return game.flagCoordinates(column, row);
+    case GameMoveType.OPEN:
+      return game.openCoordinates(column, row);
+    case GameMoveType.REMOVE_FLAG:
+      return game.unflagCoordinates(column, row);
+    default:
+      return assertNever(`Unhandled move type ${move.type}`);
+  }
+}
+
+/**
+ * Apply each of `moves` to `game` in order. Moves outside of the grid, and
+ * every move after the one which completes the game, are rejected.
+ * @param game to which the moves are applied.
+ * @param moves containing details about the cells to interact.
+ * @returns the `Game` with the applied moves and the outcome of each move.
+ */
+export function applyMoves(game: Game, moves: GameMoveDto[]): GameMoveBatch {
+  return moves.reduce<GameMoveBatch>(
+    (batch, move) => {
+      if (batch.game.gameStatus !== GameStatus.OPEN) {
+        return {
+          game: batch.game,
+          outcomes: [...batch.outcomes, GameMoveOutcome.REJECTED],
+        };
+      }
+      try {
+        return {
+          game: applyMove(batch.game, move),
+          outcomes: [...batch.outcomes, GameMoveOutcome.APPLIED],
+        };
+      } catch (error) {
+        if (!(error instanceof OutOfBoundsException)) {
+          throw error;
+        }
+        return {
+          game: batch.game,
+          outcomes: [...batch.outcomes, GameMoveOutcome.REJECTED],
+        };
+      }
+    },
+    { game, outcomes: [] }
+  );
+}
+
 export interface BaseGameService {
+  /**
+   * Find the `Game` associated with `id` and give up on it, which marks it as
+   * lost, return the updated `Game`.
+   * @param id of the record to update.
+   * @returns the abandoned `Game`.
+   * @throws if `id` does not exist.
+   * @throws if `game` is already in a completed state.
+   */
+  abandonById(id: GameId): Promise<Game>;
+
   /**
    * Find the `Game` associated with `id`, add the move represented by the
    * column and row to be opened, return the updated `Game`.
@@ -27,6 +104,17 @@ export interface BaseGameService {
    */
   addMoveById(id: GameId, move: GameMoveDto): Promise<Game>;
 
+  /**
+   * Find the `Game` associated with `id` and make each of `moves` in order,
+   * return the updated `Game` with the outcome of each move.
+   * @param id of the record to update.
+   * @param moves containing details about the cells to interact.
+   * @returns the `Game` with the applied moves and the outcome of each move.
+   * @throws if `id` does not exist.
+   * @throws if `game` is already in a completed state.
+   */
+  addMovesById(id: GameId, moves: GameMoveDto[]): Promise<GameMoveBatch>;
+
   /**
    * Create a new record and store it.
    * @param data to be stored.
@@ -34,6 +122,13 @@ export interface BaseGameService {
    */
   create(data: Omit<Props, 'id'>): Promise<Game>;
 
+  /**
+   * Remove the record matching the given `id` along with its moves.
+   * @param id of the record to remove.
+   * @throws if `id` does not exist.
+   */
+  deleteById(id: GameId): Promise<void>;
+
   /**
    * List all stored Game records.
    * @returns all records.
@@ -53,64 +148,89 @@ export class GameService implements BaseGameService {
   constructor(@Inject('DB_POOL') private readonly pool: Pool) {}
 
   /**
-   * Find the `Game` associated with `id`, add the move represented by the
-   * column and row to be opened, return the updated `Game`.
+   * Find the `Game` associated with `id` and give up on it, which marks it as
+   * lost, return the updated `Game`.
    * @param id of the record to update.
-   * @param move containing details about the cell to interact.
-   * @returns a `Game` with the move applied.
+   * @returns the abandoned `Game`.
    * @throws if `id` does not exist.
-   * @throws if `(column, row)` can not be changed.
    * @throws if `game` is already in a completed state.
    */
-  async addMoveById(id: GameId, move: GameMoveDto): Promise<Game> {
+  async abandonById(id: GameId): Promise<Game> {
     const current = await this.findById(id);
     if (typeof current === 'undefined' || current === null) {
       throw new NoRecordError(id, 'Game');
     } else if (current.gameStatus !== GameStatus.OPEN) {
       throw new GameCompleteError(current);
     }
-    const { column, row } = move;
-    const cell = current.findCell(column, row);
+    const next = current.abandon();
     const client = await this.pool.connect();
     try {
-      await client.query(SQL`BEGIN`);
-      const moveIds = await client.query<{ move_id: string }>(SQL`
-        INSERT
-        INTO game_move (cell_id, game_id, move_type)
-        VALUES (${cell.id}, ${current.id}, ${move.type})
-        RETURNING move_id
+      const gameIds = await client.query<Pick<GameRecord, 'id'>>(SQL`
+        UPDATE game
+        SET abandoned_at = ${next.abandonedAt}
+        WHERE game_id = ${id}
+        RETURNING game_id AS id
       `);
-      if (moveIds.rowCount !== 1) {
+      if (gameIds.rowCount !== 1) {
         throw new Error(
-          `Wrong number of records when inserting move: ${moveIds.rowCount}`
+          `Wrong number of records when abandoning Game: ${gameIds.rowCount}`
         );
       }
-      await client.query(SQL`COMMIT`);
-    } catch (error) {
-      await client.query(SQL`ROLLBACK`);
-      // Rethrow whatever error caused the rollback
-      throw error;
     } finally {
       client.release();
     }
-    let next: Game;
-    switch (move.type) {
-      case GameMoveType.FLAG:
-        next = current.flagCoordinates(column, row);
-        break;
-      case GameMoveType.OPEN:
-        next = current.openCoordinates(column, row);
-        break;
-      case GameMoveType.REMOVE_FLAG:
-        next = current.unflagCoordinates(column, row);
-        break;
-      default:
-        next = assertNever(`Unhandled move type ${move.type}`);
-        break;
+    return next;
+  }
+
+  /**
+   * Find the `Game` associated with `id`, add the move represented by the
+   * column and row to be opened, return the updated `Game`.
+   * @param id of the record to update.
+   * @param move containing details about the cell to interact.
+   * @returns a `Game` with the move applied.
+   * @throws if `id` does not exist.
+   * @throws if `(column, row)` can not be changed.
+   * @throws if `game` is already in a completed state.
+   */
+  async addMoveById(id: GameId, move: GameMoveDto): Promise<Game> {
+    const current = await this.findById(id);
+    if (typeof current === 'undefined' || current === null) {
+      throw new NoRecordError(id, 'Game');
+    } else if (current.gameStatus !== GameStatus.OPEN) {
+      throw new GameCompleteError(current);
     }
+    const next = applyMove(current, move);
+    await this.insertMoves(next.moves.slice(current.moves.length), current.id);
     return next;
   }
 
+  /**
+   * Find the `Game` associated with `id` and make each of `moves` in order,
+   * return the updated `Game` with the outcome of each move.
+   * @param id of the record to update.
+   * @param moves containing details about the cells to interact.
+   * @returns the `Game` with the applied moves and the outcome of each move.
+   * @throws if `id` does not exist.
+   * @throws if `game` is already in a completed state.
+   */
+  async addMovesById(
+    id: GameId,
+    moves: GameMoveDto[]
+  ): Promise<GameMoveBatch> {
+    const current = await this.findById(id);
+    if (typeof current === 'undefined' || current === null) {
+      throw new NoRecordError(id, 'Game');
+    } else if (current.gameStatus !== GameStatus.OPEN) {
+      throw new GameCompleteError(current);
+    }
+    const batch = applyMoves(current, moves);
+    const applied = batch.game.moves.slice(current.moves.length);
+    if (applied.length > 0) {
+      await this.insertMoves(applied, current.id);
+    }
+    return batch;
+  }
+
   /**
    * Create a new record and store it.
    * @param data to be stored.
@@ -123,8 +243,14 @@ export class GameService implements BaseGameService {
       await client.query(SQL`BEGIN`);
       const gameIds = await client.query<Pick<GameRecord, 'id'>>(SQL`
         INSERT
-        INTO game (game_id, column_count, row_count)
-        VALUES (${game.id}, ${data.columns}, ${data.rows})
+        INTO game (game_id, column_count, row_count, created_at, seed)
+        VALUES (
+          ${game.id},
+          ${data.columns},
+          ${data.rows},
+          ${game.createdAt},
+          ${game.seed}
+        )
         RETURNING game_id AS id
       `);
       if (gameIds.rowCount !== 1) {
@@ -172,6 +298,36 @@ export class GameService implements BaseGameService {
     return game;
   }
 
+  /**
+   * Remove the record matching the given `id` along with its cells and moves.
+   * @param id of the record to remove.
+   * @throws if `id` does not exist.
+   */
+  async deleteById(id: GameId): Promise<void> {
+    const client = await this.pool.connect();
+    try {
+      await client.query(SQL`BEGIN`);
+      await client.query(SQL`DELETE FROM game_move WHERE game_id = ${id}`);
+      await client.query(SQL`DELETE FROM game_cell WHERE game_id = ${id}`);
+      const gameIds = await client.query<Pick<GameRecord, 'id'>>(SQL`
+        DELETE
+        FROM game
+        WHERE game_id = ${id}
+        RETURNING game_id AS id
+      `);
+      if (gameIds.rowCount !== 1) {
+        throw new NoRecordError(id, 'Game');
+      }
+      await client.query(SQL`COMMIT`);
+    } catch (error) {
+      await client.query(SQL`ROLLBACK`);
+      // Rethrow whatever error caused the rollback
+      throw error;
+    } finally {
+      client.release();
+    }
+  }
+
   /**
    * List all stored Game records.
    * @returns all records.
@@ -184,6 +340,9 @@ export class GameService implements BaseGameService {
           game.game_id AS id,
           game.column_count AS columns,
           game.row_count AS rows,
+          game.created_at AS created_at,
+          game.abandoned_at AS abandoned_at,
+          game.seed AS seed,
           coalesce(game_moves.moves, json_build_array()) AS moves,
           coalesce(game_cells.cells, json_build_array()) AS cells
         FROM game
@@ -196,11 +355,17 @@ export class GameService implements BaseGameService {
             id: row.id,
             columns: row.columns,
             rows: row.rows,
+            createdAt: new Date(row.created_at),
+            abandonedAt: row.abandoned_at
+              ? new Date(row.abandoned_at)
+              : undefined,
+            seed: row.seed === null ? undefined : Number(row.seed),
             cells: row.cells.map(
               (r) => new Cell({ id: r.id, isMine: r.is_mine })
             ),
             moves: row.moves.map((r) => ({
               cellId: r.cell_id,
+              createdAt: new Date(r.created_at),
               type: r.move_type,
             })),
           })
@@ -223,6 +388,9 @@ export class GameService implements BaseGameService {
           game.game_id AS id,
           game.column_count AS columns,
           game.row_count AS rows,
+          game.created_at AS created_at,
+          game.abandoned_at AS abandoned_at,
+          game.seed AS seed,
           coalesce(game_moves.moves, json_build_array()) AS moves,
           coalesce(game_cells.cells, json_build_array()) AS cells
         FROM game
@@ -241,11 +409,17 @@ export class GameService implements BaseGameService {
             id: row.id,
             columns: row.columns,
             rows: row.rows,
+            createdAt: new Date(row.created_at),
+            abandonedAt: row.abandoned_at
+              ? new Date(row.abandoned_at)
+              : undefined,
+            seed: row.seed === null ? undefined : Number(row.seed),
             cells: row.cells.map(
               (r) => new Cell({ id: r.id, isMine: r.is_mine })
             ),
             moves: row.moves.map((r) => ({
               cellId: r.cell_id,
+              createdAt: new Date(r.created_at),
               type: r.move_type,
             })),
           })
@@ -255,12 +429,57 @@ export class GameService implements BaseGameService {
       client.release();
     }
   }
+
+  /**
+   * Store `moves` made in the game identified by `gameId`, in order, in a
+   * single transaction.
+   * @param moves to be stored.
+   * @param gameId of the game in which the moves were made.
+   */
+  private async insertMoves(
+    moves: GameMove[],
+    gameId: GameId
+  ): Promise<void> {
+    const client = await this.pool.connect();
+    try {
+      await client.query(SQL`BEGIN`);
+      const moveInsertRaw = `
+        INSERT
+        INTO game_move (cell_id, game_id, move_type)
+        VALUES ${moves.map(
+          (_, index) =>
+            `($${3 * index + 1}, $${3 * index + 2}, $${3 * index + 3})`
+        )}
+        RETURNING move_id
+      `;
+      const moveIds = await client.query<{ move_id: string }>({
+        text: moveInsertRaw,
+        values: moves.flatMap((move) => [move.cellId, gameId, move.type]),
+      });
+      if (moveIds.rowCount !== moves.length) {
+        throw new Error(
+          `Wrong number of records when inserting moves. Actual: ${moveIds.rowCount} Expected: ${moves.length}`
+        );
+      }
+      await client.query(SQL`COMMIT`);
+    } catch (error) {
+      await client.query(SQL`ROLLBACK`);
+      // Rethrow whatever error caused the rollback
+      throw error;
+    } finally {
+      client.release();
+    }
+  }
 }
 
 export type GameRecord = {
   id: string;
   columns: number;
   rows: number;
+  created_at: string;
+  abandoned_at: string | null;
+  /** A bigint, which is read as a string. */
+  seed: string | null;
   cells: GameCellRecord[];
   moves: GameMoveRecord[];
 };
@@ -272,5 +491,6 @@ export type GameCellRecord = {
 
 export type GameMoveRecord = {
   cell_id: string;
+  created_at: string;
   move_type: GameMoveType;
 };
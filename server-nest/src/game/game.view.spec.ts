@@ -58,6 +58,10 @@ describe(serializeGame, () => {
       subject = serializeGame(game);
     });
 
+    it('counts the mines', () => {
+      expect(subject).toHaveProperty('mines', 4);
+    });
+
     it('has a matching id', () => {
       expect(subject).toHaveProperty('id', game.id);
     });
@@ -100,7 +104,13 @@ describe(serializeGame, () => {
         rows: 2,
         columns: 2,
         cells,
-        moves: [{ type: GameMoveType.OPEN, cellId: cells[0].id }],
+        moves: [
+          {
+            type: GameMoveType.OPEN,
+            cellId: cells[0].id,
+            createdAt: new Date(),
+          },
+        ],
       });
       subject = serializeGame(game);
     });
@@ -1,8 +1,12 @@
 import { Game, GameId, GameStatus } from './game.model';
+import { GameMoveBatch } from './game.service';
+import { GameMoveOutcome } from './game-move.model';
 
 export interface GameView {
   board: string[][];
   id: GameId;
+  mines: number;
+  seed?: number;
   status: GameStatus;
 }
 
@@ -21,6 +25,22 @@ export function serializeGame(game: Game): GameView {
   return {
     board,
     id: game.id,
+    mines: game.mineCount,
+    seed: game.seed,
     status: game.gameStatus,
   };
 }
+
+export interface GameMoveBatchView {
+  game: GameView;
+  outcomes: GameMoveOutcome[];
+}
+
+export function serializeGameMoveBatch(
+  batch: GameMoveBatch
+): GameMoveBatchView {
+  return {
+    game: serializeGame(batch.game),
+    outcomes: batch.outcomes,
+  };
+}
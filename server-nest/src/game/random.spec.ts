@@ -0,0 +1,31 @@
+import { createRandom, MAX_SEED, randomSeed } from './random';
+
+describe(createRandom, () => {
+  const take = (random: () => number) => [random(), random(), random()];
+
+  it('repeats for the same seed', () => {
+    expect(take(createRandom(1234))).toEqual(take(createRandom(1234)));
+  });
+
+  it('differs for another seed', () => {
+    expect(take(createRandom(1234))).not.toEqual(take(createRandom(4321)));
+  });
+
+  it('stays within [0, 1)', () => {
+    const random = createRandom(MAX_SEED);
+    for (let i = 0; i < 100; i++) {
+      const value = random();
+      expect(value).toBeGreaterThanOrEqual(0);
+      expect(value).toBeLessThan(1);
+    }
+  });
+});
+
+describe(randomSeed, () => {
+  it('is a whole number within range', () => {
+    const seed = randomSeed();
+    expect(Number.isInteger(seed)).toBe(true);
+    expect(seed).toBeGreaterThanOrEqual(0);
+    expect(seed).toBeLessThanOrEqual(MAX_SEED);
+  });
+});
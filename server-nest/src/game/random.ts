@@ -0,0 +1,28 @@
+/** Largest seed a game can be created with. */
+export const MAX_SEED = 0xffffffff;
+
+/**
+ * Create a generator of numbers in `[0, 1)`, like `Math.random`, which always
+ * produces the same numbers for the same `seed`. Uses the mulberry32
+ * algorithm.
+ * @param seed between 0 and `MAX_SEED`.
+ * @returns the generator.
+ */
+export function createRandom(seed: number): () => number {
+  let state = seed >>> 0;
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+/**
+ * Pick a seed for a game which wasn't given one.
+ * @returns a seed between 0 and `MAX_SEED`.
+ */
+export function randomSeed(): number {
+  return Math.floor(Math.random() * (MAX_SEED + 1));
+}
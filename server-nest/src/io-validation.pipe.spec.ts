@@ -99,6 +99,26 @@ describe(IoValidationPipe, () => {
   });
 });
 
+describe('Query Pipe', () => {
+  const pipe = new IoValidationPipe(io.partial({ x: io.string }), 'query');
+
+  it('skips if not query', () => {
+    expect(pipe.transform(INVALID, { type: 'body' })).toBe(INVALID);
+  });
+
+  it('parses if query', () => {
+    expect(pipe.transform({ x: 'foo' }, { type: 'query' })).toEqual({
+      x: 'foo',
+    });
+  });
+
+  it('throws for an invalid query', () => {
+    expect(() => pipe.transform(INVALID, { type: 'query' })).toThrow(
+      BadRequestException
+    );
+  });
+});
+
 describe('Fail Pipe', () => {
   const FailString = new io.Type<string, string, unknown>(
     'string',
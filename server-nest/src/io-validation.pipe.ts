@@ -2,6 +2,7 @@ import {
   ArgumentMetadata,
   BadRequestException,
   Injectable,
+  Paramtype,
   PipeTransform,
 } from '@nestjs/common';
 import { Errors, Type, ValidationError } from 'io-ts';
@@ -31,10 +32,14 @@ function getErrorKeys(errors: Errors): string {
  * `guard`.
  * @param T representing the expected result.
  * @param guard specified with `io-ts` types.
+ * @param paramType of the values to validate, other values are skipped.
  */
 @Injectable()
 export class IoValidationPipe<T> implements PipeTransform {
-  constructor(private readonly decoder: Type<T>) {}
+  constructor(
+    private readonly decoder: Type<T>,
+    private readonly paramType: Paramtype = 'body'
+  ) {}
 
   /**
    * Attempts to create a `T` from the given `unknown` `value`.
@@ -70,6 +75,6 @@ export class IoValidationPipe<T> implements PipeTransform {
     _value: unknown,
     meta: ArgumentMetadata
   ): _value is T {
-    return meta.type !== 'body';
+    return meta.type !== this.paramType;
   }
 }
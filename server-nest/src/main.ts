@@ -8,7 +8,7 @@ const port = process.env.PORT || 3000;
 
 async function bootstrap(): Promise<void> {
   const app = await NestFactory.create(AppModule);
-  app.enableCors();
+  app.enableCors({ exposedHeaders: ['ETag'] });
   await app.listen(port);
   Logger.debug(`Started on port :${port}`);
 }
@@ -1,5 +1,5 @@
 /// Defines the possible values of a cell on the board.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BoardValue {
     /// An unopened cell, waiting for interaction
     Closed,
@@ -34,7 +34,7 @@ impl std::str::FromStr for BoardValue {
             "M" => Ok(BoardValue::Mine),
             "F" => Ok(BoardValue::Flag),
             "*" => Ok(BoardValue::Pending),
-            current => match usize::from_str(&current) {
+            current => match usize::from_str(current) {
                 Ok(v) => Ok(BoardValue::Open(v)),
                 _ => Err(BoardValueConvertError::ExpectedNeighborCount),
             },
@@ -42,6 +42,15 @@ impl std::str::FromStr for BoardValue {
     }
 }
 
+impl serde::ser::Serialize for BoardValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 impl<'de> serde::de::Deserialize<'de> for BoardValue {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -64,3 +73,47 @@ pub enum BoardValueConvertError {
     /// Tried to parse as a count of neighbor mines but failed
     ExpectedNeighborCount,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn parses_known_values() {
+        assert_eq!(BoardValue::from_str("").unwrap(), BoardValue::Closed);
+        assert_eq!(BoardValue::from_str(" ").unwrap(), BoardValue::Closed);
+        assert_eq!(BoardValue::from_str("F").unwrap(), BoardValue::Flag);
+        assert_eq!(BoardValue::from_str("M").unwrap(), BoardValue::Mine);
+        assert_eq!(BoardValue::from_str("*").unwrap(), BoardValue::Pending);
+        assert_eq!(BoardValue::from_str("3").unwrap(), BoardValue::Open(3));
+    }
+
+    #[test]
+    fn rejects_unknown_values() {
+        assert!(BoardValue::from_str("X").is_err());
+        assert!(BoardValue::from_str("-1").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let values = vec![
+            BoardValue::Closed,
+            BoardValue::Flag,
+            BoardValue::Mine,
+            BoardValue::Open(0),
+            BoardValue::Open(8),
+            BoardValue::Pending,
+        ];
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(json, r#"[" ","F","M","0","8","*"]"#);
+        let parsed: Vec<BoardValue> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, values);
+    }
+
+    #[test]
+    fn fails_to_deserialize_unknown_value() {
+        let parsed: Result<BoardValue, _> = serde_json::from_str(r#""X""#);
+        assert!(parsed.is_err());
+    }
+}
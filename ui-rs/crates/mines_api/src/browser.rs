@@ -0,0 +1,11 @@
+//! Code shared by every Rust UI which talks to the browser rather than describing the API. Only
+//! built with the `browser` feature.
+
+pub mod auth;
+pub mod config;
+pub mod fetch;
+pub mod input;
+
+fn window() -> web_sys::Window {
+    web_sys::window().expect("no global `window` exists")
+}
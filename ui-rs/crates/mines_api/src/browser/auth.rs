@@ -0,0 +1,59 @@
+use super::window;
+use crate::AuthToken;
+use std::cell::RefCell;
+
+/// Key of the `localStorage` entry holding the `AuthToken` between page loads.
+const STORAGE_KEY: &str = "mines:auth-token";
+
+thread_local! {
+    static TOKEN: RefCell<Option<AuthToken>> = RefCell::new(load());
+}
+
+/// The `AuthToken` for the logged in user, if there is one which hasn't expired.
+pub fn token() -> Option<AuthToken> {
+    let now = (js_sys::Date::now() / 1000.0) as u64;
+    let token = TOKEN.with(|current| current.borrow().clone());
+    match token {
+        Some(token) if token.is_expired(now) => {
+            clear();
+            None
+        }
+        token => token,
+    }
+}
+
+/// Store `token` so it is sent with all subsequent API requests, including after a reload. The
+/// token is kept for this page even if it can't be saved, the reason it couldn't is returned.
+pub fn set(token: AuthToken) -> Result<(), String> {
+    let saved = serde_json::to_string(&token)
+        .map_err(|error| error.to_string())
+        .and_then(|json| match storage() {
+            Some(storage) => storage
+                .set_item(STORAGE_KEY, &json)
+                .map_err(|error| format!("{:?}", error)),
+            None => Ok(()),
+        });
+    TOKEN.with(|current| current.replace(Some(token)));
+    saved
+}
+
+/// Forget the `AuthToken`, logging the user out.
+pub fn clear() {
+    if let Some(storage) = storage() {
+        let _ = storage.remove_item(STORAGE_KEY);
+    }
+    TOKEN.with(|current| current.replace(None));
+}
+
+fn load() -> Option<AuthToken> {
+    let json = storage()?.get_item(STORAGE_KEY).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    // There is no `window` outside of the browser, e.g. when running tests
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    window().local_storage().ok().flatten()
+}
@@ -0,0 +1,103 @@
+use super::window;
+use crate::{Config, ConfigError, FetchError};
+use std::cell::RefCell;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Path, relative to the page, of the optional configuration file.
+const CONFIG_PATH: &str = "/config.json";
+/// Name of the `<meta>` tag whose `content` holds the API base URL.
+const META_NAME: &str = "mines:api-base-url";
+/// Name of the global object holding configuration set by the hosting page.
+const WINDOW_KEY: &str = "__MINES_CONFIG__";
+
+thread_local! {
+    static CONFIG: RefCell<Option<Config>> = RefCell::new(None);
+}
+
+/// Resolve the `Config` for this page. Sources are checked in order and the first one present is
+/// used:
+///
+/// 1. `<meta name="mines:api-base-url" content="...">`
+/// 2. `window.__MINES_CONFIG__`
+/// 3. `/config.json`
+/// 4. `fallback_api_base_url`, usually `UI_BASE_API_URL` from the build environment
+///
+/// A source which is present but invalid results in an error rather than moving on to the next
+/// source.
+pub async fn load(fallback_api_base_url: &str) -> Result<Config, ConfigError> {
+    if let Some(config) = from_meta()? {
+        return Ok(config);
+    }
+    if let Some(config) = from_window()? {
+        return Ok(config);
+    }
+    if let Some(config) = from_file().await? {
+        return Ok(config);
+    }
+    Config::new(fallback_api_base_url)
+}
+
+/// Store `config` so it is used by all subsequent API requests.
+pub fn set(config: Config) {
+    CONFIG.with(|current| current.replace(Some(config)));
+}
+
+/// The stored `Config`, `None` until one has been stored.
+pub fn get() -> Option<Config> {
+    CONFIG.with(|current| current.borrow().clone())
+}
+
+/// The base URL of the game API from the stored `Config`, or `fallback_api_base_url` if no
+/// `Config` has been stored.
+pub fn api_base_url(fallback_api_base_url: &str) -> String {
+    CONFIG.with(|current| match current.borrow().as_ref() {
+        Some(config) => String::from(config.api_base_url()),
+        None => String::from(fallback_api_base_url),
+    })
+}
+
+fn from_meta() -> Result<Option<Config>, ConfigError> {
+    let selector = format!("meta[name=\"{}\"]", META_NAME);
+    let content = window()
+        .document()
+        .and_then(|document| document.query_selector(&selector).ok().flatten())
+        .and_then(|meta| meta.get_attribute("content"));
+    content.map(|url| Config::new(&url)).transpose()
+}
+
+fn from_window() -> Result<Option<Config>, ConfigError> {
+    let value = match window().get(WINDOW_KEY) {
+        Some(object) => JsValue::from(object),
+        None => return Ok(None),
+    };
+    value
+        .into_serde()
+        .map(Some)
+        .map_err(|err| ConfigError::Parse {
+            source: format!("window.{}", WINDOW_KEY),
+            reason: err.to_string(),
+        })
+}
+
+async fn from_file() -> Result<Option<Config>, ConfigError> {
+    let parse_error = || ConfigError::Parse {
+        source: String::from(CONFIG_PATH),
+        reason: String::from("expected an object with an absolute apiBaseUrl"),
+    };
+    let resp_value = JsFuture::from(window().fetch_with_str(CONFIG_PATH))
+        .await
+        .map_err(|_| ConfigError::Fetch(FetchError::FetchError))?;
+    let resp: web_sys::Response = resp_value
+        .dyn_into()
+        .map_err(|_| ConfigError::Fetch(FetchError::FetchError))?;
+    match resp.status() {
+        200..=299 => {
+            let json = resp.json().map_err(|_| parse_error())?;
+            let json = JsFuture::from(json).await.map_err(|_| parse_error())?;
+            json.into_serde().map(Some).map_err(|_| parse_error())
+        }
+        404 => Ok(None),
+        _ => Err(ConfigError::Fetch(FetchError::FetchError)),
+    }
+}
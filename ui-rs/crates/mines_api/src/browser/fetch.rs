@@ -0,0 +1,135 @@
+use super::{auth, window};
+use crate::{FetchError, ServerError};
+
+/// How to make a request with `fetch`.
+pub struct FetchRequest<'a> {
+    pub method: &'a str,
+    /// JSON sent as the body of the request
+    pub body: Option<&'a str>,
+    /// Sent as `If-None-Match` to ask whether the body cached for it is still current
+    pub etag: Option<&'a str>,
+    /// Whether the logged in user's token is sent with the request
+    pub authorize: bool,
+    /// Aborts the request, including reading its body, once it is signalled
+    pub signal: Option<&'a web_sys::AbortSignal>,
+}
+
+impl Default for FetchRequest<'_> {
+    fn default() -> Self {
+        Self {
+            method: "GET",
+            body: None,
+            etag: None,
+            authorize: true,
+            signal: None,
+        }
+    }
+}
+
+/// What a successful response held.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The text of the response body and the `ETag` it was sent with
+    Body { etag: Option<String>, text: String },
+    /// The body cached for the `ETag` sent with the request is still current
+    NotModified,
+}
+
+/// Make a single request to `url` as described by `request`. A response with an error status is
+/// read as the `ServerError` the server sent, and a request which fails or is aborted before the
+/// response is read fails with `FetchError::FetchError`.
+pub async fn fetch(url: &str, request: &FetchRequest<'_>) -> Result<Fetched, FetchError> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Request, RequestInit, RequestMode, Response};
+
+    let mut opts = RequestInit::new();
+    opts.method(request.method);
+    opts.mode(RequestMode::Cors);
+    opts.signal(request.signal);
+    if let Some(body) = request.body {
+        opts.body(Some(&JsValue::from(body)));
+    }
+    let fetch_request =
+        Request::new_with_str_and_init(url, &opts).map_err(|_| FetchError::RequestCreateError)?;
+    let headers = fetch_request.headers();
+    let set_header = |name: &str, value: &str| {
+        headers
+            .set(name, value)
+            .map_err(|_| FetchError::RequestHeaderSetError)
+    };
+    set_header("Accept", "application/json")?;
+    set_header("Content-Type", "application/json")?;
+    if let Some(etag) = request.etag {
+        set_header("If-None-Match", etag)?;
+    }
+    if let Some(token) = auth::token().filter(|_| request.authorize) {
+        set_header("Authorization", &token.bearer())?;
+    }
+    let response: Response = JsFuture::from(window().fetch_with_request(&fetch_request))
+        .await
+        .map_err(|_| FetchError::FetchError)?
+        .dyn_into()
+        .map_err(|_| FetchError::FetchError)?;
+    if response.status() == 304 {
+        return Ok(Fetched::NotModified);
+    }
+    let text = JsFuture::from(response.text().map_err(|_| FetchError::FetchError)?)
+        .await
+        .map_err(|_| FetchError::FetchError)?
+        .as_string()
+        .unwrap_or_default();
+    match response.status() {
+        100..=299 => Ok(Fetched::Body {
+            etag: response.headers().get("ETag").ok().flatten(),
+            text,
+        }),
+        status => Err(FetchError::Server(ServerError::from_response(
+            status, &text,
+        ))),
+    }
+}
+
+/// Write `body` as the JSON sent with a request.
+pub fn to_body<B>(body: &B) -> Result<String, FetchError>
+where
+    B: serde::Serialize,
+{
+    serde_json::to_string(body).map_err(|_| FetchError::SerializeBodyError)
+}
+
+/// Read the body of a successful response as `T`. An empty body is read as `null`.
+pub fn parse<T>(text: &str) -> Result<T, FetchError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let text = if text.is_empty() { "null" } else { text };
+    serde_json::from_str(text).map_err(|_| FetchError::ParseError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameCreated, GameId};
+
+    #[test]
+    fn parses_body() {
+        let id = GameId::new_v4();
+        let body = format!(r#"{{"id":"{}"}}"#, id);
+        let created: Result<GameCreated, _> = parse(&body);
+        assert_eq!(created, Ok(GameCreated { id, seed: None }));
+    }
+
+    #[test]
+    fn parses_empty_body() {
+        assert_eq!(parse::<()>(""), Ok(()));
+        let created: Result<GameCreated, _> = parse("");
+        assert_eq!(created, Err(FetchError::ParseError));
+    }
+
+    #[test]
+    fn fails_unparseable_body() {
+        let created: Result<GameCreated, _> = parse("<html></html>");
+        assert_eq!(created, Err(FetchError::ParseError));
+    }
+}
@@ -0,0 +1,5 @@
+/// Whether a click on a cell asks to flag it rather than open it, which is the case while Alt or
+/// Ctrl is held.
+pub fn is_flag_click(event: &web_sys::MouseEvent) -> bool {
+    event.alt_key() || event.ctrl_key()
+}
@@ -0,0 +1,150 @@
+use crate::FetchError;
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+/// Settings resolved when a UI starts up which describe how to reach the game API.
+///
+/// The JSON form (used by `/config.json` and `window.__MINES_CONFIG__`) is
+/// `{ "apiBaseUrl": "https://example.com" }`, optionally with a `"longPressMs"` number.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "ConfigInput")]
+pub struct Config {
+    api_base_url: String,
+    long_press_ms: Option<u32>,
+}
+
+impl Config {
+    /// Create a `Config` for an API served from `api_base_url`. The URL must be absolute and use
+    /// the `http` or `https` scheme. Trailing slashes are removed so paths can be appended.
+    pub fn new(api_base_url: &str) -> Result<Self, ConfigError> {
+        let trimmed = api_base_url.trim().trim_end_matches('/');
+        let has_host = ["http://", "https://"]
+            .iter()
+            .any(|scheme| trimmed.starts_with(scheme) && trimmed.len() > scheme.len());
+        if has_host {
+            Ok(Self {
+                api_base_url: String::from(trimmed),
+                long_press_ms: None,
+            })
+        } else {
+            Err(ConfigError::InvalidBaseUrl(String::from(api_base_url)))
+        }
+    }
+
+    /// The base URL, without a trailing slash, of the game API.
+    pub fn api_base_url(&self) -> &str {
+        &self.api_base_url
+    }
+
+    /// How long, in milliseconds, a cell has to be touched to flag it. `None` leaves it to the UI.
+    pub fn long_press_ms(&self) -> Option<u32> {
+        self.long_press_ms
+    }
+}
+
+/// The shape of the configuration as it is received, before it has been validated.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigInput {
+    api_base_url: String,
+    #[serde(default)]
+    long_press_ms: Option<u32>,
+}
+
+impl TryFrom<ConfigInput> for Config {
+    type Error = ConfigError;
+
+    fn try_from(input: ConfigInput) -> Result<Self, Self::Error> {
+        let config = Config::new(&input.api_base_url)?;
+        Ok(Self {
+            long_press_ms: input.long_press_ms,
+            ..config
+        })
+    }
+}
+
+/// The types of errors that can happen while resolving the `Config` at startup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The configuration could not be retrieved
+    Fetch(FetchError),
+    /// A configured API base URL was not an absolute `http` or `https` URL
+    InvalidBaseUrl(String),
+    /// A configuration source was present but could not be parsed
+    Parse { source: String, reason: String },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Fetch(err) => f.write_fmt(format_args!("Unable to load config: {}", err)),
+            ConfigError::InvalidBaseUrl(url) => {
+                f.write_fmt(format_args!("Invalid API base URL {:?}", url))
+            }
+            ConfigError::Parse { source, reason } => f.write_fmt(format_args!(
+                "Unable to parse config from {}: {}",
+                source, reason
+            )),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_http_and_https() {
+        let config = Config::new("http://localhost:3000").unwrap();
+        assert_eq!(config.api_base_url(), "http://localhost:3000");
+        let config = Config::new("https://mines.example.com").unwrap();
+        assert_eq!(config.api_base_url(), "https://mines.example.com");
+    }
+
+    #[test]
+    fn removes_trailing_slashes() {
+        let config = Config::new(" https://mines.example.com/api// ").unwrap();
+        assert_eq!(config.api_base_url(), "https://mines.example.com/api");
+    }
+
+    #[test]
+    fn rejects_relative_and_empty_urls() {
+        for url in &[
+            "",
+            "/api",
+            "localhost:3000",
+            "ftp://mines.example.com",
+            "https://",
+        ] {
+            assert_eq!(
+                Config::new(url),
+                Err(ConfigError::InvalidBaseUrl(String::from(*url)))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_from_json() {
+        let config: Config =
+            serde_json::from_str(r#"{ "apiBaseUrl": "https://mines.example.com/" }"#).unwrap();
+        assert_eq!(config.api_base_url(), "https://mines.example.com");
+        assert_eq!(config.long_press_ms(), None);
+    }
+
+    #[test]
+    fn parses_long_press_from_json() {
+        let config: Config = serde_json::from_str(
+            r#"{ "apiBaseUrl": "https://mines.example.com", "longPressMs": 350 }"#,
+        )
+        .unwrap();
+        assert_eq!(config.long_press_ms(), Some(350));
+    }
+
+    #[test]
+    fn rejects_invalid_json_url() {
+        let config: Result<Config, _> = serde_json::from_str(r#"{ "apiBaseUrl": "/api" }"#);
+        assert!(config.is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::ServerError;
+
+/// The kinds of failures which can occur when making a request to the game API.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FetchError {
+    /// The request was aborted because its result is no longer needed
+    Cancelled,
+    /// The request could not be completed
+    FetchError,
+    /// The response body could not be parsed into the expected type
+    ParseError,
+    /// The request could not be constructed
+    RequestCreateError,
+    /// A header could not be set on the request
+    RequestHeaderSetError,
+    /// The request was retried the maximum number of times without succeeding
+    RetriesExhausted,
+    /// The request body could not be serialized
+    SerializeBodyError,
+    /// The server responded with an error status, described by the `ServerError`
+    Server(ServerError),
+    /// No response was received before the request timed out
+    Timeout,
+}
+
+impl FetchError {
+    /// The `ServerError` sent in response to the request, if the server responded with one.
+    pub fn server_error(&self) -> Option<&ServerError> {
+        match self {
+            FetchError::Server(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Cancelled => f.write_str("The request was cancelled"),
+            FetchError::FetchError => f.write_str("The request failed"),
+            FetchError::ParseError => f.write_str("The response could not be parsed"),
+            FetchError::RequestCreateError => f.write_str("The request could not be created"),
+            FetchError::RequestHeaderSetError => {
+                f.write_str("The request headers could not be set")
+            }
+            FetchError::RetriesExhausted => f.write_str("The request failed after retrying"),
+            FetchError::SerializeBodyError => {
+                f.write_str("The request body could not be serialized")
+            }
+            FetchError::Server(error) => error.fmt(f),
+            FetchError::Timeout => f.write_str("The request timed out"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
@@ -0,0 +1,523 @@
+use crate::{BoardValue, GameId, GameSeed};
+use serde::{Deserialize, Serialize};
+
+/// Data transfer object intended for use as the request body when adding a new move to an
+/// existing Game.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameMoveInput {
+    pub column: usize,
+    pub row: usize,
+    #[serde(rename = "type")]
+    pub move_type: GameMoveType,
+}
+
+/// Data transfer object intended for use as the request body when adding several moves to an
+/// existing Game at once. The moves are applied in order.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameMoveBatchInput {
+    pub moves: Vec<GameMoveInput>,
+}
+
+/// Data transfer object intended for use as the request body when creating a new Game.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GameCreateInput {
+    pub columns: usize,
+    pub rows: usize,
+    /// How many mines are placed on the board, the server picks a density when `None`
+    #[serde(flatten)]
+    pub mines: Option<MineSetting>,
+    /// Seed for placing the mines, the same seed, size and mines always make the same board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<GameSeed>,
+}
+
+/// How the mines for a new Game are chosen, sent as either a `mines` or a `density` field.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum MineSetting {
+    /// Place exactly this many mines
+    #[serde(rename = "mines")]
+    Count(usize),
+    /// Make this share of the cells mines, greater than 0 and less than 1. The server rounds it to
+    /// a count which leaves at least one mine and one free cell
+    #[serde(rename = "density")]
+    Density(f64),
+}
+
+impl GameCreateInput {
+    /// Check the board has cells and the mines leave at least one cell without a mine, as the
+    /// server requires.
+    pub fn validate(&self) -> Result<(), GameCreateError> {
+        let cells = self.columns * self.rows;
+        if cells == 0 {
+            return Err(GameCreateError::NoCells);
+        }
+        match self.mines {
+            Some(MineSetting::Count(mines)) if mines == 0 || mines >= cells => {
+                Err(GameCreateError::MineCount { max: cells - 1 })
+            }
+            Some(MineSetting::Density(density)) if !(density > 0.0 && density < 1.0) => {
+                Err(GameCreateError::Density)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The ways a `GameCreateInput` can describe a Game the server won't create.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameCreateError {
+    /// The board has no rows or no columns
+    NoCells,
+    /// The mine count is zero or leaves no cell free of mines
+    MineCount { max: usize },
+    /// The density is not between 0 and 1
+    Density,
+}
+
+impl std::fmt::Display for GameCreateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameCreateError::NoCells => f.write_str("The board needs at least one cell"),
+            GameCreateError::MineCount { max } => {
+                f.write_fmt(format_args!("Choose between 1 and {} mines", max))
+            }
+            GameCreateError::Density => f.write_str("Mine density must be between 0 and 1"),
+        }
+    }
+}
+
+/// A move which has been applied to a Game, as listed in the Game's history.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameMove {
+    pub column: usize,
+    pub row: usize,
+    #[serde(rename = "type")]
+    pub move_type: GameMoveType,
+    /// When the move was applied, as an ISO 8601 timestamp
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+/// Represents the kind of move being added to a Game.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum GameMoveType {
+    #[serde(rename = "FLAG")]
+    Flag,
+    #[serde(rename = "OPEN")]
+    Open,
+    #[serde(rename = "REMOVE_FLAG")]
+    RemoveFlag,
+}
+
+impl GameMoveType {
+    /// The move a click on a cell showing `value` asks for. A flag is removed whatever was asked,
+    /// a closed cell is flagged when `flag` is set and opened otherwise. Any other cell can't be
+    /// moved on.
+    pub fn for_cell(value: BoardValue, flag: bool) -> Option<Self> {
+        match value {
+            BoardValue::Flag => Some(GameMoveType::RemoveFlag),
+            BoardValue::Closed if flag => Some(GameMoveType::Flag),
+            BoardValue::Closed => Some(GameMoveType::Open),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the current state of a Game.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum GameStatus {
+    /// Represents a game which is still playable, new moves will be applied.
+    #[default]
+    #[serde(rename = "OPEN")]
+    Open,
+    /// Represents a completed game in which all mines were successfully avoided
+    #[serde(rename = "WON")]
+    Won,
+    /// Represents a completed game in which a mine was opened
+    #[serde(rename = "LOST")]
+    Lost,
+}
+
+/// A struct to hold some data from the Game API.
+///
+/// Note how we don't have to define every member -- serde will ignore extra
+/// data when deserializing
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameState {
+    pub id: GameId,
+    pub board: Vec<Vec<BoardValue>>,
+    pub status: GameStatus,
+    /// Total number of mines on the board, once the server reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mines: Option<usize>,
+    /// Seed the mines were placed with, once the server reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<GameSeed>,
+}
+
+impl GameState {
+    /// The input creating a new Game with the same board as this one, `None` when the seed of
+    /// this Game isn't known.
+    pub fn replay_input(&self) -> Option<GameCreateInput> {
+        let seed = self.seed?;
+        Some(GameCreateInput {
+            columns: self.board.first().map(Vec::len).unwrap_or_default(),
+            rows: self.board.len(),
+            mines: self.mines.map(MineSetting::Count),
+            seed: Some(seed),
+        })
+    }
+}
+
+/// What happened to one of the moves in a `GameMoveBatchInput`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum MoveOutcome {
+    /// The move was applied to the Game
+    #[serde(rename = "APPLIED")]
+    Applied,
+    /// The move was not applied, e.g. because an earlier move in the batch ended the Game
+    #[serde(rename = "REJECTED")]
+    Rejected,
+}
+
+/// A struct to hold data from the Game API after a batch of moves has been applied. `outcomes`
+/// holds the outcome of each move in the order the moves were sent.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameMoveBatch {
+    pub game: GameState,
+    pub outcomes: Vec<MoveOutcome>,
+}
+
+impl GameMoveBatch {
+    /// Make sure there is an outcome for each of the `count` moves sent. The API stops applying
+    /// moves once the Game is complete, so any move it didn't report on is `MoveOutcome::Rejected`.
+    pub fn with_outcomes_for(mut self, count: usize) -> Self {
+        if self.outcomes.len() < count {
+            self.outcomes.resize(count, MoveOutcome::Rejected);
+        }
+        self
+    }
+
+    /// Number of moves which were not applied.
+    pub fn rejected(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| **outcome == MoveOutcome::Rejected)
+            .count()
+    }
+}
+
+/// A `GameState` pushed to the clients watching a Game. `version` grows with every change to the
+/// Game so frames which arrive out of order can be recognised.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameFrame {
+    pub version: u64,
+    pub game: GameState,
+}
+
+/// A struct to hold data from the Game API after game creation.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameCreated {
+    pub id: GameId,
+    /// Seed the mines were placed with, once the server reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<GameSeed>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_move_input() {
+        let input = GameMoveInput {
+            column: 1,
+            row: 2,
+            move_type: GameMoveType::RemoveFlag,
+        };
+        assert_eq!(
+            serde_json::to_value(&input).unwrap(),
+            json!({ "column": 1, "row": 2, "type": "REMOVE_FLAG" })
+        );
+    }
+
+    #[test]
+    fn picks_move_for_cell() {
+        assert_eq!(
+            GameMoveType::for_cell(BoardValue::Closed, false),
+            Some(GameMoveType::Open)
+        );
+        assert_eq!(
+            GameMoveType::for_cell(BoardValue::Closed, true),
+            Some(GameMoveType::Flag)
+        );
+        assert_eq!(
+            GameMoveType::for_cell(BoardValue::Flag, false),
+            Some(GameMoveType::RemoveFlag)
+        );
+        assert_eq!(
+            GameMoveType::for_cell(BoardValue::Flag, true),
+            Some(GameMoveType::RemoveFlag)
+        );
+        assert_eq!(GameMoveType::for_cell(BoardValue::Open(2), true), None);
+        assert_eq!(GameMoveType::for_cell(BoardValue::Pending, false), None);
+    }
+
+    #[test]
+    fn round_trips_move_input() {
+        let input = GameMoveInput {
+            column: 4,
+            row: 3,
+            move_type: GameMoveType::Flag,
+        };
+        let json = serde_json::to_string(&input).unwrap();
+        assert_eq!(serde_json::from_str::<GameMoveInput>(&json).unwrap(), input);
+    }
+
+    #[test]
+    fn parses_game_move() {
+        let value = json!({
+            "column": 2,
+            "row": 0,
+            "type": "FLAG",
+            "createdAt": "2021-02-14T18:30:05.123Z",
+        });
+        let game_move: GameMove = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            game_move,
+            GameMove {
+                column: 2,
+                row: 0,
+                move_type: GameMoveType::Flag,
+                created_at: String::from("2021-02-14T18:30:05.123Z"),
+            }
+        );
+    }
+
+    #[test]
+    fn serializes_move_batch_input() {
+        let input = GameMoveBatchInput {
+            moves: vec![GameMoveInput {
+                column: 0,
+                row: 1,
+                move_type: GameMoveType::Open,
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(&input).unwrap(),
+            json!({ "moves": [{ "column": 0, "row": 1, "type": "OPEN" }] })
+        );
+    }
+
+    #[test]
+    fn rejects_moves_missing_from_batch() {
+        let id = uuid::Uuid::new_v4();
+        let value = json!({
+            "game": { "id": id, "board": [["M", " "]], "status": "LOST" },
+            "outcomes": ["APPLIED"],
+        });
+        let batch: GameMoveBatch = serde_json::from_value(value).unwrap();
+        assert_eq!(batch.rejected(), 0);
+        let batch = batch.with_outcomes_for(3);
+        assert_eq!(
+            batch.outcomes,
+            vec![
+                MoveOutcome::Applied,
+                MoveOutcome::Rejected,
+                MoveOutcome::Rejected
+            ]
+        );
+        assert_eq!(batch.rejected(), 2);
+    }
+
+    #[test]
+    fn round_trips_create_input() {
+        let input = GameCreateInput {
+            columns: 10,
+            rows: 5,
+            mines: None,
+            seed: None,
+        };
+        let value = serde_json::to_value(&input).unwrap();
+        assert_eq!(value, json!({ "columns": 10, "rows": 5 }));
+        assert_eq!(
+            serde_json::from_value::<GameCreateInput>(value).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn round_trips_mine_settings() {
+        let expert = GameCreateInput {
+            columns: 30,
+            rows: 16,
+            mines: Some(MineSetting::Count(99)),
+            seed: None,
+        };
+        let value = serde_json::to_value(&expert).unwrap();
+        assert_eq!(value, json!({ "columns": 30, "rows": 16, "mines": 99 }));
+        assert_eq!(
+            serde_json::from_value::<GameCreateInput>(value).unwrap(),
+            expert
+        );
+        let sparse = GameCreateInput {
+            mines: Some(MineSetting::Density(0.1)),
+            ..expert
+        };
+        let value = serde_json::to_value(&sparse).unwrap();
+        assert_eq!(value, json!({ "columns": 30, "rows": 16, "density": 0.1 }));
+        assert_eq!(
+            serde_json::from_value::<GameCreateInput>(value).unwrap(),
+            sparse
+        );
+    }
+
+    #[test]
+    fn round_trips_seed() {
+        let seeded = GameCreateInput {
+            columns: 5,
+            rows: 5,
+            mines: None,
+            seed: Some(4_000_000_000),
+        };
+        let value = serde_json::to_value(&seeded).unwrap();
+        assert_eq!(
+            value,
+            json!({ "columns": 5, "rows": 5, "seed": 4_000_000_000u32 })
+        );
+        assert_eq!(
+            serde_json::from_value::<GameCreateInput>(value).unwrap(),
+            seeded
+        );
+    }
+
+    #[test]
+    fn validates_mine_settings() {
+        let input = |mines| GameCreateInput {
+            columns: 3,
+            rows: 3,
+            mines,
+            seed: None,
+        };
+        assert_eq!(input(None).validate(), Ok(()));
+        assert_eq!(input(Some(MineSetting::Count(8))).validate(), Ok(()));
+        assert_eq!(
+            input(Some(MineSetting::Count(9))).validate(),
+            Err(GameCreateError::MineCount { max: 8 })
+        );
+        assert_eq!(
+            input(Some(MineSetting::Count(0))).validate(),
+            Err(GameCreateError::MineCount { max: 8 })
+        );
+        assert_eq!(
+            input(Some(MineSetting::Density(1.0))).validate(),
+            Err(GameCreateError::Density)
+        );
+        let empty = GameCreateInput {
+            columns: 0,
+            ..input(None)
+        };
+        assert_eq!(empty.validate(), Err(GameCreateError::NoCells));
+    }
+
+    #[test]
+    fn parses_game_state() {
+        let id = uuid::Uuid::new_v4();
+        let value = json!({
+            "id": id,
+            "board": [[" ", "1"], ["F", "M"]],
+            "status": "LOST",
+            "moves": [],
+        });
+        let state: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(state.id, id);
+        assert_eq!(state.mines, None);
+        assert_eq!(
+            state.board,
+            vec![
+                vec![BoardValue::Closed, BoardValue::Open(1)],
+                vec![BoardValue::Flag, BoardValue::Mine],
+            ]
+        );
+        assert_eq!(state.status, GameStatus::Lost);
+    }
+
+    #[test]
+    fn round_trips_game_state() {
+        let state = GameState {
+            id: uuid::Uuid::new_v4(),
+            board: vec![vec![BoardValue::Pending, BoardValue::Open(2)]],
+            status: GameStatus::Won,
+            mines: None,
+            seed: None,
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<GameState>(&json).unwrap(), state);
+    }
+
+    #[test]
+    fn parses_mine_count() {
+        let value = json!({
+            "id": uuid::Uuid::new_v4(),
+            "board": [[" "]],
+            "status": "OPEN",
+            "mines": 99,
+        });
+        let state: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(state.mines, Some(99));
+    }
+
+    #[test]
+    fn replays_seeded_game() {
+        let value = json!({
+            "id": uuid::Uuid::new_v4(),
+            "board": [[" ", " ", " "], [" ", " ", " "]],
+            "status": "LOST",
+            "mines": 2,
+            "seed": 1234,
+        });
+        let state: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            state.replay_input(),
+            Some(GameCreateInput {
+                columns: 3,
+                rows: 2,
+                mines: Some(MineSetting::Count(2)),
+                seed: Some(1234),
+            })
+        );
+        let unseeded = GameState {
+            seed: None,
+            ..state
+        };
+        assert_eq!(unseeded.replay_input(), None);
+    }
+
+    #[test]
+    fn parses_game_frame() {
+        let id = uuid::Uuid::new_v4();
+        let value = json!({
+            "version": 3,
+            "game": { "id": id, "board": [["F"]], "status": "OPEN" },
+        });
+        let frame: GameFrame = serde_json::from_value(value).unwrap();
+        assert_eq!(frame.version, 3);
+        assert_eq!(frame.game.board, vec![vec![BoardValue::Flag]]);
+    }
+
+    #[test]
+    fn round_trips_game_created() {
+        let created = GameCreated {
+            id: uuid::Uuid::new_v4(),
+            seed: Some(42),
+        };
+        let json = serde_json::to_string(&created).unwrap();
+        assert_eq!(serde_json::from_str::<GameCreated>(&json).unwrap(), created);
+    }
+
+    #[test]
+    fn defaults_to_open_status() {
+        assert_eq!(GameStatus::default(), GameStatus::Open);
+    }
+}
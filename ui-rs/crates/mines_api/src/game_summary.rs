@@ -0,0 +1,170 @@
+use crate::{GameId, GameStatus};
+use serde::{Deserialize, Serialize};
+
+/// A struct to hold the details of a Game shown when listing Games, without its board.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameSummary {
+    pub id: GameId,
+    pub status: GameStatus,
+    pub rows: usize,
+    pub columns: usize,
+    /// When the Game was created, as an ISO 8601 timestamp
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    /// When the most recent move was applied, or the Game was created if there are no moves, as
+    /// an ISO 8601 timestamp
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+    /// Number of moves applied to the Game
+    #[serde(rename = "moveCount")]
+    pub move_count: usize,
+}
+
+/// A single page of `GameSummary` records. `next_cursor` is passed as `GameListQuery::cursor` to
+/// request the page after this one, it is `None` on the last page.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct GameSummaryPage {
+    pub items: Vec<GameSummary>,
+    #[serde(rename = "nextCursor", default)]
+    pub next_cursor: Option<String>,
+}
+
+/// The order Games are listed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameListSort {
+    /// Most recently created first
+    #[default]
+    Newest,
+    /// Least recently created first
+    Oldest,
+    /// Most recently moved in first
+    RecentlyPlayed,
+}
+
+impl GameListSort {
+    /// The value of the `sort` query parameter understood by the Game API.
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Newest => "-createdAt",
+            Self::Oldest => "createdAt",
+            Self::RecentlyPlayed => "-updatedAt",
+        }
+    }
+}
+
+/// Which page of which Games to list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GameListQuery {
+    /// Only list Games with this status, `None` lists every Game
+    pub status: Option<GameStatus>,
+    pub sort: GameListSort,
+    /// Where the page starts, from `GameSummaryPage::next_cursor`. `None` requests the first page
+    pub cursor: Option<String>,
+}
+
+impl GameListQuery {
+    /// The query string, including the leading `?`, requesting this page.
+    pub fn to_query_string(&self) -> String {
+        let mut params = vec![format!("sort={}", encode(self.sort.as_param()))];
+        if let Some(status) = self.status {
+            let status = match status {
+                GameStatus::Open => "OPEN",
+                GameStatus::Won => "WON",
+                GameStatus::Lost => "LOST",
+            };
+            params.push(format!("status={}", status));
+        }
+        if let Some(cursor) = &self.cursor {
+            params.push(format!("cursor={}", encode(cursor)));
+        }
+        format!("?{}", params.join("&"))
+    }
+
+    /// The same Games starting at the page after `page`, `None` if `page` is the last page.
+    pub fn next(&self, page: &GameSummaryPage) -> Option<Self> {
+        page.next_cursor.as_ref().map(|cursor| Self {
+            cursor: Some(cursor.clone()),
+            ..self.clone()
+        })
+    }
+}
+
+/// Percent encode every byte of `value` other than the unreserved characters of RFC 3986.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_summary_page() {
+        let id = uuid::Uuid::new_v4();
+        let value = json!({
+            "items": [{
+                "id": id,
+                "status": "WON",
+                "rows": 5,
+                "columns": 10,
+                "createdAt": "2021-02-14T18:30:05.123Z",
+                "updatedAt": "2021-02-14T18:42:00.000Z",
+                "moveCount": 12,
+            }],
+            "nextCursor": "abc",
+        });
+        let page: GameSummaryPage = serde_json::from_value(value).unwrap();
+        assert_eq!(page.items[0].id, id);
+        assert_eq!(page.items[0].status, GameStatus::Won);
+        assert_eq!(page.items[0].move_count, 12);
+        assert_eq!(page.next_cursor.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn parses_last_page() {
+        let page: GameSummaryPage = serde_json::from_value(json!({ "items": [] })).unwrap();
+        assert_eq!(page, GameSummaryPage::default());
+    }
+
+    #[test]
+    fn builds_query_string() {
+        assert_eq!(
+            GameListQuery::default().to_query_string(),
+            "?sort=-createdAt"
+        );
+        let query = GameListQuery {
+            status: Some(GameStatus::Lost),
+            sort: GameListSort::RecentlyPlayed,
+            cursor: Some(String::from("a+b/c=")),
+        };
+        assert_eq!(
+            query.to_query_string(),
+            "?sort=-updatedAt&status=LOST&cursor=a%2Bb%2Fc%3D"
+        );
+    }
+
+    #[test]
+    fn follows_next_cursor() {
+        let query = GameListQuery {
+            status: Some(GameStatus::Open),
+            ..GameListQuery::default()
+        };
+        assert_eq!(query.next(&GameSummaryPage::default()), None);
+        let page = GameSummaryPage {
+            items: vec![],
+            next_cursor: Some(String::from("next")),
+        };
+        let next = query.next(&page).unwrap();
+        assert_eq!(next.status, Some(GameStatus::Open));
+        assert_eq!(next.cursor.as_deref(), Some("next"));
+    }
+}
@@ -0,0 +1,30 @@
+//! Types describing the requests and responses of the minesweeper game API. These are shared by
+//! every Rust UI so a change to the API contract only needs to be made in one place.
+
+mod auth;
+mod board_value;
+#[cfg(feature = "browser")]
+pub mod browser;
+mod config;
+mod fetch_error;
+mod game;
+mod game_summary;
+mod server_error;
+
+pub use auth::{AuthToken, LoginInput, TokenizedUser};
+pub use board_value::{BoardValue, BoardValueConvertError};
+pub use config::{Config, ConfigError};
+pub use fetch_error::FetchError;
+pub use game::{
+    GameCreateError, GameCreateInput, GameCreated, GameFrame, GameMove, GameMoveBatch,
+    GameMoveBatchInput, GameMoveInput, GameMoveType, GameState, GameStatus, MineSetting,
+    MoveOutcome,
+};
+pub use game_summary::{GameListQuery, GameListSort, GameSummary, GameSummaryPage};
+pub use server_error::{ServerError, ServerErrorKind};
+
+/// The identifier for a Game.
+pub type GameId = uuid::Uuid;
+
+/// The seed mines are placed with, kept to 32 bits so it survives being a JavaScript number.
+pub type GameSeed = u32;
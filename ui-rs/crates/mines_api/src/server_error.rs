@@ -0,0 +1,219 @@
+use serde::Deserialize;
+
+/// An error response received from the game API, decoded from the status code and the JSON body
+/// sent by the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerError {
+    /// HTTP status code of the response
+    pub status: u16,
+    /// What went wrong according to the server
+    pub kind: ServerErrorKind,
+    /// Message sent by the server, if any
+    pub message: Option<String>,
+    /// Name of the request field the server rejected, if the error relates to one
+    pub field: Option<String>,
+}
+
+/// The kinds of errors the game API reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServerErrorKind {
+    /// The game is already won or lost so no more moves are accepted
+    GameComplete,
+    /// A move referred to a cell outside of the board
+    InvalidCoordinates,
+    /// A request body contained a value of the wrong type
+    InvalidParams,
+    /// A request body was missing a required value
+    MissingParams,
+    /// The requested game does not exist
+    NotFound,
+    /// The request needs credentials which were missing, expired or wrong
+    Unauthorized,
+    /// Any other error, including failures within the server itself
+    Other,
+}
+
+/// The shape of the JSON body the Nest server sends along with an error status.
+#[derive(Debug, Deserialize)]
+struct ServerErrorBody {
+    #[serde(default)]
+    message: Option<ServerErrorMessage>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Nest sends either a single message or a list of them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ServerErrorMessage {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ServerError {
+    /// Decode the error described by a response with `status` and `body`. A body which isn't in
+    /// the shape sent by the server is ignored and the kind is determined by `status` alone.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let body: Option<ServerErrorBody> = serde_json::from_str(body).ok();
+        let (message, error) = match body {
+            Some(ServerErrorBody { message, error }) => (
+                message.map(|message| match message {
+                    ServerErrorMessage::One(message) => message,
+                    ServerErrorMessage::Many(messages) => messages.join(" "),
+                }),
+                error,
+            ),
+            None => (None, None),
+        };
+        let kind = match (status, error.as_deref()) {
+            (400, Some("invalid_params")) => ServerErrorKind::InvalidParams,
+            (400, Some("missing_params")) => ServerErrorKind::MissingParams,
+            (401, _) => ServerErrorKind::Unauthorized,
+            (404, _) => ServerErrorKind::NotFound,
+            (409, _) => ServerErrorKind::GameComplete,
+            (422, Some("invalid_coordinates")) => ServerErrorKind::InvalidCoordinates,
+            _ => ServerErrorKind::Other,
+        };
+        let field = match kind {
+            ServerErrorKind::InvalidParams | ServerErrorKind::MissingParams => {
+                message.as_deref().and_then(field_from_message)
+            }
+            _ => None,
+        };
+        Self {
+            status,
+            kind,
+            message,
+            field,
+        }
+    }
+
+    /// Whether the error was caused by the server rather than by the request.
+    pub fn is_server_failure(&self) -> bool {
+        self.status >= 500
+    }
+}
+
+/// Validation messages from the server end with the rejected keys in parentheses, e.g.
+/// `"Invalid parameters. (row)"`.
+fn field_from_message(message: &str) -> Option<String> {
+    let start = message.rfind('(')?;
+    let end = message.rfind(')')?;
+    let field = message.get(start + 1..end)?.trim();
+    if field.is_empty() {
+        None
+    } else {
+        Some(String::from(field))
+    }
+}
+
+impl std::fmt::Display for ServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.kind, &self.field) {
+            (ServerErrorKind::GameComplete, _) => f.write_str("This game is already finished"),
+            (ServerErrorKind::InvalidCoordinates, _) => f.write_str("That cell is out of bounds"),
+            (ServerErrorKind::InvalidParams, Some(field)) => {
+                f.write_fmt(format_args!("Invalid value for {}", field))
+            }
+            (ServerErrorKind::MissingParams, Some(field)) => {
+                f.write_fmt(format_args!("Missing value for {}", field))
+            }
+            (ServerErrorKind::NotFound, _) => f.write_str("That game does not exist"),
+            (ServerErrorKind::Unauthorized, _) => f.write_str("Please log in to continue"),
+            _ => match &self.message {
+                Some(message) => f.write_str(message),
+                None => f.write_fmt(format_args!("The server responded with {}", self.status)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_game_complete() {
+        let error = ServerError::from_response(
+            409,
+            r#"{"statusCode":409,"message":"Game(id: \"abc\") is already WON."}"#,
+        );
+        assert_eq!(error.status, 409);
+        assert_eq!(error.kind, ServerErrorKind::GameComplete);
+        assert_eq!(
+            error.message.as_deref(),
+            Some("Game(id: \"abc\") is already WON.")
+        );
+        assert_eq!(error.field, None);
+        assert_eq!(error.to_string(), "This game is already finished");
+    }
+
+    #[test]
+    fn decodes_out_of_bounds() {
+        let error = ServerError::from_response(
+            422,
+            r#"{"statusCode":422,"message":"Coordinates out of range.","error":"invalid_coordinates"}"#,
+        );
+        assert_eq!(error.kind, ServerErrorKind::InvalidCoordinates);
+        assert_eq!(error.to_string(), "That cell is out of bounds");
+    }
+
+    #[test]
+    fn decodes_no_record() {
+        let error = ServerError::from_response(404, r#"{"statusCode":404,"message":"Not Found"}"#);
+        assert_eq!(error.kind, ServerErrorKind::NotFound);
+        assert_eq!(error.message.as_deref(), Some("Not Found"));
+    }
+
+    #[test]
+    fn decodes_unauthorized() {
+        let error = ServerError::from_response(
+            401,
+            r#"{"statusCode":401,"message":"Unable to authenticate.","error":"not_authorized"}"#,
+        );
+        assert_eq!(error.kind, ServerErrorKind::Unauthorized);
+        assert_eq!(error.to_string(), "Please log in to continue");
+    }
+
+    #[test]
+    fn decodes_validation_field() {
+        let error = ServerError::from_response(
+            400,
+            r#"{"statusCode":400,"message":"Invalid parameters. (row)","error":"invalid_params"}"#,
+        );
+        assert_eq!(error.kind, ServerErrorKind::InvalidParams);
+        assert_eq!(error.field.as_deref(), Some("row"));
+        assert_eq!(error.to_string(), "Invalid value for row");
+        let error = ServerError::from_response(
+            400,
+            r#"{"statusCode":400,"message":"Missing required parameters. (rows, columns)","error":"missing_params"}"#,
+        );
+        assert_eq!(error.kind, ServerErrorKind::MissingParams);
+        assert_eq!(error.field.as_deref(), Some("rows, columns"));
+    }
+
+    #[test]
+    fn joins_message_lists() {
+        let error = ServerError::from_response(
+            400,
+            r#"{"statusCode":400,"message":["rows must be a number","columns must be a number"]}"#,
+        );
+        assert_eq!(error.kind, ServerErrorKind::Other);
+        assert_eq!(
+            error.message.as_deref(),
+            Some("rows must be a number columns must be a number")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_status() {
+        let error = ServerError::from_response(502, "<html>Bad Gateway</html>");
+        assert_eq!(error.kind, ServerErrorKind::Other);
+        assert_eq!(error.message, None);
+        assert!(error.is_server_failure());
+        assert_eq!(error.to_string(), "The server responded with 502");
+        let error = ServerError::from_response(404, "");
+        assert_eq!(error.kind, ServerErrorKind::NotFound);
+        assert!(!error.is_server_failure());
+    }
+}
@@ -1,160 +1,98 @@
-use dotenv_codegen::dotenv;
-
-const API_BASE_URL: &str = dotenv!("UI_BASE_API_URL");
-
-pub mod model {
-    use serde::{Deserialize, Serialize};
-
-    pub type GameId = uuid::Uuid;
-
-    /// Data transfer object intended for use as the request body when adding a new move to an
-    /// existing Game.
-    #[derive(Clone, Debug, Serialize)]
-    pub struct GameMoveInput {
-        pub column: usize,
-        pub row: usize,
-        #[serde(rename = "type")]
-        pub move_type: GameMoveType,
-    }
-
-    /// Data transfer object intended for use as the request body when creating a new Game.
-    #[derive(Clone, Debug, Serialize)]
-    pub struct GameCreateInput {
-        pub columns: usize,
-        pub rows: usize,
-    }
-
-    /// Represents the kind of move being added to a Game.
-    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
-    pub enum GameMoveType {
-        #[serde(rename = "FLAG")]
-        Flag,
-        #[serde(rename = "OPEN")]
-        Open,
-        #[serde(rename = "REMOVE_FLAG")]
-        RemoveFlag,
-    }
-
-    /// Represents the current state of a Game.
-    #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
-    pub enum GameStatus {
-        /// Represents a game which is still playable, new moves will be applied.
-        #[serde(rename = "OPEN")]
-        Open,
-        /// Represents a completed game in which all mines were successfully avoided
-        #[serde(rename = "WON")]
-        Won,
-        /// Represents a completed game in which a mine was opened
-        #[serde(rename = "LOST")]
-        Lost,
-    }
-
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-    pub enum FetchError {
-        Conflict,
-        FetchError,
-        NotFound,
-        ParseError,
-        RequestCreateError,
-        RequestHeaderSetError,
-        SerializeBodyError,
+mod cache;
+mod cancel;
+mod http;
+#[cfg(test)]
+mod memory;
+mod retry;
+mod socket;
+mod timeout;
+
+use mogwai::prelude::Transmitter;
+use std::{future::Future, pin::Pin, rc::Rc};
+
+pub use cancel::{CancelHook, CancelSignal, RequestScope};
+pub use http::HttpApi;
+#[cfg(test)]
+pub use memory::{ApiCall, MemoryApi};
+pub use mines_api::*;
+pub use retry::RetryPolicy;
+pub use socket::{LiveSocket, SocketEvent};
+
+/// The eventual result of a call to a `GameApi`.
+pub type ApiFuture<T> = Pin<Box<dyn Future<Output = Result<T, FetchError>>>>;
+
+/// The calls the views make to the game API. `HttpApi` makes real requests, other implementations
+/// let the views run without a server.
+pub trait GameApi {
+    /// Fetch the game identified by `game_id`.
+    fn get_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<GameState>;
+
+    /// The last known state of the game identified by `game_id`, available without waiting on the
+    /// server. It may be out of date, `get_game` gives the current state.
+    fn cached_game(&self, _game_id: GameId) -> Option<GameState> {
+        None
     }
 
-    /// A struct to hold some data from the Game API.
-    ///
-    /// Note how we don't have to define every member -- serde will ignore extra
-    /// data when deserializing
-    #[derive(Clone, Debug, Deserialize)]
-    pub struct GameState {
-        pub id: GameId,
-        pub board: Vec<Vec<crate::model::BoardValue>>,
-        pub status: GameStatus,
+    /// Fetch the page of game summaries described by `query`.
+    fn get_game_list(
+        &self,
+        query: &GameListQuery,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameSummaryPage>;
+
+    /// The last known page of game summaries described by `query`, available without waiting on
+    /// the server. It may be out of date, `get_game_list` gives the current page.
+    fn cached_game_list(&self, _query: &GameListQuery) -> Option<GameSummaryPage> {
+        None
     }
 
-    /// A struct to hold data from the Game API after game creation.
-    #[derive(Clone, Copy, Debug, Deserialize)]
-    pub struct GameCreated {
-        pub id: GameId,
+    /// Fetch every move applied to the game identified by `game_id`, in the order they were made.
+    fn get_game_moves(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<Vec<GameMove>>;
+
+    /// Open a socket which pushes a `GameFrame` whenever the game identified by `game_id` changes,
+    /// sending everything which happens on it into `tx`. `None` when sockets aren't available.
+    fn watch_game(
+        &self,
+        _game_id: GameId,
+        _tx: Transmitter<SocketEvent>,
+    ) -> Option<Box<dyn LiveSocket>> {
+        None
     }
-}
-
-pub use model::*;
-pub async fn get_game(game_id: model::GameId) -> Result<GameState, FetchError> {
-    let url = format!("{}/game/{}", API_BASE_URL, game_id);
-    get(url).await
-}
 
-pub async fn get_game_list() -> Result<Vec<model::GameId>, FetchError> {
-    let url = format!("{}/game", API_BASE_URL);
-    get(url).await
+    /// Add a move defined by `input` to the game identified by `game_id`.
+    fn patch_game(
+        &self,
+        game_id: GameId,
+        input: GameMoveInput,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameState>;
+
+    /// Add every move in `moves` to the game identified by `game_id` with a single request. The
+    /// moves are applied in order and the `GameMoveBatch` has an outcome for each of them.
+    fn patch_game_batch(
+        &self,
+        game_id: GameId,
+        moves: Vec<GameMoveInput>,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameMoveBatch>;
+
+    /// Give up on the game identified by `game_id`, which marks it as lost.
+    fn abandon_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<GameState>;
+
+    /// Delete the game identified by `game_id`.
+    fn delete_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<()>;
+
+    /// Create a new game defined by `input`.
+    fn create_game(&self, input: GameCreateInput, cancel: CancelSignal) -> ApiFuture<GameCreated>;
+
+    /// Exchange the credentials in `input` for an access token.
+    fn login(&self, input: LoginInput, cancel: CancelSignal) -> ApiFuture<TokenizedUser>;
 }
 
-/// Add a move defined by `input` to the game identified by `game_id`.
-pub async fn patch_game(game_id: GameId, input: GameMoveInput) -> Result<GameState, FetchError> {
-    let url = format!("{}/game/{}", API_BASE_URL, game_id);
-    fetch(url, Some("PATCH"), Some(&input)).await
-}
+/// A shared handle to the `GameApi` the views make their requests through.
+pub type Client = Rc<dyn GameApi>;
 
-pub async fn create_game(input: GameCreateInput) -> Result<GameCreated, FetchError> {
-    let url = format!("{}/game", API_BASE_URL);
-    fetch(url, Some("POST"), Some(&input)).await
-}
-
-async fn get<T>(url: String) -> Result<T, FetchError>
-where
-    T: for<'a> serde::de::Deserialize<'a>,
-{
-    fetch::<(), T>(url, Some("GET"), None).await
-}
-
-async fn fetch<B, T>(url: String, method: Option<&str>, body: Option<&B>) -> Result<T, FetchError>
-where
-    B: serde::ser::Serialize,
-    T: for<'a> serde::de::Deserialize<'a>,
-{
-    use wasm_bindgen::{JsCast, JsValue};
-    use wasm_bindgen_futures::JsFuture;
-    use web_sys::{Request, RequestInit, RequestMode, Response};
-
-    let mut opts = RequestInit::new();
-    opts.method(method.unwrap_or("GET"));
-    opts.mode(RequestMode::Cors);
-    if let Some(body) = body {
-        let json_body = serde_json::to_string(body).map_err(|_| FetchError::SerializeBodyError)?;
-        opts.body(Some(&JsValue::from(json_body)));
-    }
-    // Create a new Fetch `Request` from the `RequestInit` options
-    let request =
-        Request::new_with_str_and_init(&url, &opts).map_err(|_| FetchError::RequestCreateError)?;
-    // Set the headers on the Fetch `Request`
-    request
-        .headers()
-        .set("Accept", "application/json")
-        .map_err(|_| FetchError::RequestHeaderSetError)?;
-    request
-        .headers()
-        .set("Content-Type", "application/json")
-        .map_err(|_| FetchError::RequestHeaderSetError)?;
-    let resp_value = JsFuture::from(mogwai::utils::window().fetch_with_request(&request))
-        .await
-        .map_err(|_| FetchError::FetchError)?;
-    // `resp_value` is a `Response` object.
-    let result: Result<Response, JsValue> = resp_value.dyn_into();
-    if let Ok(resp) = result {
-        match resp.status() {
-            100..=299 => {
-                // Convert this other `Promise` into a rust `Future`.
-                let json = JsFuture::from(resp.json().map_err(|_| FetchError::FetchError)?)
-                    .await
-                    .map_err(|_| FetchError::FetchError)?;
-                // Use serde to parse the JSON into a struct.
-                json.into_serde().map_err(|_| FetchError::ParseError)
-            }
-            404 => Err(FetchError::NotFound),
-            409 => Err(FetchError::Conflict),
-            _ => Err(FetchError::FetchError),
-        }
-    } else {
-        Err(FetchError::FetchError)
-    }
+/// A `Client` making requests to the game API over HTTP.
+pub fn http() -> Client {
+    Rc::new(HttpApi::default())
 }
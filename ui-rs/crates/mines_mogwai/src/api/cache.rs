@@ -0,0 +1,185 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cell::RefCell, collections::HashMap};
+
+/// Prefix of the `sessionStorage` keys mirroring cached responses.
+const STORAGE_PREFIX: &str = "mines:http-cache:";
+
+/// A response body kept for a URL along with the `ETag` the server sent with it, if any.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CacheEntry {
+    pub etag: Option<String>,
+    pub body: String,
+}
+
+/// Response bodies keyed by the URL they were read from. Entries are kept in memory and mirrored
+/// to `sessionStorage` so they survive a reload of the page.
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    entries: RefCell<HashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    /// The entry kept for `url`, looking in `sessionStorage` when it isn't held in memory.
+    pub fn get(&self, url: &str) -> Option<CacheEntry> {
+        if let Some(entry) = self.entries.borrow().get(url) {
+            return Some(entry.clone());
+        }
+        let entry = load(url)?;
+        self.entries
+            .borrow_mut()
+            .insert(String::from(url), entry.clone());
+        Some(entry)
+    }
+
+    /// The body kept for `url` parsed as `T`. An entry which can't be parsed is treated as missing.
+    pub fn read<T: DeserializeOwned>(&self, url: &str) -> Option<T> {
+        let entry = self.get(url)?;
+        serde_json::from_str(&entry.body).ok()
+    }
+
+    /// Keep `entry` as the response for `url`.
+    pub fn put(&self, url: &str, entry: CacheEntry) {
+        save(url, &entry);
+        self.entries.borrow_mut().insert(String::from(url), entry);
+    }
+
+    /// Keep `value` as the response for `url`. No `ETag` is known for it so the next read of `url`
+    /// fetches the whole body again.
+    pub fn put_value<T: Serialize>(&self, url: &str, value: &T) {
+        match serde_json::to_string(value) {
+            Ok(body) => self.put(url, CacheEntry { etag: None, body }),
+            Err(error) => ::log::error!("unable to cache {}: {}", url, error),
+        }
+    }
+
+    /// Forget the response for `url`, returning the entry which was kept for it.
+    pub fn remove(&self, url: &str) -> Option<CacheEntry> {
+        let entry = self.get(url);
+        if let Some(storage) = storage() {
+            if let Err(error) = storage.remove_item(&storage_key(url)) {
+                ::log::warn!("unable to forget cached {}: {:?}", url, error);
+            }
+        }
+        self.entries.borrow_mut().remove(url);
+        entry
+    }
+
+    /// Put back `previous` as the response for `url`, e.g. once an optimistic change to it turns
+    /// out to be wrong. The response is forgotten when `previous` is `None`.
+    pub fn restore(&self, url: &str, previous: Option<CacheEntry>) {
+        match previous {
+            Some(entry) => self.put(url, entry),
+            None => {
+                self.remove(url);
+            }
+        }
+    }
+}
+
+fn load(url: &str) -> Option<CacheEntry> {
+    let json = storage()?.get_item(&storage_key(url)).ok().flatten()?;
+    serde_json::from_str(&json).ok()
+}
+
+fn save(url: &str, entry: &CacheEntry) {
+    let storage = match storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    let result = serde_json::to_string(entry)
+        .map_err(|error| error.to_string())
+        .and_then(|json| {
+            storage
+                .set_item(&storage_key(url), &json)
+                .map_err(|error| format!("{:?}", error))
+        });
+    if let Err(error) = result {
+        ::log::warn!("unable to mirror cached {}: {}", url, error);
+    }
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    // There is no `window` outside of the browser, e.g. when running tests
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    mogwai::utils::window().session_storage().ok().flatten()
+}
+
+fn storage_key(url: &str) -> String {
+    format!("{}{}", STORAGE_PREFIX, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn misses_unknown_url() {
+        let cache = ResponseCache::default();
+        assert_eq!(cache.get("/game"), None);
+        assert_eq!(cache.read::<Vec<u32>>("/game"), None);
+    }
+
+    #[test]
+    fn keeps_etag_with_body() {
+        let cache = ResponseCache::default();
+        let entry = CacheEntry {
+            etag: Some(String::from("W/\"2-l9Fw4VUO7kr8CvBlt4zaMCqXZ0w\"")),
+            body: String::from("[]"),
+        };
+        cache.put("/game", entry.clone());
+        assert_eq!(cache.get("/game"), Some(entry));
+        assert_eq!(cache.read::<Vec<u32>>("/game"), Some(vec![]));
+    }
+
+    #[test]
+    fn put_value_forgets_etag() {
+        let cache = ResponseCache::default();
+        cache.put(
+            "/game",
+            CacheEntry {
+                etag: Some(String::from("\"1\"")),
+                body: String::from("[1]"),
+            },
+        );
+        cache.put_value("/game", &vec![1, 2]);
+        assert_eq!(
+            cache.get("/game"),
+            Some(CacheEntry {
+                etag: None,
+                body: String::from("[1,2]"),
+            })
+        );
+    }
+
+    #[test]
+    fn restores_previous_entry() {
+        let cache = ResponseCache::default();
+        let entry = CacheEntry {
+            etag: Some(String::from("\"1\"")),
+            body: String::from("[1]"),
+        };
+        cache.put("/game", entry.clone());
+        let previous = cache.remove("/game");
+        assert_eq!(previous, Some(entry.clone()));
+        assert_eq!(cache.get("/game"), None);
+        cache.restore("/game", previous);
+        assert_eq!(cache.get("/game"), Some(entry));
+        cache.restore("/game", None);
+        assert_eq!(cache.get("/game"), None);
+    }
+
+    #[test]
+    fn ignores_unreadable_body() {
+        let cache = ResponseCache::default();
+        cache.put(
+            "/game",
+            CacheEntry {
+                etag: None,
+                body: String::from("{"),
+            },
+        );
+        assert_eq!(cache.read::<Vec<u32>>("/game"), None);
+    }
+}
@@ -0,0 +1,145 @@
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{AbortController, AbortSignal};
+
+/// Owns the lifetime of a group of requests. Every request made with a `CancelSignal` from the
+/// `RequestScope` is aborted when the `RequestScope` is dropped.
+pub struct RequestScope {
+    controller: Option<AbortController>,
+}
+
+impl RequestScope {
+    pub fn new() -> Self {
+        Self {
+            controller: AbortController::new().ok(),
+        }
+    }
+
+    /// A `CancelSignal` which is cancelled when this `RequestScope` is dropped.
+    pub fn signal(&self) -> CancelSignal {
+        CancelSignal {
+            signal: self.controller.as_ref().map(AbortController::signal),
+        }
+    }
+}
+
+impl Drop for RequestScope {
+    fn drop(&mut self) {
+        if let Some(controller) = &self.controller {
+            controller.abort();
+        }
+    }
+}
+
+/// Passed along with a request to have the request aborted when the `RequestScope` which created
+/// it is dropped.
+#[derive(Clone, Debug, Default)]
+pub struct CancelSignal {
+    signal: Option<AbortSignal>,
+}
+
+impl CancelSignal {
+    /// A `CancelSignal` which is never cancelled, for requests made by tests.
+    #[cfg(test)]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Whether this signal is ever cancelled, i.e. it was made by a `RequestScope`.
+    pub fn can_cancel(&self) -> bool {
+        self.signal.is_some()
+    }
+
+    /// Whether the `RequestScope` which created this signal has been dropped.
+    pub fn is_cancelled(&self) -> bool {
+        self.signal
+            .as_ref()
+            .map(AbortSignal::aborted)
+            .unwrap_or(false)
+    }
+
+    /// Call `f` once this signal is cancelled, right away if it already has been. `f` is never
+    /// called if the returned `CancelHook` is dropped first.
+    pub fn on_cancel<F: FnOnce() + 'static>(&self, f: F) -> CancelHook {
+        if self.is_cancelled() {
+            f();
+            return CancelHook::default();
+        }
+        let signal = match &self.signal {
+            Some(signal) => signal.clone(),
+            None => return CancelHook::default(),
+        };
+        let mut f = Some(f);
+        let callback: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            if let Some(f) = f.take() {
+                f();
+            }
+        }));
+        let _ = signal.add_event_listener_with_callback("abort", callback.as_ref().unchecked_ref());
+        CancelHook {
+            listener: Some((signal, callback)),
+        }
+    }
+}
+
+/// Keeps a function passed to `CancelSignal::on_cancel` listening for the signal to be cancelled.
+#[derive(Default)]
+pub struct CancelHook {
+    listener: Option<(AbortSignal, Closure<dyn FnMut()>)>,
+}
+
+impl Drop for CancelHook {
+    fn drop(&mut self) {
+        if let Some((signal, callback)) = &self.listener {
+            let _ = signal
+                .remove_event_listener_with_callback("abort", callback.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// Aborts a single request through its own `AbortController` when a `CancelSignal` is cancelled.
+/// Stops listening to the `CancelSignal` when dropped.
+pub struct CancelForward {
+    signal: Option<AbortSignal>,
+    cancelled: Rc<Cell<bool>>,
+    callback: Closure<dyn FnMut()>,
+}
+
+impl CancelForward {
+    pub fn start(cancel: &CancelSignal, controller: &AbortController) -> Self {
+        let cancelled = Rc::new(Cell::new(cancel.is_cancelled()));
+        let callback: Closure<dyn FnMut()> = {
+            let controller = controller.clone();
+            let cancelled = cancelled.clone();
+            Closure::wrap(Box::new(move || {
+                cancelled.set(true);
+                controller.abort();
+            }))
+        };
+        if let Some(signal) = &cancel.signal {
+            let _ =
+                signal.add_event_listener_with_callback("abort", callback.as_ref().unchecked_ref());
+        }
+        Self {
+            signal: cancel.signal.clone(),
+            cancelled,
+            callback,
+        }
+    }
+
+    /// Whether the request was aborted because its `CancelSignal` was cancelled.
+    pub fn cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+}
+
+impl Drop for CancelForward {
+    fn drop(&mut self) {
+        if let Some(signal) = &self.signal {
+            let _ = signal.remove_event_listener_with_callback(
+                "abort",
+                self.callback.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use super::{
+    cache::{CacheEntry, ResponseCache},
+    cancel, retry,
+    socket::{BrowserSocket, LiveSocket, SocketEvent},
+    timeout, ApiFuture, CancelSignal, GameApi, RetryPolicy,
+};
+use crate::{auth, config::api_base_url, tabs};
+use mines_api::{
+    browser::fetch::{self, parse, FetchRequest, Fetched},
+    *,
+};
+use mogwai::prelude::Transmitter;
+use std::rc::Rc;
+
+/// The `GameApi` which makes requests to the game API over HTTP using `window.fetch`. Games and
+/// the list of games are cached so they can be shown before the server has been asked whether
+/// they changed. Abandoned, created and deleted games are shared with the other tabs, the game
+/// route shares the rest once it has applied them.
+#[derive(Clone, Debug, Default)]
+pub struct HttpApi {
+    cache: Rc<ResponseCache>,
+}
+
+impl GameApi for HttpApi {
+    fn get_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<GameState> {
+        Box::pin(get_cached(self.cache.clone(), game_url(game_id), cancel))
+    }
+
+    fn cached_game(&self, game_id: GameId) -> Option<GameState> {
+        self.cache.read(&game_url(game_id))
+    }
+
+    fn get_game_list(
+        &self,
+        query: &GameListQuery,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameSummaryPage> {
+        Box::pin(get_cached(
+            self.cache.clone(),
+            game_summary_url(query),
+            cancel,
+        ))
+    }
+
+    fn cached_game_list(&self, query: &GameListQuery) -> Option<GameSummaryPage> {
+        self.cache.read(&game_summary_url(query))
+    }
+
+    fn get_game_moves(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<Vec<GameMove>> {
+        Box::pin(get(format!("{}/moves", game_url(game_id)), cancel))
+    }
+
+    fn watch_game(
+        &self,
+        game_id: GameId,
+        tx: Transmitter<SocketEvent>,
+    ) -> Option<Box<dyn LiveSocket>> {
+        let access_token = auth::token().map(|token| String::from(token.access_token()));
+        let socket = BrowserSocket::open(&game_live_url(game_id), access_token, tx)?;
+        Some(Box::new(socket))
+    }
+
+    fn patch_game(
+        &self,
+        game_id: GameId,
+        input: GameMoveInput,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameState> {
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            let state = patch_game(game_id, input, cancel).await?;
+            cache.put_value(&game_url(game_id), &state);
+            Ok(state)
+        })
+    }
+
+    fn patch_game_batch(
+        &self,
+        game_id: GameId,
+        moves: Vec<GameMoveInput>,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameMoveBatch> {
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            let batch = patch_game_batch(game_id, moves, cancel).await?;
+            cache.put_value(&game_url(game_id), &batch.game);
+            Ok(batch)
+        })
+    }
+
+    fn abandon_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<GameState> {
+        let cache = self.cache.clone();
+        let url = game_url(game_id);
+        // Show the game as lost right away, putting back what was known if that turns out wrong
+        let previous = cache.get(&url);
+        if let Some(mut game) = cache.read::<GameState>(&url) {
+            game.status = GameStatus::Lost;
+            cache.put_value(&url, &game);
+        }
+        Box::pin(async move {
+            match abandon_game(game_id, cancel).await {
+                Ok(state) => {
+                    cache.put_value(&url, &state);
+                    tabs::post_game(&state);
+                    Ok(state)
+                }
+                Err(error) => {
+                    cache.restore(&url, previous);
+                    Err(error)
+                }
+            }
+        })
+    }
+
+    fn delete_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<()> {
+        let cache = self.cache.clone();
+        let url = game_url(game_id);
+        let previous = cache.remove(&url);
+        Box::pin(async move {
+            match delete_game(game_id, cancel).await {
+                Ok(()) => {
+                    tabs::post_game_deleted(game_id);
+                    Ok(())
+                }
+                Err(error) => {
+                    cache.restore(&url, previous);
+                    Err(error)
+                }
+            }
+        })
+    }
+
+    fn create_game(&self, input: GameCreateInput, cancel: CancelSignal) -> ApiFuture<GameCreated> {
+        Box::pin(async move {
+            let created = create_game(input, cancel).await?;
+            tabs::post_game_created(created.id);
+            Ok(created)
+        })
+    }
+
+    fn login(&self, input: LoginInput, cancel: CancelSignal) -> ApiFuture<TokenizedUser> {
+        Box::pin(login(input, cancel))
+    }
+}
+
+/// Milliseconds to wait for a response before a request is aborted.
+const DEFAULT_TIMEOUT_MS: i32 = 10_000;
+
+/// Controls how a single call to the API is made.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FetchOptions {
+    /// Milliseconds to wait for a response before the request is aborted
+    pub timeout_ms: i32,
+    /// How failed attempts are retried, `None` means the request is only attempted once
+    pub retry: Option<RetryPolicy>,
+    /// Whether the logged in user's token is sent with the request. A rejected token logs the user
+    /// out and sends them to `Route::Login`.
+    pub authorize: bool,
+}
+
+impl FetchOptions {
+    /// Options for requests which can safely be made more than once, i.e. requests which don't
+    /// change anything on the server.
+    pub fn idempotent() -> Self {
+        Self {
+            retry: Some(RetryPolicy::default()),
+            ..Self::default()
+        }
+    }
+}
+
+/// By default requests time out but are never retried.
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            timeout_ms: DEFAULT_TIMEOUT_MS,
+            retry: None,
+            authorize: true,
+        }
+    }
+}
+
+fn game_url(game_id: GameId) -> String {
+    format!("{}/game/{}", api_base_url(), game_id)
+}
+
+/// The socket URL for watching the game identified by `game_id`.
+fn game_live_url(game_id: GameId) -> String {
+    let base = api_base_url();
+    let base = match base.split_once("://") {
+        Some(("https", rest)) => format!("wss://{}", rest),
+        Some(("http", rest)) => format!("ws://{}", rest),
+        _ => base,
+    };
+    format!("{}/game/{}/live", base, game_id)
+}
+
+fn game_list_url() -> String {
+    format!("{}/game", api_base_url())
+}
+
+fn game_summary_url(query: &GameListQuery) -> String {
+    format!("{}/summaries{}", game_list_url(), query.to_query_string())
+}
+
+/// Read `url` through `cache`. When a body is already cached the server is asked whether it is
+/// still current with `If-None-Match`, a `304 Not Modified` answers with the cached body.
+async fn get_cached<T>(
+    cache: Rc<ResponseCache>,
+    url: String,
+    cancel: CancelSignal,
+) -> Result<T, FetchError>
+where
+    T: for<'a> serde::de::Deserialize<'a>,
+{
+    let cached = cache.get(&url);
+    let etag = cached.as_ref().and_then(|entry| entry.etag.as_deref());
+    let options = FetchOptions::idempotent();
+    match fetch_body::<()>(&url, Some("GET"), None, etag, options, &cancel).await? {
+        Fetched::Body { etag, text } => {
+            let value = parse(&text)?;
+            cache.put(&url, CacheEntry { etag, body: text });
+            Ok(value)
+        }
+        Fetched::NotModified => match cached {
+            Some(entry) => parse(&entry.body),
+            None => Err(FetchError::ParseError),
+        },
+    }
+}
+
+/// Add a move defined by `input` to the game identified by `game_id`. Never retried because the
+/// move may have been applied even if the response was lost.
+async fn patch_game(
+    game_id: GameId,
+    input: GameMoveInput,
+    cancel: CancelSignal,
+) -> Result<GameState, FetchError> {
+    let url = game_url(game_id);
+    let options = FetchOptions::default();
+    fetch(url, Some("PATCH"), Some(&input), options, cancel).await
+}
+
+/// Add every move in `moves` to the game identified by `game_id` in one request. Never retried
+/// because some of the moves may have been applied even if the response was lost.
+async fn patch_game_batch(
+    game_id: GameId,
+    moves: Vec<GameMoveInput>,
+    cancel: CancelSignal,
+) -> Result<GameMoveBatch, FetchError> {
+    let url = format!("{}/moves", game_url(game_id));
+    let count = moves.len();
+    let input = GameMoveBatchInput { moves };
+    let options = FetchOptions::default();
+    let batch: GameMoveBatch = fetch(url, Some("POST"), Some(&input), options, cancel).await?;
+    Ok(batch.with_outcomes_for(count))
+}
+
+/// Mark the game identified by `game_id` as lost. Never retried, like every other change to a
+/// game.
+async fn abandon_game(game_id: GameId, cancel: CancelSignal) -> Result<GameState, FetchError> {
+    let url = format!("{}/abandon", game_url(game_id));
+    let options = FetchOptions::default();
+    fetch::<(), GameState>(url, Some("POST"), None, options, cancel).await
+}
+
+/// Delete the game identified by `game_id`. The response has no body.
+async fn delete_game(game_id: GameId, cancel: CancelSignal) -> Result<(), FetchError> {
+    let url = game_url(game_id);
+    let options = FetchOptions::default();
+    fetch_body::<()>(&url, Some("DELETE"), None, None, options, &cancel).await?;
+    Ok(())
+}
+
+/// Create a new game defined by `input`. Never retried because a lost response would otherwise
+/// create more than one game.
+async fn create_game(
+    input: GameCreateInput,
+    cancel: CancelSignal,
+) -> Result<GameCreated, FetchError> {
+    let url = game_list_url();
+    let options = FetchOptions::default();
+    fetch(url, Some("POST"), Some(&input), options, cancel).await
+}
+
+/// Exchange the credentials in `input` for an access token. Never retried and sent without any
+/// existing token.
+async fn login(input: LoginInput, cancel: CancelSignal) -> Result<TokenizedUser, FetchError> {
+    let url = format!("{}/auth/login", api_base_url());
+    let options = FetchOptions {
+        authorize: false,
+        ..FetchOptions::default()
+    };
+    fetch(url, Some("POST"), Some(&input), options, cancel).await
+}
+
+async fn get<T>(url: String, cancel: CancelSignal) -> Result<T, FetchError>
+where
+    T: for<'a> serde::de::Deserialize<'a>,
+{
+    let options = FetchOptions::idempotent();
+    fetch::<(), T>(url, Some("GET"), None, options, cancel).await
+}
+
+/// Make a request to `url` and parse the body of the response as `T`.
+async fn fetch<B, T>(
+    url: String,
+    method: Option<&str>,
+    body: Option<&B>,
+    options: FetchOptions,
+    cancel: CancelSignal,
+) -> Result<T, FetchError>
+where
+    B: serde::ser::Serialize,
+    T: for<'a> serde::de::Deserialize<'a>,
+{
+    match fetch_body(&url, method, body, None, options, &cancel).await? {
+        Fetched::Body { text, .. } => parse(&text),
+        // Only possible when asking whether a cached body is current
+        Fetched::NotModified => Err(FetchError::ParseError),
+    }
+}
+
+/// Make a request to `url`, retrying with exponential backoff according to `options` when the
+/// attempt fails in a way which might succeed later. Stops with `FetchError::Cancelled` as soon as
+/// `cancel` is cancelled. When `etag` is given it is sent as `If-None-Match`.
+async fn fetch_body<B>(
+    url: &str,
+    method: Option<&str>,
+    body: Option<&B>,
+    etag: Option<&str>,
+    options: FetchOptions,
+    cancel: &CancelSignal,
+) -> Result<Fetched, FetchError>
+where
+    B: serde::ser::Serialize,
+{
+    let json_body = body.map(fetch::to_body).transpose()?;
+    let policy = match options.retry {
+        Some(policy) => policy,
+        None => return fetch_once(url, method, json_body.as_deref(), etag, &options, cancel).await,
+    };
+    let mut attempt = 1;
+    loop {
+        match fetch_once(url, method, json_body.as_deref(), etag, &options, cancel).await {
+            Err(error) if retry::is_retryable(&error) => {
+                if attempt >= policy.max_attempts {
+                    ::log::debug!("giving up on {} after {} attempts", url, attempt);
+                    return Err(FetchError::RetriesExhausted);
+                }
+                let delay = policy.delay_ms(attempt, js_sys::Math::random());
+                ::log::debug!("retrying {} in {}ms after {:?}", url, delay, error);
+                mogwai::utils::wait_approximately(delay).await;
+                if cancel.is_cancelled() {
+                    return Err(FetchError::Cancelled);
+                }
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Make a single attempt at a request to `url`, aborting it if no response has been read within
+/// the timeout from `options` or if `cancel` is cancelled first.
+async fn fetch_once(
+    url: &str,
+    method: Option<&str>,
+    json_body: Option<&str>,
+    etag: Option<&str>,
+    options: &FetchOptions,
+    cancel: &CancelSignal,
+) -> Result<Fetched, FetchError> {
+    use web_sys::AbortController;
+
+    if cancel.is_cancelled() {
+        return Err(FetchError::Cancelled);
+    }
+    let controller = AbortController::new().map_err(|_| FetchError::RequestCreateError)?;
+    let signal = controller.signal();
+    let request = FetchRequest {
+        method: method.unwrap_or("GET"),
+        body: json_body,
+        etag,
+        authorize: options.authorize,
+        signal: Some(&signal),
+    };
+    // The timeout covers reading the body as well, it is cleared when `timeout` is dropped. The
+    // same goes for listening to `cancel`.
+    let timeout = timeout::RequestTimeout::start(&controller, options.timeout_ms);
+    let forward = cancel::CancelForward::start(cancel, &controller);
+    match fetch::fetch(url, &request).await {
+        Err(FetchError::FetchError) if forward.cancelled() => Err(FetchError::Cancelled),
+        Err(FetchError::FetchError) if timeout.timed_out() => Err(FetchError::Timeout),
+        Err(FetchError::Server(error)) => {
+            if options.authorize && error.kind == ServerErrorKind::Unauthorized {
+                auth::unauthorized();
+            }
+            Err(FetchError::Server(error))
+        }
+        result => result,
+    }
+}
@@ -0,0 +1,213 @@
+use super::{ApiFuture, CancelSignal, GameApi, LiveSocket, SocketEvent};
+use mines_api::*;
+use mogwai::prelude::Transmitter;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    future::ready,
+};
+
+/// A call made to a `MemoryApi`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiCall {
+    AbandonGame(GameId),
+    CreateGame(GameCreateInput),
+    DeleteGame(GameId),
+    GetGame(GameId),
+    GetGameList(GameListQuery),
+    GetGameMoves(GameId),
+    Login(LoginInput),
+    PatchGame(GameId, GameMoveInput),
+    PatchGameBatch(GameId, Vec<GameMoveInput>),
+}
+
+/// A `GameApi` which answers calls with responses scripted ahead of time and records every call it
+/// receives. A call without a scripted response fails with `FetchError::FetchError`, as if the
+/// server couldn't be reached. Sockets opened with `watch_game` are `MemorySocket`s whose events
+/// are sent by hand.
+#[derive(Default)]
+pub struct MemoryApi {
+    cached_game_lists: RefCell<Vec<(GameListQuery, GameSummaryPage)>>,
+    cached_games: RefCell<HashMap<GameId, GameState>>,
+    calls: RefCell<Vec<ApiCall>>,
+    created: RefCell<VecDeque<Result<GameCreated, FetchError>>>,
+    deleted: RefCell<VecDeque<Result<(), FetchError>>>,
+    game_batches: RefCell<VecDeque<Result<GameMoveBatch, FetchError>>>,
+    game_lists: RefCell<VecDeque<Result<GameSummaryPage, FetchError>>>,
+    game_moves: RefCell<VecDeque<Result<Vec<GameMove>, FetchError>>>,
+    games: RefCell<VecDeque<Result<GameState, FetchError>>>,
+    logins: RefCell<VecDeque<Result<TokenizedUser, FetchError>>>,
+    sockets: RefCell<Vec<Transmitter<SocketEvent>>>,
+    sockets_disabled: Cell<bool>,
+}
+
+/// A socket opened by `MemoryApi::watch_game`.
+pub struct MemorySocket;
+
+impl LiveSocket for MemorySocket {
+    fn close(&self) {}
+}
+
+impl MemoryApi {
+    /// Answer the next `create_game` with `response`.
+    pub fn respond_create_game(&self, response: Result<GameCreated, FetchError>) {
+        self.created.borrow_mut().push_back(response);
+    }
+
+    /// Answer the next `delete_game` with `response`.
+    pub fn respond_delete_game(&self, response: Result<(), FetchError>) {
+        self.deleted.borrow_mut().push_back(response);
+    }
+
+    /// Answer the next `get_game`, `patch_game` or `abandon_game` with `response`.
+    pub fn respond_game(&self, response: Result<GameState, FetchError>) {
+        self.games.borrow_mut().push_back(response);
+    }
+
+    /// Answer the next `patch_game_batch` with `response`.
+    pub fn respond_game_batch(&self, response: Result<GameMoveBatch, FetchError>) {
+        self.game_batches.borrow_mut().push_back(response);
+    }
+
+    /// Answer the next `get_game_list` with `response`.
+    pub fn respond_game_list(&self, response: Result<GameSummaryPage, FetchError>) {
+        self.game_lists.borrow_mut().push_back(response);
+    }
+
+    /// Answer the next `get_game_moves` with `response`.
+    pub fn respond_game_moves(&self, response: Result<Vec<GameMove>, FetchError>) {
+        self.game_moves.borrow_mut().push_back(response);
+    }
+
+    /// Answer the next `login` with `response`.
+    pub fn respond_login(&self, response: Result<TokenizedUser, FetchError>) {
+        self.logins.borrow_mut().push_back(response);
+    }
+
+    /// Report `state` as the last known state of its game.
+    pub fn cache_game(&self, state: GameState) {
+        self.cached_games.borrow_mut().insert(state.id, state);
+    }
+
+    /// Report `page` as the last known page of games described by `query`.
+    pub fn cache_game_list(&self, query: GameListQuery, page: GameSummaryPage) {
+        self.cached_game_lists.borrow_mut().push((query, page));
+    }
+
+    /// Make `watch_game` behave as though sockets aren't available.
+    pub fn disable_sockets(&self) {
+        self.sockets_disabled.set(true);
+    }
+
+    /// Where to send the events for the socket opened by call number `index` to `watch_game`.
+    pub fn socket(&self, index: usize) -> Transmitter<SocketEvent> {
+        self.sockets.borrow()[index].clone()
+    }
+
+    /// Number of sockets opened with `watch_game`.
+    pub fn socket_count(&self) -> usize {
+        self.sockets.borrow().len()
+    }
+
+    /// Every call received so far, in the order they were made.
+    pub fn calls(&self) -> Vec<ApiCall> {
+        self.calls.borrow().clone()
+    }
+
+    fn answer<T: 'static>(
+        &self,
+        call: ApiCall,
+        responses: &RefCell<VecDeque<Result<T, FetchError>>>,
+        cancel: CancelSignal,
+    ) -> ApiFuture<T> {
+        self.calls.borrow_mut().push(call);
+        let response = responses
+            .borrow_mut()
+            .pop_front()
+            .unwrap_or(Err(FetchError::FetchError));
+        Box::pin(ready(if cancel.is_cancelled() {
+            Err(FetchError::Cancelled)
+        } else {
+            response
+        }))
+    }
+}
+
+impl GameApi for MemoryApi {
+    fn get_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<GameState> {
+        self.answer(ApiCall::GetGame(game_id), &self.games, cancel)
+    }
+
+    fn cached_game(&self, game_id: GameId) -> Option<GameState> {
+        self.cached_games.borrow().get(&game_id).cloned()
+    }
+
+    fn get_game_list(
+        &self,
+        query: &GameListQuery,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameSummaryPage> {
+        let call = ApiCall::GetGameList(query.clone());
+        self.answer(call, &self.game_lists, cancel)
+    }
+
+    fn cached_game_list(&self, query: &GameListQuery) -> Option<GameSummaryPage> {
+        let cached = self.cached_game_lists.borrow();
+        cached
+            .iter()
+            .rev()
+            .find(|(cached_query, _)| cached_query == query)
+            .map(|(_, page)| page.clone())
+    }
+
+    fn get_game_moves(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<Vec<GameMove>> {
+        self.answer(ApiCall::GetGameMoves(game_id), &self.game_moves, cancel)
+    }
+
+    fn watch_game(
+        &self,
+        _game_id: GameId,
+        tx: Transmitter<SocketEvent>,
+    ) -> Option<Box<dyn LiveSocket>> {
+        if self.sockets_disabled.get() {
+            return None;
+        }
+        self.sockets.borrow_mut().push(tx);
+        Some(Box::new(MemorySocket))
+    }
+
+    fn patch_game(
+        &self,
+        game_id: GameId,
+        input: GameMoveInput,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameState> {
+        self.answer(ApiCall::PatchGame(game_id, input), &self.games, cancel)
+    }
+
+    fn patch_game_batch(
+        &self,
+        game_id: GameId,
+        moves: Vec<GameMoveInput>,
+        cancel: CancelSignal,
+    ) -> ApiFuture<GameMoveBatch> {
+        let call = ApiCall::PatchGameBatch(game_id, moves);
+        self.answer(call, &self.game_batches, cancel)
+    }
+
+    fn abandon_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<GameState> {
+        self.answer(ApiCall::AbandonGame(game_id), &self.games, cancel)
+    }
+
+    fn delete_game(&self, game_id: GameId, cancel: CancelSignal) -> ApiFuture<()> {
+        self.answer(ApiCall::DeleteGame(game_id), &self.deleted, cancel)
+    }
+
+    fn create_game(&self, input: GameCreateInput, cancel: CancelSignal) -> ApiFuture<GameCreated> {
+        self.answer(ApiCall::CreateGame(input), &self.created, cancel)
+    }
+
+    fn login(&self, input: LoginInput, cancel: CancelSignal) -> ApiFuture<TokenizedUser> {
+        self.answer(ApiCall::Login(input), &self.logins, cancel)
+    }
+}
@@ -0,0 +1,97 @@
+use mines_api::FetchError;
+
+/// Describes how many times, and how far apart, a failed request is attempted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts to make, including the first one
+    pub max_attempts: u32,
+    /// Milliseconds to wait before the first retry, doubled for each retry after it
+    pub base_delay_ms: f64,
+    /// Upper bound, in milliseconds, on the wait between two attempts
+    pub max_delay_ms: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay_ms: 250.0,
+            max_delay_ms: 4_000.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Milliseconds to wait before making retry number `retry` (starting at 1). The delay grows
+    /// exponentially up to `max_delay_ms`, and the second half of it is scaled by `jitter` (a
+    /// value in `[0, 1)`) so clients which failed together do not retry together.
+    pub fn delay_ms(&self, retry: u32, jitter: f64) -> f64 {
+        let exponent = retry.saturating_sub(1).min(31) as i32;
+        let delay = (self.base_delay_ms * 2f64.powi(exponent)).min(self.max_delay_ms);
+        let jitter = jitter.clamp(0.0, 1.0);
+        delay / 2.0 + delay / 2.0 * jitter
+    }
+}
+
+/// Whether a request which failed with `error` might succeed if it is made again. Only failures
+/// which don't depend on the request itself are worth retrying.
+pub fn is_retryable(error: &FetchError) -> bool {
+    match error {
+        FetchError::FetchError | FetchError::Timeout => true,
+        FetchError::Server(error) => error.is_server_failure(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mines_api::ServerError;
+
+    #[test]
+    fn doubles_delay_for_each_retry() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_ms(1, 1.0), 250.0);
+        assert_eq!(policy.delay_ms(2, 1.0), 500.0);
+        assert_eq!(policy.delay_ms(3, 1.0), 1_000.0);
+    }
+
+    #[test]
+    fn caps_delay_at_maximum() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_ms(10, 1.0), 4_000.0);
+        assert_eq!(policy.delay_ms(u32::MAX, 1.0), 4_000.0);
+    }
+
+    #[test]
+    fn jitter_only_shortens_delay_by_half() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_ms(2, 0.0), 250.0);
+        assert_eq!(policy.delay_ms(2, 0.5), 375.0);
+        assert_eq!(policy.delay_ms(2, -1.0), 250.0);
+        assert_eq!(policy.delay_ms(2, 2.0), 500.0);
+    }
+
+    #[test]
+    fn retries_transient_failures() {
+        assert!(is_retryable(&FetchError::FetchError));
+        assert!(is_retryable(&FetchError::Timeout));
+        assert!(is_retryable(&FetchError::Server(
+            ServerError::from_response(503, "")
+        )));
+    }
+
+    #[test]
+    fn does_not_retry_request_failures() {
+        assert!(!is_retryable(&FetchError::Server(
+            ServerError::from_response(404, "")
+        )));
+        assert!(!is_retryable(&FetchError::Server(
+            ServerError::from_response(409, "")
+        )));
+        assert!(!is_retryable(&FetchError::Cancelled));
+        assert!(!is_retryable(&FetchError::ParseError));
+        assert!(!is_retryable(&FetchError::RequestCreateError));
+        assert!(!is_retryable(&FetchError::SerializeBodyError));
+    }
+}
@@ -0,0 +1,92 @@
+use mogwai::prelude::Transmitter;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{MessageEvent, WebSocket};
+
+/// Something which happened on a socket opened with `GameApi::watch_game`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SocketEvent {
+    /// The connection was established
+    Opened,
+    /// A text frame was received
+    Frame(String),
+    /// The connection was lost, or couldn't be established
+    Closed,
+}
+
+/// A socket opened with `GameApi::watch_game`. Nothing more is sent about the socket once it has
+/// been closed.
+pub trait LiveSocket {
+    fn close(&self);
+}
+
+/// A `LiveSocket` using the browser's `WebSocket`. Closed when dropped.
+pub struct BrowserSocket {
+    socket: WebSocket,
+    _on_open: Closure<dyn FnMut()>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut()>,
+}
+
+impl BrowserSocket {
+    /// Connect to `url`, sending everything which happens on the socket into `tx`. `None` when the
+    /// browser can't open the socket at all.
+    ///
+    /// Browsers can't send headers when opening a socket, and a token in the URL ends up in logs,
+    /// so `access_token` is sent as `{ "accessToken": "..." }` in the first frame instead.
+    pub fn open(
+        url: &str,
+        access_token: Option<String>,
+        tx: Transmitter<SocketEvent>,
+    ) -> Option<Self> {
+        let socket = WebSocket::new(url)
+            .map_err(|error| ::log::warn!("unable to open a socket: {:?}", error))
+            .ok()?;
+        let on_open: Closure<dyn FnMut()> = {
+            let socket = socket.clone();
+            let tx = tx.clone();
+            Closure::wrap(Box::new(move || {
+                if let Some(access_token) = &access_token {
+                    let frame = serde_json::json!({ "accessToken": access_token });
+                    if let Err(error) = socket.send_with_str(&frame.to_string()) {
+                        ::log::warn!("unable to authorize socket: {:?}", error);
+                    }
+                }
+                tx.send(&SocketEvent::Opened)
+            }))
+        };
+        let on_message: Closure<dyn FnMut(MessageEvent)> = {
+            let tx = tx.clone();
+            Closure::wrap(Box::new(move |event: MessageEvent| {
+                if let Some(text) = event.data().as_string() {
+                    tx.send(&SocketEvent::Frame(text));
+                }
+            }))
+        };
+        let on_close: Closure<dyn FnMut()> =
+            Closure::wrap(Box::new(move || tx.send(&SocketEvent::Closed)));
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        Some(Self {
+            socket,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_close: on_close,
+        })
+    }
+}
+
+impl LiveSocket for BrowserSocket {
+    fn close(&self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+    }
+}
+
+impl Drop for BrowserSocket {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
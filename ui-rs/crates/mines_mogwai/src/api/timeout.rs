@@ -0,0 +1,50 @@
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::AbortController;
+
+/// Aborts a request through its `AbortController` if it is still running after a number of
+/// milliseconds. The timer is cleared when the `RequestTimeout` is dropped.
+pub struct RequestTimeout {
+    handle: Option<i32>,
+    timed_out: Rc<Cell<bool>>,
+    // Held so the JS callback stays valid until the timer is cleared
+    _callback: Closure<dyn FnMut()>,
+}
+
+impl RequestTimeout {
+    pub fn start(controller: &AbortController, millis: i32) -> Self {
+        let timed_out = Rc::new(Cell::new(false));
+        let callback: Closure<dyn FnMut()> = {
+            let controller = controller.clone();
+            let timed_out = timed_out.clone();
+            Closure::once(move || {
+                timed_out.set(true);
+                controller.abort();
+            })
+        };
+        let handle = mogwai::utils::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                callback.as_ref().unchecked_ref(),
+                millis,
+            )
+            .ok();
+        Self {
+            handle,
+            timed_out,
+            _callback: callback,
+        }
+    }
+
+    /// Whether the request was aborted because the time ran out.
+    pub fn timed_out(&self) -> bool {
+        self.timed_out.get()
+    }
+}
+
+impl Drop for RequestTimeout {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle {
+            mogwai::utils::window().clear_timeout_with_handle(handle);
+        }
+    }
+}
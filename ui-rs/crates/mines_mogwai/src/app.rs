@@ -1,24 +1,28 @@
-use crate::{route_dispatch, Route};
+use crate::{api, auth, route_dispatch, Route};
 use mogwai::prelude::*;
+use std::{cell::RefCell, rc::Rc};
 
 #[derive(Copy, Clone, Debug)]
 pub enum Out {
     Render { route: Route },
     RenderClicks(i32),
+    RenderLoggedIn(bool),
 }
 
-#[derive(Debug)]
 pub struct App {
     click_count: i32,
+    client: api::Client,
     current_route: Route,
 }
 
 impl App {
-    pub fn gizmo(initial_route: Route) -> Gizmo<Self> {
+    /// Create the `App` starting at `initial_route` and making requests through `client`.
+    pub fn gizmo(initial_route: Route, client: api::Client) -> Gizmo<Self> {
         let tx_model = Transmitter::new();
         let rx_view = Receiver::new();
         let app = App {
             click_count: 0,
+            client,
             current_route: initial_route,
         };
         Gizmo::from_parts(app, tx_model, rx_view)
@@ -32,6 +36,10 @@ impl Component for App {
 
     fn update(&mut self, msg: &Route, tx_view: &Transmitter<Out>, _sub: &Subscriber<Route>) {
         if self.current_route != *msg {
+            // Remember where the user was so they can be sent back after logging in
+            if *msg == Route::Login {
+                auth::set_return_to(self.current_route);
+            }
             self.current_route = *msg;
             tx_view.send(&Out::Render {
                 route: self.current_route,
@@ -40,6 +48,8 @@ impl Component for App {
         }
         self.click_count += 1;
         tx_view.send(&Out::RenderClicks(self.click_count));
+        // Logging in and out always comes with a change of route
+        tx_view.send(&Out::RenderLoggedIn(auth::token().is_some()));
     }
 
     #[allow(unused_braces)]
@@ -49,12 +59,28 @@ impl Component for App {
             Out::RenderClicks(count) => Some(format!("{} times", count)),
             _ => None,
         });
+        // Requests belong to the route which made them, replacing the scope when the route changes
+        // cancels anything the previous route still has in flight.
+        let scope = Rc::new(RefCell::new(api::RequestScope::new()));
+        let client = self.client.clone();
+        let initial_view =
+            route_dispatch::view_builder(tx.clone(), self.current_route, &client, &scope.borrow());
+        // Send the user to log in whenever the API rejects their token
+        auth::redirect_unauthorized(tx.clone());
+        let rx_session = rx.branch_filter_map(|msg| match msg {
+            Out::RenderLoggedIn(logged_in) => Some(*logged_in),
+            _ => None,
+        });
+        let session_view = session_link(tx, auth::token().is_some(), rx_session);
         let dispatch = tx.clone();
         let rx_main = rx.branch_filter_map(move |msg| match msg {
-            Out::Render { route } => Some(Patch::Replace {
-                index: 0,
-                value: route_dispatch::view_builder(dispatch.clone(), *route),
-            }),
+            Out::Render { route } => {
+                let next_scope = api::RequestScope::new();
+                let value =
+                    route_dispatch::view_builder(dispatch.clone(), *route, &client, &next_scope);
+                scope.replace(next_scope);
+                Some(Patch::Replace { index: 0, value })
+            }
             _ => None,
         });
         builder! {
@@ -91,11 +117,61 @@ impl Component for App {
                     >
                         "Not Found"
                     </a>
+                    {session_view}
                 </nav>
                 <slot patch:children=rx_main>
-                    {route_dispatch::view_builder(tx.clone(), self.current_route)}
+                    {initial_view}
                 </slot>
             </div>
         }
     }
 }
+
+/// Show a link to log in or, once logged in, a link to log out. Logging out returns to
+/// `Route::Home`.
+#[allow(unused_braces)]
+fn session_link(
+    tx: &Transmitter<Route>,
+    logged_in: bool,
+    rx_logged_in: Receiver<bool>,
+) -> ViewBuilder<HtmlElement> {
+    let link = move |tx: &Transmitter<Route>, logged_in: bool| {
+        if logged_in {
+            builder! {
+                <a
+                    href="/"
+                    on:click=tx.contra_map(|e: &Event| {
+                        e.prevent_default();
+                        auth::clear();
+                        Route::Home
+                    })
+                >
+                    "Log Out"
+                </a>
+            }
+        } else {
+            builder! {
+                <a
+                    href="/login"
+                    on:click=tx.contra_map(|e: &Event| {
+                        e.prevent_default();
+                        Route::Login
+                    })
+                >
+                    "Log In"
+                </a>
+            }
+        }
+    };
+    let initial_link = link(tx, logged_in);
+    let dispatch = tx.clone();
+    let rx_link = rx_logged_in.branch_map(move |logged_in| Patch::Replace {
+        index: 0,
+        value: link(&dispatch, *logged_in),
+    });
+    builder! {
+        <slot name="session" patch:children=rx_link>
+            {initial_link}
+        </slot>
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{api::AuthToken, Route};
+use mines_api::browser::auth;
+use mogwai::prelude::Transmitter;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static RETURN_TO: Cell<Option<Route>> = Cell::new(None);
+    static ON_UNAUTHORIZED: RefCell<Option<Transmitter<Route>>> = RefCell::new(None);
+}
+
+pub use auth::{clear, token};
+
+/// Store `token` so it is sent with all subsequent API requests, including after a reload.
+pub fn set(token: AuthToken) {
+    if let Err(error) = auth::set(token) {
+        ::log::error!("unable to save auth token: {}", error);
+    }
+}
+
+/// Remember `route` so the user can be sent back to it after logging in.
+pub fn set_return_to(route: Route) {
+    RETURN_TO.with(|current| current.set(Some(route)));
+}
+
+/// The `Route` to show after logging in, `Route::Home` if no other route was remembered.
+pub fn take_return_to() -> Route {
+    RETURN_TO
+        .with(|current| current.take())
+        .unwrap_or(Route::Home)
+}
+
+/// Send `Route::Login` into `dispatch` whenever the API rejects the `AuthToken`.
+pub fn redirect_unauthorized(dispatch: Transmitter<Route>) {
+    ON_UNAUTHORIZED.with(|current| current.replace(Some(dispatch)));
+}
+
+/// Log the user out and ask them to log in again because the API rejected the `AuthToken`.
+pub fn unauthorized() {
+    clear();
+    let dispatch = ON_UNAUTHORIZED.with(|current| current.borrow().clone());
+    if let Some(dispatch) = dispatch {
+        dispatch.send(&Route::Login);
+    }
+}
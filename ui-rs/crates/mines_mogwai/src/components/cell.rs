@@ -1,37 +1,222 @@
-use crate::model::{BoardValue, CellInteract, CellInteractKind, CellUpdate};
+use crate::api;
+use crate::config;
+use crate::model::{
+    cell_label, touch_position, Board, BoardValue, CellInput, CellInteract, CellInteractKind,
+    CellMark, CellMarks, CellUpdate, Cursor, CursorMove, KeyCommand, LongPress,
+};
 use mogwai::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// What every cell on a board shares.
+pub struct BoardLinks {
+    /// The board as confirmed by the API, for finding neighbours
+    pub board: Rc<RefCell<Board>>,
+    /// Changes to the board confirmed by the API, received by every cell
+    pub rx_board: Receiver<CellUpdate>,
+    /// Changes the player made to other cells which haven't been confirmed yet, e.g. by chording,
+    /// received by every cell
+    pub tx_optimistic: Transmitter<CellUpdate>,
+    pub rx_optimistic: Receiver<CellUpdate>,
+    /// What the player needs to know about the moves they made, received by every cell
+    pub rx_marks: Receiver<CellMarks>,
+    /// Moves of the keyboard cursor
+    pub tx_cursor: Transmitter<CursorMove>,
+    /// Where the keyboard cursor is after each move, as (row, column)
+    pub rx_cursor: Receiver<(usize, usize)>,
+    /// The keyboard cursor, moved by `tx_cursor`
+    pub cursor: Rc<RefCell<Cursor>>,
+}
+
+/// What a `BoardCell` is told.
+#[derive(Clone, Debug)]
+pub enum CellMsg {
+    /// The player asked something of the cell
+    Input(CellInput),
+    /// The API confirmed the cell shows the value
+    Confirmed(BoardValue),
+    /// The player changed the cell, the value is shown until the API confirms a value
+    Optimistic(BoardValue),
+    /// The cell's mark changed, `None` once nothing about its moves needs pointing out
+    Mark(Option<CellMark>),
+}
+
+/// What a `BoardCell` shows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CellView {
+    pub value: BoardValue,
+    pub mark: Option<CellMark>,
+}
+
+impl CellView {
+    /// Describe the cell at `row` and `column` for screen readers, including its mark.
+    fn label(&self, row: usize, column: usize) -> String {
+        let label = cell_label(row, column, self.value);
+        match &self.mark {
+            Some(mark) => format!("{}, {}", label, mark.label()),
+            None => label,
+        }
+    }
+
+    /// The class of the cell, empty unless it is marked.
+    fn class(&self) -> &'static str {
+        self.mark.as_ref().map(CellMark::class).unwrap_or_default()
+    }
+}
 
 pub struct BoardCell {
+    /// The board the cell is on, as confirmed, for finding its neighbours
+    board: Rc<RefCell<Board>>,
     column: usize,
-    current_display: BoardValue,
+    /// What the API last said the cell shows, which decides what input does
+    confirmed: BoardValue,
+    cursor: Rc<RefCell<Cursor>>,
+    /// What the player needs to know about the moves made on the cell
+    mark: Option<CellMark>,
+    /// What the cell shows until the API confirms a value, if the player changed it
+    optimistic: Option<BoardValue>,
     row: usize,
+    rx_cursor: Receiver<(usize, usize)>,
     tx_cells: Transmitter<CellInteract>,
+    tx_cursor: Transmitter<CursorMove>,
+    tx_optimistic: Transmitter<CellUpdate>,
 }
 
 impl BoardCell {
+    /// Create the cell at `column` and `row` of the board shared through `links`, showing
+    /// `optimistic` until the API confirms a value if the player already changed it. Interactions
+    /// are transmitted to `tx`.
     pub fn gizmo(
         column: usize,
         row: usize,
         initial_value: BoardValue,
+        optimistic: Option<BoardValue>,
+        links: &BoardLinks,
         tx: &Transmitter<CellInteract>,
-        rx: &Receiver<CellUpdate>,
     ) -> Gizmo<Self> {
+        // The cell's model follows every change to the cell, so what it decides matches what is
+        // shown
+        let tx_in = Transmitter::new();
+        links
+            .rx_board
+            .branch()
+            .forward_filter_map(&tx_in, move |update| {
+                update.value_at(row, column).map(CellMsg::Confirmed)
+            });
+        links
+            .rx_optimistic
+            .branch()
+            .forward_filter_map(&tx_in, move |update| {
+                update.value_at(row, column).map(CellMsg::Optimistic)
+            });
+        // Only changes to the cell's own mark are passed on
+        let mark = RefCell::new(None);
+        links
+            .rx_marks
+            .branch()
+            .forward_filter_map(&tx_in, move |marks| {
+                let next = marks.get(row, column).cloned();
+                if *mark.borrow() == next {
+                    None
+                } else {
+                    mark.replace(next.clone());
+                    Some(CellMsg::Mark(next))
+                }
+            });
         Gizmo::from_parts(
             BoardCell {
+                board: links.board.clone(),
                 column,
-                current_display: initial_value.into(),
+                confirmed: initial_value,
+                cursor: links.cursor.clone(),
+                mark: None,
+                optimistic,
                 row,
+                rx_cursor: links.rx_cursor.branch(),
                 tx_cells: tx.clone(),
+                tx_cursor: links.tx_cursor.clone(),
+                tx_optimistic: links.tx_optimistic.clone(),
             },
-            Transmitter::new(),
-            rx.branch(),
+            tx_in,
+            Receiver::new(),
         )
     }
+
+    /// What the cell shows.
+    fn shown(&self) -> BoardValue {
+        self.optimistic.unwrap_or(self.confirmed)
+    }
+
+    fn cell_view(&self) -> CellView {
+        CellView {
+            value: self.shown(),
+            mark: self.mark.clone(),
+        }
+    }
+
+    /// Act on `input` according to the confirmed value of the cell, showing the result right away
+    /// through `tx`. A cell waiting on a change the player made ignores input until the change is
+    /// confirmed.
+    fn input(&mut self, input: CellInput, tx: &Transmitter<CellView>) {
+        if self.optimistic.is_some() {
+            return;
+        }
+        let kind = match (self.confirmed, input) {
+            // The refused move is made again as it was
+            (_, CellInput::Retry) => match &self.mark {
+                Some(CellMark::Rejected(rejected)) => {
+                    CellInteractKind::from(rejected.input.move_type)
+                }
+                _ => return,
+            },
+            // Opening a revealed number again chords it instead
+            (BoardValue::Open(_), CellInput::Open) | (BoardValue::Open(_), CellInput::Chord) => {
+                return self.chord()
+            }
+            (_, CellInput::Chord) => return,
+            // Only closed cells can be flagged or opened, pending cells wait on the API
+            (value, input) => match api::GameMoveType::for_cell(value, input == CellInput::Flag) {
+                Some(move_type) => CellInteractKind::from(move_type),
+                None => return,
+            },
+        };
+        let value = match kind {
+            CellInteractKind::RemoveFlag => BoardValue::Closed,
+            CellInteractKind::Flag => BoardValue::Flag,
+            CellInteractKind::Open => BoardValue::Pending,
+        };
+        // Show the result right away because the cell "knows" what the move does to it
+        self.optimistic = Some(value);
+        tx.send(&self.cell_view());
+        // Send the `CellInteract` out, it will eventually result in a confirmed value
+        self.tx_cells.send(&CellInteract {
+            column: self.column,
+            row: self.row,
+            kind,
+        });
+    }
+
+    /// Open every closed neighbour of this revealed number once all of its mines are flagged. The
+    /// neighbours show as pending straight away.
+    fn chord(&self) {
+        let neighbours = self.board.borrow().chord(self.row, self.column);
+        for (row, column) in neighbours {
+            self.tx_optimistic.send(&CellUpdate::Single {
+                row,
+                column,
+                value: BoardValue::Pending,
+            });
+            self.tx_cells.send(&CellInteract {
+                row,
+                column,
+                kind: CellInteractKind::Open,
+            });
+        }
+    }
 }
 
 impl Component for BoardCell {
-    type ModelMsg = BoardCellInteract;
-    type ViewMsg = CellUpdate;
+    type ModelMsg = CellMsg;
+    type ViewMsg = CellView;
     type DomNode = HtmlElement;
 
     fn update(
@@ -40,37 +225,16 @@ impl Component for BoardCell {
         tx: &Transmitter<Self::ViewMsg>,
         _sub: &Subscriber<Self::ModelMsg>,
     ) {
-        // The kind of `CellInteract` to send out depends on the current state
-        // of the `BoardCell`
-        let kind = match (self.current_display, msg) {
-            (BoardValue::Flag, _) => CellInteractKind::RemoveFlag,
-            (BoardValue::Closed, BoardCellInteract::Flag) => CellInteractKind::Flag,
-            _ => CellInteractKind::Open,
-        };
-        let interaction = CellInteract {
-            column: self.column,
-            row: self.row,
-            kind,
-        };
-        let board_value = match kind {
-            CellInteractKind::RemoveFlag => BoardValue::Closed,
-            CellInteractKind::Flag => BoardValue::Flag,
-            CellInteractKind::Open => BoardValue::Pending,
-        };
-        // `current_display` will get out of sync with the displayed value if the cell moves to an
-        // open state because messages from the `Receiver` don't have any way update the
-        // `current_display` value.
-        self.current_display = board_value;
-        // Optimistically update the cell because certain actions don't depend
-        // on the game state so the `BoardCell` "knows" the result
-        tx.send(&CellUpdate::Single {
-            column: self.column,
-            row: self.row,
-            value: board_value,
-        });
-        // Send the `CellInteract` out it (may) eventually result in receiving
-        // a `ViewMessage`
-        self.tx_cells.send(&interaction);
+        match msg {
+            CellMsg::Input(input) => return self.input(*input, tx),
+            CellMsg::Confirmed(value) => {
+                self.confirmed = *value;
+                self.optimistic = None;
+            }
+            CellMsg::Optimistic(value) => self.optimistic = Some(*value),
+            CellMsg::Mark(mark) => self.mark = mark.clone(),
+        }
+        tx.send(&self.cell_view());
     }
 
     #[allow(unused_braces)]
@@ -81,40 +245,236 @@ impl Component for BoardCell {
     ) -> ViewBuilder<HtmlElement> {
         let col = self.column;
         let row = self.row;
-        let rx_value: Receiver<BoardValue> = rx.branch_filter_map(move |update| match update {
-            CellUpdate::All { cells } => cells.get(row).map(|r| r.get(col)).flatten().map(|s| *s),
-            CellUpdate::Single {
-                row: y,
-                column: x,
-                value,
-            } if *x == col && *y == row => Some(*value),
-            _ => None,
+        let rx_text = rx.branch_map(|view| view.value.to_string());
+        // Screen readers read the label rather than the glyph shown
+        let rx_label = rx.branch_map(move |view| view.label(row, col));
+        let rx_class = rx.branch_map(|view| view.class().to_string());
+        let tx = tx.contra_map(|input: &CellInput| CellMsg::Input(*input));
+        // Right clicks and long presses flag the cell, scrolling the board doesn't
+        let press = Rc::new(RefCell::new(LongPress::new(config::long_press_ms())));
+        let context_press = press.clone();
+        let tx_context_menu = tx.contra_filter_map(move |event: &Event| {
+            event.prevent_default();
+            context_press.borrow_mut().context_menu()
+        });
+        let start_press = press.clone();
+        let tx_touch_start = tx.contra_filter_map(move |event: &Event| {
+            if let Some((x, y)) = touch_position(event) {
+                start_press.borrow_mut().start(event.time_stamp(), x, y);
+            }
+            None
+        });
+        let move_press = press.clone();
+        let tx_touch_move = tx.contra_filter_map(move |event: &Event| {
+            if let Some((x, y)) = touch_position(event) {
+                move_press.borrow_mut().move_to(x, y);
+            }
+            None
+        });
+        let end_press = press.clone();
+        let tx_touch_end = tx.contra_filter_map(move |event: &Event| {
+            let outcome = end_press.borrow_mut().end(event.time_stamp());
+            if outcome.prevents_click() {
+                event.prevent_default();
+            }
+            outcome.input()
+        });
+        let tx_touch_cancel = tx.contra_filter_map(move |_: &Event| {
+            press.borrow_mut().cancel();
+            None
+        });
+        // Only the cell under the keyboard cursor can be tabbed to, keys pressed on it either act
+        // on it or move the cursor, which follows focus moved some other way
+        let position = (row, col);
+        let tab_index = |has_cursor| if has_cursor { "0" } else { "-1" };
+        let rx_tab_index = self
+            .rx_cursor
+            .branch_map(move |cursor| tab_index(*cursor == position).to_string());
+        let (tx_built, rx_built) = txrx::<HtmlElement>();
+        let rx_focus =
+            self.rx_cursor.branch_filter_map(
+                move |cursor| {
+                    if *cursor == position {
+                        Some(())
+                    } else {
+                        None
+                    }
+                },
+            );
+        rx_built.respond(move |element| {
+            let element = element.clone();
+            rx_focus.branch().respond(move |_| {
+                // Focus can only fail for elements which can't hold it
+                let _ = element.focus();
+            });
+        });
+        let tx_cursor = self.tx_cursor.clone();
+        let tx_key_down =
+            tx.contra_filter_map(move |event: &Event| match KeyCommand::from_event(event)? {
+                KeyCommand::Move(movement) => {
+                    tx_cursor.send(&movement);
+                    None
+                }
+                KeyCommand::Input(input) => Some(input),
+            });
+        // Focus moved by the cursor is already where the cursor is
+        let cursor = self.cursor.clone();
+        let tx_focus = self.tx_cursor.contra_filter_map(move |_: &Event| {
+            if cursor.borrow().position() == position {
+                None
+            } else {
+                Some(CursorMove::To { row, column: col })
+            }
         });
-        let rx_text = rx_value.branch_map(|update| update.to_string());
+        // A cell with a refused move offers to make it again. The button stays out of the tab order
+        // as the cell itself takes `r` to retry.
+        let tx_retry = tx.contra_map(|event: &Event| {
+            event.stop_propagation();
+            CellInput::Retry
+        });
+        let rx_retry = rx.branch_filter_fold(false, move |shown: &mut bool, view: &CellView| {
+            let rejected = matches!(view.mark, Some(CellMark::Rejected(_)));
+            if rejected == *shown {
+                return None;
+            }
+            *shown = rejected;
+            if rejected {
+                Some(Patch::PushBack {
+                    value: builder! {
+                        <button class="retry" tabindex="-1" aria-label="Retry" on:click=tx_retry.clone()>
+                            "↻"
+                        </button>
+                    },
+                })
+            } else {
+                Some(Patch::PopBack)
+            }
+        });
+        let initial = self.cell_view();
         builder! {
             <td
-                on:click=tx.contra_map(|event: &Event| BoardCellInteract::from(event))
+                class=(initial.class().to_string(), rx_class)
+                role="gridcell"
+                aria-colindex=(col + 1).to_string()
+                aria-label=(initial.label(row, col), rx_label)
+                tabindex=(tab_index(self.cursor.borrow().position() == position).to_string(), rx_tab_index)
+                post:build=tx_built
+                on:keydown=tx_key_down
+                on:focus=tx_focus
+                on:click=tx.contra_map(CellInput::from_click)
+                on:auxclick=tx.contra_filter_map(CellInput::from_aux_click)
+                on:contextmenu=tx_context_menu
+                on:touchstart=tx_touch_start
+                on:touchmove=tx_touch_move
+                on:touchend=tx_touch_end
+                on:touchcancel=tx_touch_cancel
+                patch:children=rx_retry
             >
                 // Cells initialize to empty but may update if revealed or clicked
-                {(self.current_display.to_string(), rx_text)}
+                {(initial.value.to_string(), rx_text)}
             </td>
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub enum BoardCellInteract {
-    Flag,
-    Open,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl From<&Event> for BoardCellInteract {
-    fn from(event: &Event) -> Self {
-        let event: Option<&web_sys::MouseEvent> = event.dyn_ref();
-        if event.map(|e| e.alt_key() || e.ctrl_key()).unwrap_or(false) {
-            BoardCellInteract::Flag
-        } else {
-            BoardCellInteract::Open
-        }
+    /// A cell at row 0, column 0 of a 1x1 board showing `value`, along with the links it is
+    /// connected by and every interaction it sends.
+    fn cell(
+        value: BoardValue,
+    ) -> (
+        Gizmo<BoardCell>,
+        Transmitter<CellUpdate>,
+        Rc<RefCell<Vec<CellInteract>>>,
+    ) {
+        let (tx_board, rx_board) = txrx();
+        let (tx_optimistic, rx_optimistic) = txrx();
+        let (tx_cursor, _) = txrx();
+        let links = BoardLinks {
+            board: Rc::new(RefCell::new(Board::new(vec![vec![value]]))),
+            rx_board,
+            tx_optimistic,
+            rx_optimistic,
+            rx_marks: Receiver::new(),
+            tx_cursor,
+            rx_cursor: Receiver::new(),
+            cursor: Rc::new(RefCell::new(Cursor::new(1, 1))),
+        };
+        let (tx, rx) = txrx();
+        let sent = Rc::new(RefCell::new(vec![]));
+        let remote_sent = sent.clone();
+        rx.respond(move |interaction: &CellInteract| remote_sent.borrow_mut().push(*interaction));
+        (
+            BoardCell::gizmo(0, 0, value, None, &links, &tx),
+            tx_board,
+            sent,
+        )
+    }
+
+    fn kinds(sent: &Rc<RefCell<Vec<CellInteract>>>) -> Vec<CellInteractKind> {
+        sent.borrow()
+            .iter()
+            .map(|interaction| interaction.kind)
+            .collect()
+    }
+
+    #[test]
+    fn decides_from_confirmed_value() {
+        let (gizmo, tx_board, sent) = cell(BoardValue::Closed);
+        // A flag placed elsewhere, e.g. in another tab
+        tx_board.send(&CellUpdate::All {
+            cells: vec![vec![BoardValue::Flag]],
+        });
+        gizmo.send(&CellMsg::Input(CellInput::Open));
+        assert_eq!(kinds(&sent), vec![CellInteractKind::RemoveFlag]);
+    }
+
+    #[test]
+    fn waits_on_optimistic_value() {
+        let (gizmo, tx_board, sent) = cell(BoardValue::Closed);
+        gizmo.send(&CellMsg::Input(CellInput::Flag));
+        assert_eq!(gizmo.state_ref().shown(), BoardValue::Flag);
+        assert_eq!(gizmo.state_ref().confirmed, BoardValue::Closed);
+        gizmo.send(&CellMsg::Input(CellInput::Open));
+        assert_eq!(kinds(&sent), vec![CellInteractKind::Flag]);
+        tx_board.send(&CellUpdate::All {
+            cells: vec![vec![BoardValue::Flag]],
+        });
+        gizmo.send(&CellMsg::Input(CellInput::Open));
+        assert_eq!(
+            kinds(&sent),
+            vec![CellInteractKind::Flag, CellInteractKind::RemoveFlag]
+        );
+    }
+
+    #[test]
+    fn retries_rejected_move() {
+        let (gizmo, _, sent) = cell(BoardValue::Closed);
+        // Nothing to retry without a refused move
+        gizmo.send(&CellMsg::Input(CellInput::Retry));
+        assert!(sent.borrow().is_empty());
+        let rejected = crate::model::RejectedMove {
+            input: api::GameMoveInput {
+                row: 0,
+                column: 0,
+                move_type: api::GameMoveType::Flag,
+            },
+            reason: crate::model::RejectReason::NotApplied,
+        };
+        let ssr = View::from(gizmo.view_builder());
+        gizmo.send(&CellMsg::Mark(Some(CellMark::Rejected(rejected))));
+        assert!(ssr.html_string().contains(
+            "class=\"rejected\" role=\"gridcell\" aria-colindex=\"1\" \
+             aria-label=\"row 1 column 1, unopened, couldn't flag: The move was not applied to the game, press R to retry\""
+        ));
+        assert!(ssr.html_string().contains("<button class=\"retry\""));
+        gizmo.send(&CellMsg::Input(CellInput::Retry));
+        assert_eq!(kinds(&sent), vec![CellInteractKind::Flag]);
+        assert_eq!(gizmo.state_ref().shown(), BoardValue::Flag);
+        gizmo.send(&CellMsg::Mark(None));
+        assert!(!ssr.html_string().contains("<button"));
     }
 }
@@ -1,43 +1,146 @@
-use crate::model::{CellInteract, CellUpdate};
+use crate::components::cell::BoardLinks;
+use crate::model::{Board, CellInteract, CellMarks, CellUpdate, Cursor, CursorMove};
 use mogwai::prelude::*;
+use std::{cell::RefCell, rc::Rc};
 
-/// Create a `<tr>` representing a row of game cells. Interactions are transmitted to `tx` and new
-/// text to display is received by `rx`.
+/// Create a `<tr>` representing a row of game cells on the board shared through `links`, showing
+/// the values of `optimistic` over the cells they change. Interactions are transmitted to `tx`.
 fn board_row<'a>(
     row: usize,
     initial_cells: Vec<crate::model::BoardValue>,
+    optimistic: &[CellUpdate],
+    links: &BoardLinks,
     tx: &Transmitter<CellInteract>,
-    rx: &Receiver<CellUpdate>,
 ) -> ViewBuilder<HtmlElement> {
     use crate::components::cell::BoardCell;
-    let children = initial_cells
-        .into_iter()
-        .enumerate()
-        .map(|(col, value)| BoardCell::gizmo(col, row, value, tx, rx).view_builder());
-    let mut tr = builder! { <tr /> };
+    let children = initial_cells.into_iter().enumerate().map(|(col, value)| {
+        let shown = optimistic
+            .iter()
+            .rev()
+            .find_map(|update| update.value_at(row, col));
+        BoardCell::gizmo(col, row, value, shown, links, tx).view_builder()
+    });
+    let mut tr = builder! { <tr role="row" aria-rowindex=(row + 1).to_string() /> };
     for child in children {
         tr.with(child);
     }
     tr
 }
 
+/// Create the board for `cells` as confirmed by the API, with the changes in `optimistic` which
+/// aren't confirmed yet shown over them.
 #[allow(unused_braces)]
 pub fn board<'a>(
     cells: Vec<Vec<crate::model::BoardValue>>,
+    optimistic: &[CellUpdate],
     tx: &Transmitter<CellInteract>,
     rx: &Receiver<CellUpdate>,
+    tx_optimistic: &Transmitter<CellUpdate>,
+    rx_marks: &Receiver<CellMarks>,
 ) -> ViewBuilder<HtmlElement> {
+    // The `Board` follows every change confirmed by the API so neighbours can be looked up. Changes
+    // which aren't confirmed yet, sent through `tx_optimistic` by the game or by the cells when
+    // the player chords, are only shown.
+    let board = Rc::new(RefCell::new(Board::new(cells.clone())));
+    let board_model = board.clone();
+    rx.branch()
+        .respond(move |update| board_model.borrow_mut().apply(update));
+    let rx_optimistic = tx_optimistic.spawn_recv();
+    // A single keyboard cursor moves around the board. It lives as long as the board so changes
+    // to the cells, including `CellUpdate::All`, leave it where it is.
+    let rows = cells.len();
+    let columns = cells.first().map(Vec::len).unwrap_or(0);
+    let cursor = Rc::new(RefCell::new(Cursor::new(rows, columns)));
+    let (tx_cursor, rx_cursor) = txrx::<CursorMove>();
+    let rx_position = Receiver::new();
+    let cursor_model = cursor.clone();
+    rx_cursor.forward_map(&rx_position.new_trns(), move |movement| {
+        cursor_model.borrow_mut().apply(*movement)
+    });
+    let links = BoardLinks {
+        board,
+        rx_board: rx.branch(),
+        tx_optimistic: tx_optimistic.clone(),
+        rx_optimistic,
+        rx_marks: rx_marks.branch(),
+        tx_cursor,
+        rx_cursor: rx_position,
+        cursor,
+    };
     let children = cells
         .into_iter()
         .enumerate()
-        .map(|(row, cells)| board_row(row, cells, tx, rx));
+        .map(|(row, cells)| board_row(row, cells, optimistic, &links, tx));
     let mut tbody: ViewBuilder<HtmlElement> = builder! { <tbody /> };
     for child in children {
         tbody.with(child);
     }
     builder! {
-        <table>
+        <table
+            role="grid"
+            aria-label="Game board"
+            aria-rowcount=rows.to_string()
+            aria-colcount=columns.to_string()
+        >
             {tbody}
         </table>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BoardValue, CellMark};
+
+    #[test]
+    fn labels_grid_for_screen_readers() {
+        let tx = Transmitter::new();
+        let (tx_update, rx_update) = txrx();
+        let cells = vec![vec![BoardValue::Open(2), BoardValue::Closed]];
+        let ssr = View::from(board(
+            cells,
+            &[],
+            &tx,
+            &rx_update,
+            &Transmitter::new(),
+            &Receiver::new(),
+        ));
+        assert_eq!(
+            ssr.html_string(),
+            "<table role=\"grid\" aria-label=\"Game board\" aria-rowcount=\"1\" aria-colcount=\"2\">\
+             <tbody><tr role=\"row\" aria-rowindex=\"1\">\
+             <td class=\"\" role=\"gridcell\" aria-colindex=\"1\" aria-label=\"row 1 column 1, 2 adjacent mines\" tabindex=\"0\">2</td> \
+             <td class=\"\" role=\"gridcell\" aria-colindex=\"2\" aria-label=\"row 1 column 2, unopened\" tabindex=\"-1\"></td>\
+             </tr></tbody></table>"
+        );
+        tx_update.send(&CellUpdate::Single {
+            row: 0,
+            column: 1,
+            value: BoardValue::Flag,
+        });
+        assert!(ssr.html_string().contains(
+            "<td class=\"\" role=\"gridcell\" aria-colindex=\"2\" aria-label=\"row 1 column 2, flagged\" tabindex=\"-1\">F</td>"
+        ));
+    }
+
+    #[test]
+    fn marks_unsynced_cells() {
+        let tx = Transmitter::new();
+        let (tx_marks, rx_marks) = txrx();
+        let cells = vec![vec![BoardValue::Flag, BoardValue::Closed]];
+        let ssr = View::from(board(
+            cells,
+            &[],
+            &tx,
+            &Receiver::new(),
+            &Transmitter::new(),
+            &rx_marks,
+        ));
+        tx_marks.send(&CellMarks::new(vec![(0, 0, CellMark::Unsynced)]));
+        assert!(ssr.html_string().contains(
+            "<td class=\"unsynced\" role=\"gridcell\" aria-colindex=\"1\" aria-label=\"row 1 column 1, flagged, waiting to sync\" tabindex=\"0\">F</td>"
+        ));
+        tx_marks.send(&CellMarks::default());
+        assert!(!ssr.html_string().contains("unsynced"));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    api,
+    model::{GameAction, GameActionEvent},
+};
+use mogwai::prelude::*;
+
+/// Buttons for the `GameAction`s which can be taken on a game with `status`, sending the player's
+/// choices into `tx`. While `confirming` an action the buttons are replaced by a question asking
+/// the player to confirm it.
+#[allow(unused_braces)]
+pub fn game_actions(
+    status: api::GameStatus,
+    confirming: Option<GameAction>,
+    tx: &Transmitter<GameActionEvent>,
+) -> ViewBuilder<HtmlElement> {
+    let mut actions = builder! { <span class="game-actions" /> };
+    match confirming {
+        Some(action) => {
+            let tx_confirm = tx.contra_map(|_: &Event| GameActionEvent::Confirm);
+            let tx_dismiss = tx.contra_map(|_: &Event| GameActionEvent::Dismiss);
+            actions.with(builder! { <span>{action.question()}</span> });
+            actions.with(builder! { <button on:click=tx_confirm>"Yes"</button> });
+            actions.with(builder! { <button on:click=tx_dismiss>"No"</button> });
+        }
+        None => {
+            // Only open games can be given up on
+            let available: &[GameAction] = match status {
+                api::GameStatus::Open => &[GameAction::Abandon, GameAction::Delete],
+                _ => &[GameAction::Delete],
+            };
+            for action in available.iter().copied() {
+                let tx_ask = tx.contra_map(move |_: &Event| GameActionEvent::Ask(action));
+                actions.with(builder! { <button on:click=tx_ask>{action.label()}</button> });
+            }
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offers_actions_for_status() {
+        let tx = Transmitter::new();
+        let open = View::from(game_actions(api::GameStatus::Open, None, &tx));
+        assert_eq!(
+            open.html_string(),
+            "<span class=\"game-actions\"><button>Abandon</button> <button>Delete</button></span>"
+        );
+        let won = View::from(game_actions(api::GameStatus::Won, None, &tx));
+        assert_eq!(
+            won.html_string(),
+            "<span class=\"game-actions\"><button>Delete</button></span>"
+        );
+    }
+
+    #[test]
+    fn asks_for_confirmation() {
+        let tx = Transmitter::new();
+        let view = View::from(game_actions(
+            api::GameStatus::Open,
+            Some(GameAction::Delete),
+            &tx,
+        ));
+        assert_eq!(
+            view.html_string(),
+            "<span class=\"game-actions\"><span>Delete this game?</span> \
+             <button>Yes</button> <button>No</button></span>"
+        );
+    }
+}
@@ -1,4 +1,8 @@
 mod cell;
 pub mod game;
+mod game_actions;
+mod move_log;
 
 pub use game::board;
+pub use game_actions::game_actions;
+pub use move_log::{move_log, MoveLog};
@@ -0,0 +1,137 @@
+use crate::api;
+use mogwai::prelude::*;
+
+/// The moves made in a game along with the board they led to, which tells what each move
+/// revealed.
+#[derive(Clone, Debug, Default)]
+pub struct MoveLog {
+    pub moves: Vec<api::GameMove>,
+    pub board: Vec<Vec<api::BoardValue>>,
+}
+
+/// A panel listing the moves of each `MoveLog` received by `rx` in the order they were made.
+#[allow(unused_braces)]
+pub fn move_log(rx: &Receiver<MoveLog>) -> ViewBuilder<HtmlElement> {
+    let rx_patch = rx.branch_map(|log| Patch::Replace {
+        index: 0,
+        value: move_list(log),
+    });
+    builder! {
+        <aside class="move-log">
+            <h2>"Moves"</h2>
+            <slot patch:children=rx_patch>
+                <ol></ol>
+            </slot>
+        </aside>
+    }
+}
+
+fn move_list(log: &MoveLog) -> ViewBuilder<HtmlElement> {
+    let mut list = builder! { <ol /> };
+    for game_move in log.moves.iter() {
+        list.with(move_item(game_move, &log.board));
+    }
+    list
+}
+
+#[allow(unused_braces)]
+fn move_item(
+    game_move: &api::GameMove,
+    board: &[Vec<api::BoardValue>],
+) -> ViewBuilder<HtmlElement> {
+    let created_at = game_move.created_at.clone();
+    let time = String::from(time_of_day(&game_move.created_at));
+    builder! {
+        <li>
+            <time datetime=created_at>{time}</time>
+            {describe(game_move, board)}
+        </li>
+    }
+}
+
+/// The `HH:MM:SS` part of an ISO 8601 `timestamp`, or all of it if it isn't in that format.
+fn time_of_day(timestamp: &str) -> &str {
+    timestamp.get(11..19).unwrap_or(timestamp)
+}
+
+/// Describe `game_move`, including what the opened cell shows on `board`.
+fn describe(game_move: &api::GameMove, board: &[Vec<api::BoardValue>]) -> String {
+    let cell = format!("row {}, column {}", game_move.row + 1, game_move.column + 1);
+    match game_move.move_type {
+        api::GameMoveType::Flag => format!("Flagged {}", cell),
+        api::GameMoveType::RemoveFlag => format!("Removed flag from {}", cell),
+        api::GameMoveType::Open => {
+            let value = board
+                .get(game_move.row)
+                .and_then(|row| row.get(game_move.column));
+            match value {
+                Some(api::BoardValue::Mine) => format!("Opened {}, a mine", cell),
+                Some(api::BoardValue::Open(0)) => format!("Opened {}, no mines nearby", cell),
+                Some(api::BoardValue::Open(1)) => format!("Opened {}, 1 mine nearby", cell),
+                Some(api::BoardValue::Open(count)) => {
+                    format!("Opened {}, {} mines nearby", cell, count)
+                }
+                _ => format!("Opened {}", cell),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_move(row: usize, column: usize, move_type: api::GameMoveType) -> api::GameMove {
+        api::GameMove {
+            column,
+            row,
+            move_type,
+            created_at: String::from("2021-02-14T18:30:05.123Z"),
+        }
+    }
+
+    #[test]
+    fn describes_result_of_move() {
+        let board = vec![vec![api::BoardValue::Open(2), api::BoardValue::Mine]];
+        assert_eq!(
+            describe(&game_move(0, 0, api::GameMoveType::Open), &board),
+            "Opened row 1, column 1, 2 mines nearby"
+        );
+        assert_eq!(
+            describe(&game_move(0, 1, api::GameMoveType::Open), &board),
+            "Opened row 1, column 2, a mine"
+        );
+        assert_eq!(
+            describe(&game_move(0, 1, api::GameMoveType::Flag), &board),
+            "Flagged row 1, column 2"
+        );
+        assert_eq!(
+            describe(&game_move(3, 3, api::GameMoveType::Open), &board),
+            "Opened row 4, column 4"
+        );
+    }
+
+    #[test]
+    fn lists_moves_in_order() {
+        let (tx, rx) = txrx();
+        let ssr = View::from(move_log(&rx));
+        assert_eq!(
+            ssr.html_string(),
+            "<aside class=\"move-log\"><h2>Moves</h2> <slot><ol></ol></slot></aside>"
+        );
+        tx.send(&MoveLog {
+            moves: vec![
+                game_move(0, 1, api::GameMoveType::Flag),
+                game_move(0, 0, api::GameMoveType::Open),
+            ],
+            board: vec![vec![api::BoardValue::Open(1), api::BoardValue::Flag]],
+        });
+        assert_eq!(
+            ssr.html_string(),
+            "<aside class=\"move-log\"><h2>Moves</h2> <slot><ol>\
+             <li><time datetime=\"2021-02-14T18:30:05.123Z\">18:30:05</time> Flagged row 1, column 2</li> \
+             <li><time datetime=\"2021-02-14T18:30:05.123Z\">18:30:05</time> Opened row 1, column 1, 1 mine nearby</li>\
+             </ol></slot></aside>"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::api::{Config, ConfigError};
+use dotenv_codegen::dotenv;
+use mines_api::browser::config;
+
+/// The API base URL from the build environment. Only used when no runtime configuration is found.
+const FALLBACK_API_BASE_URL: &str = dotenv!("UI_BASE_API_URL");
+/// Milliseconds a cell is touched before it is flagged, unless the `Config` sets it.
+const DEFAULT_LONG_PRESS_MS: u32 = 500;
+
+pub use config::set;
+
+/// Resolve the `Config` for this page, falling back to `UI_BASE_API_URL` from the build
+/// environment when the page doesn't provide one.
+pub async fn load() -> Result<Config, ConfigError> {
+    config::load(FALLBACK_API_BASE_URL).await
+}
+
+/// The base URL of the game API from the stored `Config`, or the build environment value if no
+/// `Config` has been stored.
+pub fn api_base_url() -> String {
+    config::api_base_url(FALLBACK_API_BASE_URL)
+}
+
+/// How long, in milliseconds, a cell has to be touched to flag it according to the stored
+/// `Config`, or the default if it doesn't say.
+pub fn long_press_ms() -> u32 {
+    config::get()
+        .as_ref()
+        .and_then(Config::long_press_ms)
+        .unwrap_or(DEFAULT_LONG_PRESS_MS)
+}
@@ -1,8 +1,14 @@
 mod api;
 mod app;
+mod auth;
 mod components;
+mod config;
+mod live;
 mod model;
+mod offline;
 mod routes;
+mod spawn;
+mod tabs;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -18,12 +24,16 @@ pub enum Route {
     GameList,
     /// Default landing screen
     Home,
+    /// Screen to log in
+    Login,
+    /// Screen to start a new game, placing its mines with `seed` when there is one
+    NewGame { seed: Option<api::GameSeed> },
     /// Screen to display when the requested path does not exist
     NotFound,
 }
 
 #[wasm_bindgen::prelude::wasm_bindgen(start)]
-pub fn run_app() -> Result<(), wasm_bindgen::JsValue> {
+pub async fn run_app() -> Result<(), wasm_bindgen::JsValue> {
     use crate::app::App;
     use mogwai::prelude::*;
 
@@ -36,11 +46,27 @@ pub fn run_app() -> Result<(), wasm_bindgen::JsValue> {
         ::log::trace!("Hello from release @mines/uirs");
     }
 
-    let pathname = utils::window().location().pathname();
+    // Resolve where the API lives before anything is rendered which might talk to it
+    match config::load().await {
+        Ok(config) => {
+            ::log::info!("Using API at {}", config.api_base_url());
+            config::set(config);
+        }
+        Err(error) => {
+            ::log::error!("{}", error);
+            return Err(format!("Unable to start @mines/uirs. {}", error).into());
+        }
+    }
+
+    // Routes such as `/new?seed=...` read the query string along with the path
+    let location = utils::window().location();
+    let pathname = location
+        .pathname()
+        .map(|path| path + &location.search().unwrap_or_default());
     if let Ok(path) = pathname {
         let initial_route: Route = path.into();
         // Create our app's view by hydrating a gizmo from an initial state
-        let root: Gizmo<App> = App::gizmo(initial_route);
+        let root: Gizmo<App> = App::gizmo(initial_route, api::http());
 
         // Hand the app's view ownership to the window so it never
         // goes out of scope
@@ -52,7 +78,7 @@ pub fn run_app() -> Result<(), wasm_bindgen::JsValue> {
 }
 
 mod route_dispatch {
-    use super::{routes, Route};
+    use super::{api, routes, Route};
     use mogwai::prelude::*;
 
     /// Dispatch the given `Route`.
@@ -72,12 +98,22 @@ mod route_dispatch {
     }
 
     /// Create a `ViewBuilder` for the given `Route`. The `ViewBuilder` will be
-    /// given access to the `Transmitter`.
-    pub fn view_builder(tx: Transmitter<Route>, route: Route) -> ViewBuilder<HtmlElement> {
+    /// given access to the `Transmitter`. Requests made by the view go through
+    /// `client` and are cancelled once `scope` is dropped.
+    pub fn view_builder(
+        tx: Transmitter<Route>,
+        route: Route,
+        client: &api::Client,
+        scope: &api::RequestScope,
+    ) -> ViewBuilder<HtmlElement> {
+        let client = client.clone();
+        let cancel = scope.signal();
         match route {
-            Route::Game { game_id } => routes::game(game_id),
-            Route::GameList => routes::game_list(tx),
-            Route::Home => routes::home(tx),
+            Route::Game { game_id } => routes::game(tx, game_id, client, cancel),
+            Route::GameList => routes::game_list(tx, client, cancel),
+            Route::Home => routes::home(tx, None, client, cancel),
+            Route::Login => routes::login(tx, client, cancel),
+            Route::NewGame { seed } => routes::home(tx, seed, client, cancel),
             Route::NotFound => routes::not_found(),
         }
     }
@@ -89,6 +125,9 @@ impl std::fmt::Display for Route {
             Route::Game { game_id } => f.write_fmt(format_args!("/game/{}", game_id)),
             Route::GameList => f.write_str("/game"),
             Route::Home => f.write_str("/"),
+            Route::Login => f.write_str("/login"),
+            Route::NewGame { seed: None } => f.write_str("/new"),
+            Route::NewGame { seed: Some(seed) } => f.write_fmt(format_args!("/new?seed={}", seed)),
             Route::NotFound => f.write_str("/404"),
         }
     }
@@ -98,6 +137,11 @@ impl<T: AsRef<str>> From<T> for Route {
     fn from(path: T) -> Self {
         let s = path.as_ref();
         ::log::trace!("route from: {}", s);
+        // keep the query string apart from the path, only `/new` reads it
+        let (s, query) = match s.find('?') {
+            Some(index) => (&s[..index], &s[index + 1..]),
+            None => (s, ""),
+        };
         // remove the scheme, if it has one
         let paths: Vec<&str> = s.split("/").collect::<Vec<_>>();
         ::log::info!("route parts = {:?}", paths);
@@ -106,6 +150,13 @@ impl<T: AsRef<str>> From<T> for Route {
             [""] => Route::Home,
             ["", ""] => Route::Home,
             ["", "game"] => Route::GameList,
+            ["", "login"] => Route::Login,
+            ["", "new"] => Route::NewGame {
+                seed: query
+                    .split('&')
+                    .find_map(|param| param.strip_prefix("seed="))
+                    .and_then(|seed| seed.parse().ok()),
+            },
             ["", "game", game_id] => match uuid::Uuid::parse_str(game_id) {
                 Ok(game_id) => Route::Game { game_id },
                 Err(_) => Route::NotFound,
@@ -117,8 +168,27 @@ impl<T: AsRef<str>> From<T> for Route {
 
 #[cfg(test)]
 mod tests {
+    use super::Route;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn reads_seed_from_new_game_path() {
+        assert_eq!(Route::from("/new"), Route::NewGame { seed: None });
+        assert_eq!(
+            Route::from("/new?seed=1234"),
+            Route::NewGame { seed: Some(1234) }
+        );
+        assert_eq!(
+            Route::from("/new?rows=5&seed=7"),
+            Route::NewGame { seed: Some(7) }
+        );
+        assert_eq!(Route::from("/new?seed=abc"), Route::NewGame { seed: None });
+        let route = Route::NewGame { seed: Some(42) };
+        assert_eq!(Route::from(route.to_string()), route);
+        assert_eq!(Route::from("/login?seed=1"), Route::Login);
+    }
 }
@@ -0,0 +1,371 @@
+use crate::{api, model, spawn};
+use mogwai::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// Milliseconds between requests for a game which can't be watched over a socket.
+const POLL_INTERVAL_MS: f64 = 5_000.0;
+
+/// Keep `tx` up to date with the game identified by `game_id`, including moves made on other
+/// devices, until `cancel` is cancelled. Changes are pushed over a socket opened by `client`
+/// which is reopened with backoff whenever it is lost. When sockets aren't available, or
+/// reopening keeps failing, the game is polled instead until it is won or lost.
+///
+/// Each game is sent with a sequence number from `moves`, taken when the request for it is sent or
+/// when it is pushed, so it can be dropped if a newer response has already been applied.
+pub fn watch(
+    game_id: api::GameId,
+    client: api::Client,
+    moves: Rc<RefCell<model::MoveQueue>>,
+    tx: Transmitter<model::SequencedResponse>,
+    cancel: api::CancelSignal,
+) {
+    let state = Rc::new(RefCell::new(WatchState::default()));
+    let cancelled_state = Rc::downgrade(&state);
+    let hook = cancel.on_cancel(move || {
+        let socket = cancelled_state
+            .upgrade()
+            .and_then(|state| state.borrow_mut().socket.take());
+        if let Some(socket) = socket {
+            socket.close();
+        }
+    });
+    state.borrow_mut().hook = Some(hook);
+    let watcher = Watcher {
+        cancel,
+        client,
+        game_id,
+        moves,
+        policy: api::RetryPolicy::default(),
+        state,
+        tx,
+    };
+    watcher.connect();
+}
+
+#[derive(Clone)]
+struct Watcher {
+    cancel: api::CancelSignal,
+    client: api::Client,
+    game_id: api::GameId,
+    /// Gives out the sequence numbers games are sent with
+    moves: Rc<RefCell<model::MoveQueue>>,
+    /// How far apart attempts to reopen the socket are and how many are made before polling
+    policy: api::RetryPolicy,
+    state: Rc<RefCell<WatchState>>,
+    tx: Transmitter<model::SequencedResponse>,
+}
+
+#[derive(Default)]
+struct WatchState {
+    /// Socket most recently opened, it may since have closed
+    socket: Option<Box<dyn api::LiveSocket>>,
+    /// Closes `socket` once the watch is cancelled
+    hook: Option<api::CancelHook>,
+    /// Version of the most recent frame sent on
+    version: Option<u64>,
+    /// Number of sockets lost, or never opened, since one last opened
+    failures: u32,
+    /// Whether the game has been won or lost, after which it can't change
+    finished: bool,
+}
+
+impl Watcher {
+    /// Open a socket for the game, polling instead if that isn't possible.
+    fn connect(&self) {
+        if self.cancel.is_cancelled() {
+            return;
+        }
+        let (tx_event, rx_event) = txrx();
+        let watcher = self.clone();
+        rx_event.respond(move |event| watcher.handle(event));
+        match self.client.watch_game(self.game_id, tx_event) {
+            // Replacing the previous socket is only done here, never from within its own events
+            Some(socket) => self.state.borrow_mut().socket = Some(socket),
+            None => {
+                ::log::debug!("sockets unavailable, polling {}", self.game_id);
+                self.poll();
+            }
+        }
+    }
+
+    fn handle(&self, event: &api::SocketEvent) {
+        match event {
+            api::SocketEvent::Opened => self.state.borrow_mut().failures = 0,
+            api::SocketEvent::Frame(text) => match serde_json::from_str::<api::GameFrame>(text) {
+                Ok(frame) => self.receive(frame),
+                Err(error) => ::log::warn!("unreadable frame for {}: {}", self.game_id, error),
+            },
+            api::SocketEvent::Closed => self.reconnect(),
+        }
+    }
+
+    /// Send `frame` on unless a newer frame has already been sent.
+    fn receive(&self, frame: api::GameFrame) {
+        {
+            let mut state = self.state.borrow_mut();
+            match state.version {
+                Some(version) if version >= frame.version => {
+                    ::log::debug!("dropping frame {} behind {}", frame.version, version);
+                    return;
+                }
+                _ => state.version = Some(frame.version),
+            }
+        }
+        self.send(frame.game, self.next_sequence());
+    }
+
+    fn next_sequence(&self) -> u64 {
+        self.moves.borrow_mut().next_sequence()
+    }
+
+    /// Send `game`, the answer to the request identified by `sequence`, on and remember whether it
+    /// is finished.
+    fn send(&self, game: api::GameState, sequence: u64) {
+        if game.status != api::GameStatus::Open {
+            self.state.borrow_mut().finished = true;
+        }
+        self.tx.send(&(sequence, Ok(game)));
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state.borrow().finished
+    }
+
+    /// Open a new socket after waiting for longer the more attempts have failed, giving up and
+    /// polling once the `RetryPolicy` runs out of attempts.
+    fn reconnect(&self) {
+        if self.cancel.is_cancelled() || self.is_finished() {
+            return;
+        }
+        let failures = {
+            let mut state = self.state.borrow_mut();
+            state.failures += 1;
+            state.failures
+        };
+        if failures >= self.policy.max_attempts {
+            ::log::debug!("giving up on socket after {} attempts", failures);
+            self.poll();
+            return;
+        }
+        let delay = self.policy.delay_ms(failures, jitter());
+        let watcher = self.clone();
+        spawn::spawn_local(async move {
+            spawn::wait(delay).await;
+            watcher.connect();
+        });
+    }
+
+    /// Request the game every `POLL_INTERVAL_MS` until the watch is cancelled or the game is won
+    /// or lost.
+    fn poll(&self) {
+        let watcher = self.clone();
+        spawn::spawn_local(async move {
+            loop {
+                spawn::wait(POLL_INTERVAL_MS).await;
+                if watcher.cancel.is_cancelled() || watcher.is_finished() {
+                    return;
+                }
+                let sequence = watcher.next_sequence();
+                let get_game = watcher
+                    .client
+                    .get_game(watcher.game_id, watcher.cancel.clone());
+                match get_game.await {
+                    Ok(game) => watcher.send(game, sequence),
+                    Err(api::FetchError::Cancelled) => return,
+                    Err(error) => ::log::debug!("unable to poll {}: {}", watcher.game_id, error),
+                }
+            }
+        });
+    }
+}
+
+/// A value in `[0, 1)` to spread out reconnecting clients.
+fn jitter() -> f64 {
+    // There is no `Math` outside of the browser, e.g. when running tests
+    if cfg!(target_arch = "wasm32") {
+        js_sys::Math::random()
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(game_id: api::GameId, status: api::GameStatus) -> api::GameState {
+        api::GameState {
+            id: game_id,
+            board: vec![],
+            status,
+            mines: None,
+            seed: None,
+        }
+    }
+
+    fn frame(game_id: api::GameId, version: u64, status: api::GameStatus) -> api::SocketEvent {
+        let frame = api::GameFrame {
+            version,
+            game: game(game_id, status),
+        };
+        api::SocketEvent::Frame(serde_json::to_string(&frame).unwrap())
+    }
+
+    /// Every game sent on by a watch, as (sequence, status).
+    type Sent = Rc<RefCell<Vec<(u64, api::GameStatus)>>>;
+
+    /// Watch the game identified by `game_id` through `client`, returning the queue sequence
+    /// numbers are taken from and every game sent on.
+    fn watch_game(
+        game_id: api::GameId,
+        client: &Rc<api::MemoryApi>,
+    ) -> (Rc<RefCell<model::MoveQueue>>, Sent) {
+        let moves = Rc::new(RefCell::new(model::MoveQueue::default()));
+        let sent = Rc::new(RefCell::new(vec![]));
+        let (tx, rx) = txrx();
+        let remote_sent = sent.clone();
+        rx.respond(move |(sequence, result): &model::SequencedResponse| {
+            if let Ok(game) = result {
+                remote_sent.borrow_mut().push((*sequence, game.status));
+            }
+        });
+        watch(
+            game_id,
+            client.clone(),
+            moves.clone(),
+            tx,
+            api::CancelSignal::none(),
+        );
+        (moves, sent)
+    }
+
+    /// Start watching a new game, returning the id of the game, the client it is watched through
+    /// and every game sent on.
+    fn start() -> (api::GameId, Rc<api::MemoryApi>, Sent) {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        let (_, sent) = watch_game(game_id, &client);
+        (game_id, client, sent)
+    }
+
+    fn statuses(sent: &Sent) -> Vec<api::GameStatus> {
+        sent.borrow().iter().map(|(_, status)| *status).collect()
+    }
+
+    #[test]
+    fn sends_pushed_games() {
+        let (game_id, client, sent) = start();
+        let socket = client.socket(0);
+        socket.send(&api::SocketEvent::Opened);
+        socket.send(&frame(game_id, 1, api::GameStatus::Open));
+        socket.send(&frame(game_id, 2, api::GameStatus::Won));
+        assert_eq!(
+            statuses(&sent),
+            vec![api::GameStatus::Open, api::GameStatus::Won]
+        );
+    }
+
+    #[test]
+    fn tags_games_with_sequence() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        let (moves, sent) = watch_game(game_id, &client);
+        client
+            .socket(0)
+            .send(&frame(game_id, 1, api::GameStatus::Open));
+        // A move sent before the next frame arrives
+        moves.borrow_mut().next_sequence();
+        client
+            .socket(0)
+            .send(&frame(game_id, 2, api::GameStatus::Open));
+        assert_eq!(
+            *sent.borrow(),
+            vec![(1, api::GameStatus::Open), (3, api::GameStatus::Open)]
+        );
+    }
+
+    #[test]
+    fn drops_out_of_order_frames() {
+        let (game_id, client, sent) = start();
+        let socket = client.socket(0);
+        socket.send(&frame(game_id, 2, api::GameStatus::Lost));
+        socket.send(&frame(game_id, 1, api::GameStatus::Open));
+        socket.send(&frame(game_id, 2, api::GameStatus::Open));
+        socket.send(&api::SocketEvent::Frame(String::from("{")));
+        assert_eq!(statuses(&sent), vec![api::GameStatus::Lost]);
+    }
+
+    #[test]
+    fn reconnects_after_waiting() {
+        let (game_id, client, sent) = start();
+        client.socket(0).send(&api::SocketEvent::Closed);
+        spawn::run_pending();
+        assert_eq!(client.socket_count(), 1);
+        spawn::run_timers();
+        assert_eq!(client.socket_count(), 2);
+        // Versions carry over to the new socket
+        client
+            .socket(0)
+            .send(&frame(game_id, 5, api::GameStatus::Open));
+        client
+            .socket(1)
+            .send(&frame(game_id, 4, api::GameStatus::Won));
+        assert_eq!(statuses(&sent), vec![api::GameStatus::Open]);
+    }
+
+    #[test]
+    fn stays_closed_once_finished() {
+        let (game_id, client, _) = start();
+        client
+            .socket(0)
+            .send(&frame(game_id, 1, api::GameStatus::Lost));
+        client.socket(0).send(&api::SocketEvent::Closed);
+        spawn::run_timers();
+        assert_eq!(client.socket_count(), 1);
+        assert!(client.calls().is_empty());
+    }
+
+    #[test]
+    fn polls_once_reconnecting_fails() {
+        let (game_id, client, sent) = start();
+        for attempt in 0..3 {
+            client.socket(attempt).send(&api::SocketEvent::Closed);
+            spawn::run_timers();
+        }
+        assert_eq!(client.socket_count(), 4);
+        // The fourth failure uses up the `RetryPolicy`
+        client.socket(3).send(&api::SocketEvent::Closed);
+        client.respond_game(Ok(game(game_id, api::GameStatus::Won)));
+        spawn::run_timers();
+        assert_eq!(client.calls(), vec![api::ApiCall::GetGame(game_id)]);
+        assert_eq!(statuses(&sent), vec![api::GameStatus::Won]);
+    }
+
+    #[test]
+    fn polls_without_sockets() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.disable_sockets();
+        client.respond_game(Ok(game(game_id, api::GameStatus::Open)));
+        let (_, sent) = watch_game(game_id, &client);
+        spawn::run_pending();
+        assert!(client.calls().is_empty());
+        spawn::run_timers();
+        assert_eq!(client.calls(), vec![api::ApiCall::GetGame(game_id)]);
+        assert_eq!(*sent.borrow(), vec![(1, api::GameStatus::Open)]);
+    }
+
+    #[test]
+    fn stops_polling_finished_game() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.disable_sockets();
+        client.respond_game(Ok(game(game_id, api::GameStatus::Lost)));
+        let (_, sent) = watch_game(game_id, &client);
+        spawn::run_timers();
+        client.respond_game(Ok(game(game_id, api::GameStatus::Lost)));
+        spawn::run_timers();
+        assert_eq!(client.calls(), vec![api::ApiCall::GetGame(game_id)]);
+        assert_eq!(statuses(&sent), vec![api::GameStatus::Lost]);
+    }
+}
@@ -1,7 +1,23 @@
-mod board_value;
+mod announcement;
+mod board;
+mod cell_input;
 mod cell_interact;
+mod cell_mark;
 mod cell_update;
+mod cursor;
+mod game_action;
+mod move_queue;
 
-pub use board_value::BoardValue;
+pub use announcement::{announce, cell_label};
+pub use board::Board;
+pub use cell_input::{touch_position, CellInput, LongPress};
 pub use cell_interact::{CellInteract, CellInteractKind};
+pub use cell_mark::{CellMark, CellMarks};
 pub use cell_update::CellUpdate;
+pub use cursor::{Cursor, CursorMove, KeyCommand};
+pub use game_action::{GameAction, GameActionEvent};
+pub use mines_api::BoardValue;
+pub use move_queue::{MoveQueue, QueuedMove, RejectedMove, SequencedResponse};
+// Reasons are only ever built by the queue outside of tests
+#[cfg(test)]
+pub use move_queue::RejectReason;
@@ -0,0 +1,164 @@
+use crate::api::{BoardValue, GameState, GameStatus};
+
+/// Describe the cell at `row` and `column` showing `value` for screen readers, e.g. "row 3 column
+/// 4, 2 adjacent mines".
+pub fn cell_label(row: usize, column: usize, value: BoardValue) -> String {
+    format!(
+        "row {} column {}, {}",
+        row + 1,
+        column + 1,
+        value_label(value)
+    )
+}
+
+fn value_label(value: BoardValue) -> String {
+    match value {
+        BoardValue::Closed => String::from("unopened"),
+        BoardValue::Flag => String::from("flagged"),
+        BoardValue::Mine => String::from("mine"),
+        BoardValue::Open(0) => String::from("no adjacent mines"),
+        BoardValue::Open(1) => String::from("1 adjacent mine"),
+        BoardValue::Open(count) => format!("{} adjacent mines", count),
+        BoardValue::Pending => String::from("opening"),
+    }
+}
+
+/// Describe what changed between `previous` and `next`, states of the same game, for screen
+/// readers. A single changed cell is described in full, otherwise the changes are counted. `None`
+/// if nothing a player would notice changed.
+pub fn announce(previous: &GameState, next: &GameState) -> Option<String> {
+    let mut opened = vec![];
+    let mut flagged = vec![];
+    let mut unflagged = vec![];
+    for (row, cells) in next.board.iter().enumerate() {
+        for (column, value) in cells.iter().enumerate() {
+            let before = previous
+                .board
+                .get(row)
+                .and_then(|cells| cells.get(column))
+                .copied()
+                .unwrap_or(BoardValue::Closed);
+            let change = (row, column, *value);
+            match (before, value) {
+                (before, after) if before == *after => (),
+                (_, BoardValue::Open(_)) | (_, BoardValue::Mine) => opened.push(change),
+                (_, BoardValue::Flag) => flagged.push(change),
+                (BoardValue::Flag, BoardValue::Closed) => unflagged.push(change),
+                _ => (),
+            }
+        }
+    }
+    let mut sentences = vec![];
+    match opened.as_slice() {
+        [] => (),
+        [(row, column, value)] => {
+            sentences.push(format!("Opened {}.", cell_label(*row, *column, *value)))
+        }
+        cells => sentences.push(format!("Opened {} cells.", cells.len())),
+    }
+    match flagged.as_slice() {
+        [] => (),
+        [(row, column, _)] => {
+            sentences.push(format!("Flagged row {} column {}.", row + 1, column + 1))
+        }
+        cells => sentences.push(format!("Flagged {} cells.", cells.len())),
+    }
+    match unflagged.as_slice() {
+        [] => (),
+        [(row, column, _)] => sentences.push(format!(
+            "Removed flag from row {} column {}.",
+            row + 1,
+            column + 1
+        )),
+        cells => sentences.push(format!("Removed {} flags.", cells.len())),
+    }
+    if previous.status != next.status {
+        match next.status {
+            GameStatus::Won => sentences.push(String::from("You won, every safe cell is open.")),
+            GameStatus::Lost => sentences.push(String::from("You hit a mine, game over.")),
+            GameStatus::Open => (),
+        }
+    }
+    if sentences.is_empty() {
+        None
+    } else {
+        Some(sentences.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(rows: &[&str], status: GameStatus) -> GameState {
+        GameState {
+            id: uuid::Uuid::nil(),
+            board: rows
+                .iter()
+                .map(|row| {
+                    row.chars()
+                        .map(|cell| cell.to_string().parse().unwrap())
+                        .collect()
+                })
+                .collect(),
+            status,
+            mines: None,
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn labels_cells() {
+        assert_eq!(
+            cell_label(2, 3, BoardValue::Open(2)),
+            "row 3 column 4, 2 adjacent mines"
+        );
+        assert_eq!(
+            cell_label(0, 0, BoardValue::Flag),
+            "row 1 column 1, flagged"
+        );
+        assert_eq!(
+            cell_label(0, 1, BoardValue::Closed),
+            "row 1 column 2, unopened"
+        );
+        assert_eq!(
+            cell_label(1, 0, BoardValue::Open(1)),
+            "row 2 column 1, 1 adjacent mine"
+        );
+    }
+
+    #[test]
+    fn announces_single_changes() {
+        let closed = game(&["  ", "  "], GameStatus::Open);
+        let opened = game(&["1 ", "  "], GameStatus::Open);
+        assert_eq!(
+            announce(&closed, &opened).as_deref(),
+            Some("Opened row 1 column 1, 1 adjacent mine.")
+        );
+        let flagged = game(&["1F", "  "], GameStatus::Open);
+        assert_eq!(
+            announce(&opened, &flagged).as_deref(),
+            Some("Flagged row 1 column 2.")
+        );
+        assert_eq!(
+            announce(&flagged, &opened).as_deref(),
+            Some("Removed flag from row 1 column 2.")
+        );
+        assert_eq!(announce(&opened, &opened), None);
+    }
+
+    #[test]
+    fn counts_cells_and_announces_result() {
+        let closed = game(&["  ", "  "], GameStatus::Open);
+        let won = game(&["00", "0 "], GameStatus::Won);
+        assert_eq!(
+            announce(&closed, &won).as_deref(),
+            Some("Opened 3 cells. You won, every safe cell is open.")
+        );
+        let lost = game(&["  ", " M"], GameStatus::Lost);
+        assert_eq!(
+            announce(&closed, &lost).as_deref(),
+            Some("Opened row 2 column 2, mine. You hit a mine, game over.")
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::model::{BoardValue, CellUpdate};
+
+/// The cells of a game board as they are currently shown, indexed by row and then column.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Board {
+    cells: Vec<Vec<BoardValue>>,
+}
+
+impl Board {
+    pub fn new(cells: Vec<Vec<BoardValue>>) -> Self {
+        Self { cells }
+    }
+
+    /// The value of the cell at `row` and `column`, `None` if it is off the board.
+    pub fn get(&self, row: usize, column: usize) -> Option<BoardValue> {
+        self.cells
+            .get(row)
+            .and_then(|cells| cells.get(column))
+            .copied()
+    }
+
+    /// Show the change described by `update`.
+    pub fn apply(&mut self, update: &CellUpdate) {
+        match update {
+            CellUpdate::All { cells } => self.cells = cells.clone(),
+            CellUpdate::Single { row, column, value } => {
+                if let Some(cell) = self
+                    .cells
+                    .get_mut(*row)
+                    .and_then(|cells| cells.get_mut(*column))
+                {
+                    *cell = *value;
+                }
+            }
+        }
+    }
+
+    /// The positions, as (row, column), of the cells touching the cell at `row` and `column`.
+    pub fn neighbours(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
+        let mut neighbours = Vec::with_capacity(8);
+        for y in row.saturating_sub(1)..=row + 1 {
+            for x in column.saturating_sub(1)..=column + 1 {
+                if (y, x) != (row, column) && self.get(y, x).is_some() {
+                    neighbours.push((y, x));
+                }
+            }
+        }
+        neighbours
+    }
+
+    /// The cells opened by chording the cell at `row` and `column`. A revealed number with as many
+    /// flags around it as it counts mines opens every closed neighbour, otherwise nothing opens.
+    pub fn chord(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
+        let mines = match self.get(row, column) {
+            Some(BoardValue::Open(mines)) if mines > 0 => mines,
+            _ => return vec![],
+        };
+        let neighbours = self.neighbours(row, column);
+        let flags = neighbours
+            .iter()
+            .filter(|(y, x)| self.get(*y, *x) == Some(BoardValue::Flag))
+            .count();
+        if flags != mines {
+            return vec![];
+        }
+        neighbours
+            .into_iter()
+            .filter(|(y, x)| self.get(*y, *x) == Some(BoardValue::Closed))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a `Board` from rows of cells written the way the API sends them.
+    fn board(rows: &[&str]) -> Board {
+        Board::new(
+            rows.iter()
+                .map(|row| {
+                    row.chars()
+                        .map(|cell| cell.to_string().parse().unwrap())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn finds_neighbours_within_board() {
+        let board = board(&["   ", "   ", "   "]);
+        assert_eq!(board.neighbours(0, 0), vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(board.neighbours(1, 1).len(), 8);
+        assert_eq!(board.neighbours(2, 2), vec![(1, 1), (1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn chords_satisfied_number() {
+        let board = board(&["F2 ", "F F", "1  "]);
+        assert_eq!(board.chord(1, 1), vec![]);
+        let board = board_with(&board, 1, 1, BoardValue::Open(3));
+        assert_eq!(board.chord(1, 1), vec![(0, 2), (2, 1), (2, 2)]);
+        assert_eq!(board.chord(0, 1), vec![]);
+    }
+
+    #[test]
+    fn skips_unsatisfied_and_closed_cells() {
+        let board = board(&["F2 ", "  *", "   "]);
+        assert_eq!(board.chord(0, 1), vec![]);
+        assert_eq!(board.chord(2, 2), vec![]);
+        let board = board_with(&board, 0, 1, BoardValue::Open(1));
+        assert_eq!(board.chord(0, 1), vec![(0, 2), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn applies_updates() {
+        let mut board = board(&["  "]);
+        board.apply(&CellUpdate::Single {
+            row: 0,
+            column: 1,
+            value: BoardValue::Flag,
+        });
+        assert_eq!(board.get(0, 1), Some(BoardValue::Flag));
+        board.apply(&CellUpdate::Single {
+            row: 4,
+            column: 4,
+            value: BoardValue::Flag,
+        });
+        assert_eq!(board.get(4, 4), None);
+        board.apply(&CellUpdate::All { cells: vec![] });
+        assert_eq!(board, Board::default());
+    }
+
+    fn board_with(board: &Board, row: usize, column: usize, value: BoardValue) -> Board {
+        let mut board = board.clone();
+        board.apply(&CellUpdate::Single { row, column, value });
+        board
+    }
+}
@@ -0,0 +1,220 @@
+use mines_api::browser::input::is_flag_click;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, MouseEvent, TouchEvent};
+
+/// Distance, in CSS pixels, a touch can drift before it is treated as scrolling the board.
+const SCROLL_TOLERANCE_PX: f64 = 10.0;
+
+/// What the player asked of a cell, however they asked for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellInput {
+    /// Place or remove a flag
+    Flag,
+    /// Open the cell
+    Open,
+    /// Open the closed cells around a revealed number once all of its mines are flagged
+    Chord,
+    /// Make the move the API refused for the cell again
+    Retry,
+}
+
+impl CellInput {
+    /// Read a click on a cell. Holding Alt or Ctrl flags the cell, as it did before right clicks
+    /// and long presses were understood.
+    pub fn from_click(event: &Event) -> Self {
+        let event: Option<&MouseEvent> = event.dyn_ref();
+        if event.map(is_flag_click).unwrap_or(false) {
+            CellInput::Flag
+        } else {
+            CellInput::Open
+        }
+    }
+
+    /// Read a click with a button other than the main one. Middle clicks chord, anything else is
+    /// left to the browser.
+    pub fn from_aux_click(event: &Event) -> Option<Self> {
+        let event: &MouseEvent = event.dyn_ref()?;
+        if event.button() == 1 {
+            event.prevent_default();
+            Some(CellInput::Chord)
+        } else {
+            None
+        }
+    }
+}
+
+/// How a touch on a cell ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchOutcome {
+    /// Released before the long press delay, the browser follows up with a click
+    Tap,
+    /// Moved too far to be a press, e.g. because the board was scrolled
+    Scroll,
+    /// Held still for the long press delay
+    LongPress,
+    /// The browser already reported the press as a context menu, which flagged the cell
+    Handled,
+}
+
+impl TouchOutcome {
+    /// The input the touch asked for, if the click following it won't ask for one.
+    pub fn input(&self) -> Option<CellInput> {
+        match self {
+            TouchOutcome::LongPress => Some(CellInput::Flag),
+            _ => None,
+        }
+    }
+
+    /// Whether the click the browser sends after the touch has to be prevented so the cell isn't
+    /// also opened.
+    pub fn prevents_click(&self) -> bool {
+        matches!(self, TouchOutcome::LongPress | TouchOutcome::Handled)
+    }
+}
+
+/// Follows a touch on a cell to tell a long press apart from a tap or a scroll. Times are in
+/// milliseconds and positions in CSS pixels, both as reported by the touch events.
+#[derive(Clone, Debug)]
+pub struct LongPress {
+    delay_ms: f64,
+    touch: Option<Touch>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Touch {
+    started_at: f64,
+    x: f64,
+    y: f64,
+    scrolled: bool,
+    handled: bool,
+}
+
+impl LongPress {
+    /// Track touches which flag a cell once held for `delay_ms`.
+    pub fn new(delay_ms: u32) -> Self {
+        Self {
+            delay_ms: f64::from(delay_ms),
+            touch: None,
+        }
+    }
+
+    /// A finger touched the cell at (`x`, `y`). A second finger means the board is being zoomed so
+    /// neither touch is a press.
+    pub fn start(&mut self, at: f64, x: f64, y: f64) {
+        match &mut self.touch {
+            Some(touch) => touch.scrolled = true,
+            None => {
+                self.touch = Some(Touch {
+                    started_at: at,
+                    x,
+                    y,
+                    scrolled: false,
+                    handled: false,
+                })
+            }
+        }
+    }
+
+    /// The finger moved to (`x`, `y`).
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        if let Some(touch) = &mut self.touch {
+            let distance = (x - touch.x).hypot(y - touch.y);
+            touch.scrolled = touch.scrolled || distance > SCROLL_TOLERANCE_PX;
+        }
+    }
+
+    /// The browser asked for a context menu, either because of a right click or because it
+    /// noticed the long press itself. Returns the input for it unless the touch was a scroll.
+    pub fn context_menu(&mut self) -> Option<CellInput> {
+        match &mut self.touch {
+            Some(touch) if touch.scrolled || touch.handled => None,
+            Some(touch) => {
+                touch.handled = true;
+                Some(CellInput::Flag)
+            }
+            None => Some(CellInput::Flag),
+        }
+    }
+
+    /// The finger was lifted at `at`.
+    pub fn end(&mut self, at: f64) -> TouchOutcome {
+        match self.touch.take() {
+            Some(touch) if touch.handled => TouchOutcome::Handled,
+            Some(touch) if touch.scrolled => TouchOutcome::Scroll,
+            Some(touch) if at - touch.started_at >= self.delay_ms => TouchOutcome::LongPress,
+            _ => TouchOutcome::Tap,
+        }
+    }
+
+    /// The browser took over the touch, e.g. to scroll the page.
+    pub fn cancel(&mut self) {
+        self.touch = None;
+    }
+}
+
+/// Where the touch which changed in `event` is, as (x, y).
+pub fn touch_position(event: &Event) -> Option<(f64, f64)> {
+    let event: &TouchEvent = event.dyn_ref()?;
+    let touch = event.changed_touches().get(0)?;
+    Some((f64::from(touch.client_x()), f64::from(touch.client_y())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taps_before_delay() {
+        let mut press = LongPress::new(500);
+        press.start(1000.0, 10.0, 10.0);
+        assert_eq!(press.end(1499.0), TouchOutcome::Tap);
+        assert_eq!(TouchOutcome::Tap.input(), None);
+        assert!(!TouchOutcome::Tap.prevents_click());
+    }
+
+    #[test]
+    fn flags_after_delay() {
+        let mut press = LongPress::new(500);
+        press.start(1000.0, 10.0, 10.0);
+        press.move_to(14.0, 13.0);
+        let outcome = press.end(1500.0);
+        assert_eq!(outcome, TouchOutcome::LongPress);
+        assert_eq!(outcome.input(), Some(CellInput::Flag));
+        assert!(outcome.prevents_click());
+    }
+
+    #[test]
+    fn ignores_scrolling() {
+        let mut press = LongPress::new(500);
+        press.start(1000.0, 10.0, 10.0);
+        press.move_to(10.0, 40.0);
+        press.move_to(10.0, 10.0);
+        assert_eq!(press.context_menu(), None);
+        assert_eq!(press.end(2000.0), TouchOutcome::Scroll);
+        let mut zoom = LongPress::new(500);
+        zoom.start(1000.0, 10.0, 10.0);
+        zoom.start(1010.0, 60.0, 60.0);
+        assert_eq!(zoom.end(2000.0), TouchOutcome::Scroll);
+    }
+
+    #[test]
+    fn flags_once_for_context_menu() {
+        let mut press = LongPress::new(500);
+        assert_eq!(press.context_menu(), Some(CellInput::Flag));
+        press.start(1000.0, 10.0, 10.0);
+        assert_eq!(press.context_menu(), Some(CellInput::Flag));
+        assert_eq!(press.context_menu(), None);
+        let outcome = press.end(1600.0);
+        assert_eq!(outcome, TouchOutcome::Handled);
+        assert_eq!(outcome.input(), None);
+        assert!(outcome.prevents_click());
+    }
+
+    #[test]
+    fn forgets_cancelled_touch() {
+        let mut press = LongPress::new(500);
+        press.start(1000.0, 10.0, 10.0);
+        press.cancel();
+        assert_eq!(press.end(2000.0), TouchOutcome::Tap);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::api;
+
 #[derive(Copy, Clone, Debug)]
 pub struct CellInteract {
     pub row: usize,
@@ -12,21 +14,20 @@ impl AsRef<CellInteract> for CellInteract {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CellInteractKind {
     Flag,
     RemoveFlag,
     Open,
 }
 
-impl From<&web_sys::Event> for CellInteractKind {
-    fn from(event: &web_sys::Event) -> Self {
-        use wasm_bindgen::JsCast;
-        let event: Option<&web_sys::MouseEvent> = event.dyn_ref();
-        if event.map(|e| e.alt_key() || e.ctrl_key()).unwrap_or(false) {
-            CellInteractKind::Flag
-        } else {
-            CellInteractKind::Open
+/// The kind of interaction which makes `move_type`.
+impl From<api::GameMoveType> for CellInteractKind {
+    fn from(move_type: api::GameMoveType) -> Self {
+        match move_type {
+            api::GameMoveType::Flag => CellInteractKind::Flag,
+            api::GameMoveType::RemoveFlag => CellInteractKind::RemoveFlag,
+            api::GameMoveType::Open => CellInteractKind::Open,
         }
     }
 }
@@ -0,0 +1,99 @@
+use crate::{api, model::RejectedMove};
+
+/// Something the player needs to know about a move they made on a cell, shown on the cell itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CellMark {
+    /// The move couldn't reach the API and is held until it can
+    Unsynced,
+    /// The API refused the move, it is offered to be made again until another move is made on the
+    /// cell
+    Rejected(RejectedMove),
+}
+
+impl CellMark {
+    /// The class given to the marked cell.
+    pub fn class(&self) -> &'static str {
+        match self {
+            CellMark::Unsynced => "unsynced",
+            CellMark::Rejected(_) => "rejected",
+        }
+    }
+
+    /// Describe the mark for screen readers, read after the label of the cell.
+    pub fn label(&self) -> String {
+        match self {
+            CellMark::Unsynced => String::from("waiting to sync"),
+            CellMark::Rejected(rejected) => {
+                let action = match rejected.input.move_type {
+                    api::GameMoveType::Flag => "flag",
+                    api::GameMoveType::RemoveFlag => "remove the flag",
+                    api::GameMoveType::Open => "open",
+                };
+                format!("couldn't {}: {}, press R to retry", action, rejected.reason)
+            }
+        }
+    }
+}
+
+/// The marks on the cells of a board, one for each move which has one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellMarks {
+    /// Marked moves in the order they were made, as (row, column, mark)
+    marks: Vec<(usize, usize, CellMark)>,
+}
+
+impl CellMarks {
+    pub fn new(marks: Vec<(usize, usize, CellMark)>) -> Self {
+        Self { marks }
+    }
+
+    /// The mark on the cell at `row` and `column`. A cell with more than one marked move shows the
+    /// mark of the latest one.
+    pub fn get(&self, row: usize, column: usize) -> Option<&CellMark> {
+        self.marks
+            .iter()
+            .rev()
+            .find(|(y, x, _)| (*y, *x) == (row, column))
+            .map(|(_, _, mark)| mark)
+    }
+
+    /// How many moves are marked with `mark`.
+    pub fn count(&self, mark: &CellMark) -> usize {
+        self.marks.iter().filter(|(_, _, m)| m == mark).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_latest_mark_for_cell() {
+        let marks = CellMarks::new(vec![
+            (0, 1, CellMark::Unsynced),
+            (1, 0, CellMark::Unsynced),
+            (0, 1, CellMark::Unsynced),
+        ]);
+        assert_eq!(marks.get(0, 1), Some(&CellMark::Unsynced));
+        assert_eq!(marks.get(1, 1), None);
+        assert_eq!(marks.count(&CellMark::Unsynced), 3);
+        assert_eq!(CellMarks::default().count(&CellMark::Unsynced), 0);
+    }
+
+    #[test]
+    fn describes_rejected_move() {
+        let mark = CellMark::Rejected(RejectedMove {
+            input: api::GameMoveInput {
+                row: 0,
+                column: 1,
+                move_type: api::GameMoveType::Flag,
+            },
+            reason: crate::model::RejectReason::NotApplied,
+        });
+        assert_eq!(mark.class(), "rejected");
+        assert_eq!(
+            mark.label(),
+            "couldn't flag: The move was not applied to the game, press R to retry"
+        );
+    }
+}
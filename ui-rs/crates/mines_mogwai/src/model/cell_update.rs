@@ -3,10 +3,24 @@ pub enum CellUpdate {
     All {
         cells: Vec<Vec<crate::model::BoardValue>>,
     },
-    #[allow(unused)]
     Single {
         row: usize,
         column: usize,
         value: crate::model::BoardValue,
     },
 }
+
+impl CellUpdate {
+    /// The value the update gives the cell at `row` and `column`, if it changes that cell.
+    pub fn value_at(&self, row: usize, column: usize) -> Option<crate::model::BoardValue> {
+        match self {
+            CellUpdate::All { cells } => cells.get(row).and_then(|r| r.get(column)).copied(),
+            CellUpdate::Single {
+                row: y,
+                column: x,
+                value,
+            } if *x == column && *y == row => Some(*value),
+            CellUpdate::Single { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::model::CellInput;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, KeyboardEvent};
+
+/// A way the keyboard cursor can move around the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorMove {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// First cell of the row
+    RowStart,
+    /// Last cell of the row
+    RowEnd,
+    /// First row of the board, staying in the same column
+    Top,
+    /// Last row of the board, staying in the same column
+    Bottom,
+    /// Straight to the cell at `row` and `column`, e.g. because it was clicked
+    To {
+        row: usize,
+        column: usize,
+    },
+}
+
+/// What a key pressed on the board asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyCommand {
+    /// Move the cursor
+    Move(CursorMove),
+    /// Act on the cell under the cursor
+    Input(CellInput),
+}
+
+impl KeyCommand {
+    /// Read the `KeyboardEvent.key` value `key`. Arrow keys and `hjkl` move the cursor, Space and
+    /// Enter open, `f` flags and `c` chords.
+    pub fn from_key(key: &str) -> Option<Self> {
+        let command = match key {
+            "ArrowUp" | "k" => KeyCommand::Move(CursorMove::Up),
+            "ArrowDown" | "j" => KeyCommand::Move(CursorMove::Down),
+            "ArrowLeft" | "h" => KeyCommand::Move(CursorMove::Left),
+            "ArrowRight" | "l" => KeyCommand::Move(CursorMove::Right),
+            "Home" => KeyCommand::Move(CursorMove::RowStart),
+            "End" => KeyCommand::Move(CursorMove::RowEnd),
+            "PageUp" => KeyCommand::Move(CursorMove::Top),
+            "PageDown" => KeyCommand::Move(CursorMove::Bottom),
+            " " | "Enter" => KeyCommand::Input(CellInput::Open),
+            "f" => KeyCommand::Input(CellInput::Flag),
+            "c" => KeyCommand::Input(CellInput::Chord),
+            "r" | "R" => KeyCommand::Input(CellInput::Retry),
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    /// Read a key press. Keys held with a modifier are left to the browser, keys used by the board
+    /// don't also scroll the page.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let event: &KeyboardEvent = event.dyn_ref()?;
+        if event.alt_key() || event.ctrl_key() || event.meta_key() {
+            return None;
+        }
+        let command = Self::from_key(&event.key())?;
+        event.prevent_default();
+        Some(command)
+    }
+}
+
+/// The single cell of a board which can be focused, moved around with the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    row: usize,
+    column: usize,
+    rows: usize,
+    columns: usize,
+}
+
+impl Cursor {
+    /// A cursor on the first cell of a board with `rows` rows of `columns` cells.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            row: 0,
+            column: 0,
+            rows,
+            columns,
+        }
+    }
+
+    /// Where the cursor is, as (row, column).
+    pub fn position(&self) -> (usize, usize) {
+        (self.row, self.column)
+    }
+
+    /// Move the cursor, stopping at the edges of the board. Returns the new position.
+    pub fn apply(&mut self, movement: CursorMove) -> (usize, usize) {
+        let last_row = self.rows.saturating_sub(1);
+        let last_column = self.columns.saturating_sub(1);
+        match movement {
+            CursorMove::Up => self.row = self.row.saturating_sub(1),
+            CursorMove::Down => self.row = (self.row + 1).min(last_row),
+            CursorMove::Left => self.column = self.column.saturating_sub(1),
+            CursorMove::Right => self.column = (self.column + 1).min(last_column),
+            CursorMove::RowStart => self.column = 0,
+            CursorMove::RowEnd => self.column = last_column,
+            CursorMove::Top => self.row = 0,
+            CursorMove::Bottom => self.row = last_row,
+            CursorMove::To { row, column } => {
+                self.row = row.min(last_row);
+                self.column = column.min(last_column);
+            }
+        }
+        self.position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_keys() {
+        assert_eq!(KeyCommand::from_key("ArrowLeft"), KeyCommand::from_key("h"));
+        assert_eq!(
+            KeyCommand::from_key("PageDown"),
+            Some(KeyCommand::Move(CursorMove::Bottom))
+        );
+        assert_eq!(
+            KeyCommand::from_key(" "),
+            Some(KeyCommand::Input(CellInput::Open))
+        );
+        assert_eq!(
+            KeyCommand::from_key("c"),
+            Some(KeyCommand::Input(CellInput::Chord))
+        );
+        assert_eq!(
+            KeyCommand::from_key("r"),
+            Some(KeyCommand::Input(CellInput::Retry))
+        );
+        assert_eq!(KeyCommand::from_key("R"), KeyCommand::from_key("r"));
+        assert_eq!(KeyCommand::from_key("Tab"), None);
+    }
+
+    #[test]
+    fn moves_within_board() {
+        let mut cursor = Cursor::new(3, 4);
+        assert_eq!(cursor.apply(CursorMove::Up), (0, 0));
+        assert_eq!(cursor.apply(CursorMove::Left), (0, 0));
+        assert_eq!(cursor.apply(CursorMove::Right), (0, 1));
+        assert_eq!(cursor.apply(CursorMove::Down), (1, 1));
+        assert_eq!(cursor.apply(CursorMove::Down), (2, 1));
+        assert_eq!(cursor.apply(CursorMove::Down), (2, 1));
+    }
+
+    #[test]
+    fn jumps_to_edges() {
+        let mut cursor = Cursor::new(3, 4);
+        cursor.apply(CursorMove::To { row: 1, column: 2 });
+        assert_eq!(cursor.apply(CursorMove::RowEnd), (1, 3));
+        assert_eq!(cursor.apply(CursorMove::Bottom), (2, 3));
+        assert_eq!(cursor.apply(CursorMove::RowStart), (2, 0));
+        assert_eq!(cursor.apply(CursorMove::Top), (0, 0));
+        assert_eq!(cursor.apply(CursorMove::To { row: 9, column: 9 }), (2, 3));
+    }
+}
@@ -0,0 +1,45 @@
+/// Something which can be done to a whole game once the player confirms it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameAction {
+    /// Give up on an open game, which marks it as lost
+    Abandon,
+    /// Remove the game entirely
+    Delete,
+}
+
+impl GameAction {
+    /// Text of the button which asks for the action.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Abandon => "Abandon",
+            Self::Delete => "Delete",
+        }
+    }
+
+    /// The action as a verb within a sentence.
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Self::Abandon => "abandon",
+            Self::Delete => "delete",
+        }
+    }
+
+    /// The question the player confirms the action with.
+    pub fn question(&self) -> &'static str {
+        match self {
+            Self::Abandon => "Abandon this game?",
+            Self::Delete => "Delete this game?",
+        }
+    }
+}
+
+/// An interaction with the controls for a `GameAction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameActionEvent {
+    /// The player asked for the action, it still has to be confirmed
+    Ask(GameAction),
+    /// The player confirmed the action they asked for
+    Confirm,
+    /// The player changed their mind
+    Dismiss,
+}
@@ -0,0 +1,460 @@
+use crate::{
+    api,
+    model::{BoardValue, CellMark, CellMarks, CellUpdate},
+};
+use std::collections::VecDeque;
+
+/// A response from the API tagged with the sequence number of the request it answers.
+pub type SequencedResponse = (u64, Result<api::GameState, api::FetchError>);
+
+/// A move waiting to be confirmed by the API along with the sequence number it was assigned when
+/// it was queued.
+#[derive(Clone, Debug)]
+pub struct QueuedMove {
+    pub sequence: u64,
+    pub input: api::GameMoveInput,
+}
+
+/// A move the API refused, along with why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectedMove {
+    pub input: api::GameMoveInput,
+    pub reason: RejectReason,
+}
+
+/// Why the API refused a move.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RejectReason {
+    /// The request for the move failed
+    Failed(api::FetchError),
+    /// The move was sent along with others and the API reported it wasn't applied, e.g. because
+    /// an earlier move ended the game
+    NotApplied,
+}
+
+impl std::fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RejectReason::Failed(error) => error.fmt(f),
+            RejectReason::NotApplied => f.write_str("The move was not applied to the game"),
+        }
+    }
+}
+
+/// Keeps the moves for a single game in the order they were made so they can be sent to the API
+/// one at a time, or all together when replaying moves held while offline. Every request for the
+/// game, including fetching it, is given a sequence number so responses to requests older than the
+/// last applied response can be dropped.
+///
+/// When a move can't reach the API the queue goes offline. Moves made while offline are held,
+/// along with the moves which failed, until the queue is resumed.
+#[derive(Debug, Default)]
+pub struct MoveQueue {
+    /// Sequence number most recently given out
+    sequence: u64,
+    /// Sequence number of the most recently applied response
+    applied: u64,
+    /// Moves which have been sent together but not yet responded to. A batch is identified by
+    /// the sequence number of its last move.
+    in_flight: Vec<QueuedMove>,
+    /// Moves waiting for `in_flight` to complete
+    queued: VecDeque<QueuedMove>,
+    /// Whether moves are being held until the API can be reached
+    offline: bool,
+    /// Whether the moves waiting in the queue have failed to sync at least once, meaning they
+    /// need to be kept somewhere durable until they are confirmed
+    unsynced: bool,
+    /// Moves the API refused, kept until another move is made on their cell
+    rejected: Vec<RejectedMove>,
+    /// Whether the API can't take several moves at once, so held moves are sent one at a time
+    unbatched: bool,
+}
+
+impl MoveQueue {
+    /// Give out a sequence number for a request which is not a move.
+    pub fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    /// Add `input` to the end of the queue. The `QueuedMove` is returned if it should be sent
+    /// right away because no other move is waiting on a response and the queue is online.
+    pub fn push(&mut self, input: api::GameMoveInput) -> Option<QueuedMove> {
+        // A new move for a cell replaces any refused move for it
+        self.rejected.retain(|rejected| {
+            (rejected.input.row, rejected.input.column) != (input.row, input.column)
+        });
+        let queued = QueuedMove {
+            sequence: self.next_sequence(),
+            input,
+        };
+        if self.in_flight.is_empty() && !self.offline {
+            self.in_flight = vec![queued.clone()];
+            Some(queued)
+        } else {
+            self.queued.push_back(queued);
+            None
+        }
+    }
+
+    /// Add moves which were saved by an earlier visit to the game. The queue is offline until
+    /// `resume` is called.
+    pub fn restore(&mut self, inputs: Vec<api::GameMoveInput>) {
+        if inputs.is_empty() {
+            return;
+        }
+        self.offline = true;
+        self.unsynced = true;
+        for input in inputs {
+            self.push(input);
+        }
+    }
+
+    /// Mark the move, or batch of moves, identified by `sequence` as responded to. The next
+    /// `QueuedMove` to send is returned, if there is one.
+    pub fn complete(&mut self, sequence: u64) -> Option<QueuedMove> {
+        if !self.is_in_flight(sequence) {
+            return None;
+        }
+        self.in_flight = self.queued.pop_front().into_iter().collect();
+        if self.in_flight.is_empty() {
+            self.unsynced = false;
+        }
+        self.in_flight.first().cloned()
+    }
+
+    /// Mark the move, or batch of moves, identified by `sequence` as refused by the API because of
+    /// `error`. The moves are kept until another move is made on their cell. The next
+    /// `QueuedMove` to send is returned, if there is one.
+    pub fn reject(&mut self, sequence: u64, error: api::FetchError) -> Option<QueuedMove> {
+        if !self.is_in_flight(sequence) {
+            return None;
+        }
+        let rejected = self.in_flight.iter().map(|queued| RejectedMove {
+            input: queued.input.clone(),
+            reason: RejectReason::Failed(error.clone()),
+        });
+        self.rejected.extend(rejected.collect::<Vec<_>>());
+        self.complete(sequence)
+    }
+
+    /// Mark the batch of moves identified by `sequence` as responded to with an outcome for each
+    /// move. Moves which weren't applied, including any without an outcome, are kept as refused
+    /// until another move is made on their cell. The next `QueuedMove` to send is returned, if
+    /// there is one.
+    pub fn complete_batch(
+        &mut self,
+        sequence: u64,
+        outcomes: &[api::MoveOutcome],
+    ) -> Option<QueuedMove> {
+        if !self.is_in_flight(sequence) {
+            return None;
+        }
+        let rejected = self
+            .in_flight
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| outcomes.get(*index) != Some(&api::MoveOutcome::Applied))
+            .map(|(_, queued)| RejectedMove {
+                input: queued.input.clone(),
+                reason: RejectReason::NotApplied,
+            });
+        self.rejected.extend(rejected.collect::<Vec<_>>());
+        self.complete(sequence)
+    }
+
+    /// Send the moves of the batch identified by `sequence` one at a time because the API can't
+    /// take them together, as are moves held from now on. The first of them is returned to be
+    /// sent right away.
+    pub fn unbatch(&mut self, sequence: u64) -> Option<QueuedMove> {
+        if !self.is_in_flight(sequence) {
+            return None;
+        }
+        self.unbatched = true;
+        for queued in self.in_flight.drain(1..).rev() {
+            self.queued.push_front(queued);
+        }
+        self.in_flight.first().cloned()
+    }
+
+    /// Moves the API refused, in the order they were made.
+    #[cfg(test)]
+    pub fn rejected(&self) -> &[RejectedMove] {
+        &self.rejected
+    }
+
+    /// Put the move, or batch of moves, identified by `sequence` back at the front of the queue
+    /// because it couldn't reach the API. Nothing more is sent until `resume` is called.
+    pub fn suspend(&mut self, sequence: u64) {
+        if self.is_in_flight(sequence) {
+            for queued in self.in_flight.drain(..).rev() {
+                self.queued.push_front(queued);
+            }
+        }
+        self.offline = true;
+        self.unsynced = true;
+    }
+
+    /// Go back online. Every held move is returned, to be sent together, if nothing is already
+    /// waiting on a response. Only the first is returned once the API is known not to take moves
+    /// together.
+    pub fn resume(&mut self) -> Vec<QueuedMove> {
+        self.offline = false;
+        if !self.in_flight.is_empty() {
+            return vec![];
+        }
+        self.in_flight = if self.unbatched {
+            self.queued.pop_front().into_iter().collect()
+        } else {
+            self.queued.drain(..).collect()
+        };
+        self.in_flight.clone()
+    }
+
+    /// Forget about every move which has not been responded to or was refused.
+    pub fn clear(&mut self) {
+        self.in_flight.clear();
+        self.queued.clear();
+        self.rejected.clear();
+        self.unsynced = false;
+    }
+
+    /// Moves which need to be kept until they are confirmed, in the order they were made. Empty
+    /// unless a move has failed to reach the API.
+    pub fn unsynced(&self) -> Vec<api::GameMoveInput> {
+        if self.unsynced {
+            self.waiting().map(|queued| queued.input.clone()).collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Mark the cells of the moves the API refused and of the moves which need to be kept until
+    /// they are confirmed.
+    pub fn marks(&self) -> CellMarks {
+        let rejected = self.rejected.iter().map(|rejected| {
+            let api::GameMoveInput { row, column, .. } = rejected.input;
+            (row, column, CellMark::Rejected(rejected.clone()))
+        });
+        let unsynced = self
+            .unsynced()
+            .into_iter()
+            .map(|input| (input.row, input.column, CellMark::Unsynced));
+        CellMarks::new(rejected.chain(unsynced).collect())
+    }
+
+    /// Whether a successful response to the request identified by `sequence` should be applied.
+    /// Once accepted, responses to any earlier request are no longer accepted.
+    pub fn accept(&mut self, sequence: u64) -> bool {
+        if sequence > self.applied {
+            self.applied = sequence;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Updates showing every cell with a move which has not been responded to as
+    /// `BoardValue::Pending`.
+    pub fn pending(&self) -> Vec<CellUpdate> {
+        self.waiting()
+            .map(|queued| CellUpdate::Single {
+                row: queued.input.row,
+                column: queued.input.column,
+                value: BoardValue::Pending,
+            })
+            .collect()
+    }
+
+    fn is_in_flight(&self, sequence: u64) -> bool {
+        matches!(self.in_flight.last(), Some(queued) if queued.sequence == sequence)
+    }
+
+    fn waiting(&self) -> impl Iterator<Item = &QueuedMove> {
+        self.in_flight.iter().chain(self.queued.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(row: usize, column: usize) -> api::GameMoveInput {
+        api::GameMoveInput {
+            row,
+            column,
+            move_type: api::GameMoveType::Open,
+        }
+    }
+
+    #[test]
+    fn sends_one_move_at_a_time() {
+        let mut moves = MoveQueue::default();
+        let first = moves.push(open(0, 0)).unwrap();
+        assert!(moves.push(open(0, 1)).is_none());
+        assert!(moves.push(open(1, 1)).is_none());
+        let second = moves.complete(first.sequence).unwrap();
+        assert_eq!(second.input.column, 1);
+        let third = moves.complete(second.sequence).unwrap();
+        assert_eq!(third.input.row, 1);
+        assert!(moves.complete(third.sequence).is_none());
+        assert!(moves.push(open(2, 2)).is_some());
+    }
+
+    #[test]
+    fn ignores_unknown_completion() {
+        let mut moves = MoveQueue::default();
+        let first = moves.push(open(0, 0)).unwrap();
+        moves.push(open(0, 1));
+        assert!(moves.complete(first.sequence + 1).is_none());
+        assert!(moves.complete(first.sequence).is_some());
+    }
+
+    #[test]
+    fn drops_stale_responses() {
+        let mut moves = MoveQueue::default();
+        let fetch = moves.next_sequence();
+        let first = moves.push(open(0, 0)).unwrap();
+        assert!(moves.accept(first.sequence));
+        assert!(!moves.accept(fetch));
+        assert!(!moves.accept(first.sequence));
+    }
+
+    #[test]
+    fn holds_moves_while_offline() {
+        let mut moves = MoveQueue::default();
+        let first = moves.push(open(0, 0)).unwrap();
+        assert!(moves.unsynced().is_empty());
+        moves.suspend(first.sequence);
+        assert!(moves.push(open(0, 1)).is_none());
+        assert_eq!(moves.unsynced(), vec![open(0, 0), open(0, 1)]);
+        let replay = moves.resume();
+        assert_eq!(replay.len(), 2);
+        assert_eq!(replay[0].input, open(0, 0));
+        assert!(moves.resume().is_empty());
+        assert!(moves.push(open(1, 1)).is_none());
+        // Only the last move identifies the batch
+        assert!(moves.complete(replay[0].sequence).is_none());
+        let third = moves.complete(replay[1].sequence).unwrap();
+        assert_eq!(moves.unsynced(), vec![open(1, 1)]);
+        assert!(moves.complete(third.sequence).is_none());
+        assert!(moves.unsynced().is_empty());
+    }
+
+    #[test]
+    fn holds_failed_batch_in_order() {
+        let mut moves = MoveQueue::default();
+        moves.restore(vec![open(0, 0), open(0, 1)]);
+        let replay = moves.resume();
+        moves.push(open(1, 1));
+        moves.suspend(replay[1].sequence);
+        assert_eq!(moves.unsynced(), vec![open(0, 0), open(0, 1), open(1, 1)]);
+        assert_eq!(moves.resume().len(), 3);
+    }
+
+    #[test]
+    fn rejects_moves_batch_did_not_apply() {
+        let mut moves = MoveQueue::default();
+        moves.restore(vec![open(0, 0), open(0, 1), open(1, 1)]);
+        let replay = moves.resume();
+        assert!(moves
+            .complete_batch(
+                replay[2].sequence,
+                &[api::MoveOutcome::Applied, api::MoveOutcome::Rejected]
+            )
+            .is_none());
+        // A move without an outcome wasn't applied either
+        let rejected: Vec<_> = moves.rejected().iter().map(|r| r.input.clone()).collect();
+        assert_eq!(rejected, vec![open(0, 1), open(1, 1)]);
+        assert_eq!(moves.rejected()[0].reason, RejectReason::NotApplied);
+        assert!(moves.unsynced().is_empty());
+    }
+
+    #[test]
+    fn sends_batch_one_at_a_time_once_unbatched() {
+        let mut moves = MoveQueue::default();
+        moves.restore(vec![open(0, 0), open(0, 1)]);
+        let replay = moves.resume();
+        assert!(moves.unbatch(replay[0].sequence).is_none());
+        let first = moves.unbatch(replay[1].sequence).unwrap();
+        assert_eq!(first.input, open(0, 0));
+        // Held moves stay held until they are confirmed
+        assert_eq!(moves.unsynced(), vec![open(0, 0), open(0, 1)]);
+        moves.suspend(first.sequence);
+        moves.push(open(1, 1));
+        let resumed = moves.resume();
+        assert_eq!(resumed.len(), 1);
+        let second = moves.complete(resumed[0].sequence).unwrap();
+        assert_eq!(second.input, open(0, 1));
+        let third = moves.complete(second.sequence).unwrap();
+        assert_eq!(third.input, open(1, 1));
+    }
+
+    #[test]
+    fn restores_saved_moves() {
+        let mut moves = MoveQueue::default();
+        moves.restore(vec![]);
+        assert!(moves.unsynced().is_empty());
+        moves.restore(vec![open(1, 0), open(1, 1)]);
+        // Nothing is sent until the queue is resumed
+        assert!(moves.push(open(1, 2)).is_none());
+        assert_eq!(moves.unsynced(), vec![open(1, 0), open(1, 1), open(1, 2)]);
+        assert_eq!(moves.marks().get(1, 1), Some(&CellMark::Unsynced));
+        assert_eq!(moves.marks().get(0, 0), None);
+        assert_eq!(moves.resume()[0].input, open(1, 0));
+        moves.clear();
+        assert!(moves.unsynced().is_empty());
+    }
+
+    #[test]
+    fn keeps_rejected_moves_until_replaced() {
+        let mut moves = MoveQueue::default();
+        let first = moves.push(open(0, 0)).unwrap();
+        moves.push(open(0, 1));
+        let error = api::FetchError::ParseError;
+        assert!(moves.reject(first.sequence + 1, error.clone()).is_none());
+        let second = moves.reject(first.sequence, error.clone()).unwrap();
+        let rejected = RejectedMove {
+            input: open(0, 0),
+            reason: RejectReason::Failed(error),
+        };
+        assert_eq!(moves.rejected(), std::slice::from_ref(&rejected));
+        assert_eq!(moves.marks().get(0, 0), Some(&CellMark::Rejected(rejected)));
+        // Nothing is sent while another move is waiting
+        assert!(moves.push(open(0, 0)).is_none());
+        assert!(moves.rejected().is_empty());
+        assert_eq!(moves.marks(), CellMarks::default());
+        let retried = moves.complete(second.sequence).unwrap();
+        assert_eq!(retried.input, open(0, 0));
+    }
+
+    #[test]
+    fn replaces_rejected_move_for_cell() {
+        let mut moves = MoveQueue::default();
+        let first = moves.push(open(0, 0)).unwrap();
+        moves.reject(first.sequence, api::FetchError::FetchError);
+        assert_eq!(moves.rejected().len(), 1);
+        moves.push(api::GameMoveInput {
+            row: 0,
+            column: 0,
+            move_type: api::GameMoveType::Flag,
+        });
+        assert!(moves.rejected().is_empty());
+    }
+
+    #[test]
+    fn marks_unconfirmed_cells_pending() {
+        let mut moves = MoveQueue::default();
+        let first = moves.push(open(0, 0)).unwrap();
+        moves.push(open(1, 1));
+        moves.push(open(5, 5));
+        moves.complete(first.sequence);
+        let pending = moves.pending();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].value_at(1, 1), Some(BoardValue::Pending));
+        assert_eq!(pending[0].value_at(0, 0), None);
+        assert_eq!(pending[1].value_at(5, 5), Some(BoardValue::Pending));
+        moves.clear();
+        assert!(moves.pending().is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::api::{FetchError, GameId, GameMoveInput};
+
+/// Prefix of the `localStorage` key holding the moves for a game which have not reached the API.
+const STORAGE_PREFIX: &str = "mines:unsynced-moves:";
+
+/// Whether `error` means the API couldn't be reached, as opposed to the API rejecting the request.
+pub fn is_connectivity_error(error: &FetchError) -> bool {
+    matches!(error, FetchError::FetchError)
+}
+
+/// Moves for the game identified by `game_id` which were saved because they couldn't reach the
+/// API, in the order they were made.
+pub fn load(game_id: GameId) -> Vec<GameMoveInput> {
+    let stored = storage().and_then(|storage| storage.get_item(&storage_key(game_id)).ok());
+    match stored.flatten() {
+        Some(json) => serde_json::from_str(&json).unwrap_or_else(|error| {
+            ::log::warn!("discarding unreadable moves for {}: {}", game_id, error);
+            vec![]
+        }),
+        None => vec![],
+    }
+}
+
+/// Replace the saved moves for the game identified by `game_id` with `moves`. Nothing is kept when
+/// `moves` is empty.
+pub fn save(game_id: GameId, moves: &[GameMoveInput]) {
+    let storage = match storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    let key = storage_key(game_id);
+    let result = if moves.is_empty() {
+        storage.remove_item(&key)
+    } else {
+        match serde_json::to_string(moves) {
+            Ok(json) => storage.set_item(&key, &json),
+            Err(error) => {
+                ::log::error!("unable to save moves for {}: {}", game_id, error);
+                return;
+            }
+        }
+    };
+    if let Err(error) = result {
+        ::log::error!("unable to save moves for {}: {:?}", game_id, error);
+    }
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    // There is no `window` outside of the browser, e.g. when running tests
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    mogwai::utils::window().local_storage().ok().flatten()
+}
+
+fn storage_key(game_id: GameId) -> String {
+    format!("{}{}", STORAGE_PREFIX, game_id.to_hyphenated())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+
+    #[test]
+    fn only_unreachable_is_connectivity() {
+        assert!(is_connectivity_error(&FetchError::FetchError));
+        assert!(!is_connectivity_error(&FetchError::Timeout));
+        assert!(!is_connectivity_error(&FetchError::Cancelled));
+        assert!(!is_connectivity_error(&FetchError::Server(
+            api::ServerError::from_response(409, "")
+        )));
+    }
+
+    #[test]
+    fn keys_by_game() {
+        let game_id = uuid::Uuid::nil();
+        assert_eq!(
+            storage_key(game_id),
+            "mines:unsynced-moves:00000000-0000-0000-0000-000000000000"
+        );
+    }
+}
@@ -1,12 +1,24 @@
 mod game;
+mod game_actions;
 mod game_list;
 mod index;
+mod login;
 
 use mogwai::prelude::*;
 
 pub use game::game;
 pub use game_list::game_list;
 pub use index::home;
+pub use login::login;
+
+/// Read the current value of the `<input>` which triggered `event`.
+fn input_value(event: &Event) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
 
 pub fn not_found() -> ViewBuilder<HtmlElement> {
     builder! {
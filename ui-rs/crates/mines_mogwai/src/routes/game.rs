@@ -1,16 +1,35 @@
-use crate::{api, components, model};
+use super::game_actions::game_actions;
+use crate::{api, components, live, model, offline, spawn, tabs, Route};
 use mogwai::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 /// Create a game screen for the game referenced by the provided `api::GameId`. Set up the game
-/// screen and display a game board. The board will display as empty until game information can be
-/// retrieved from the API.
+/// screen and display a game board. The board shows the last known state of the game, or is empty
+/// if there isn't one, until game information can be retrieved from the API through `client`.
+/// Requests for the game are aborted once `cancel` is cancelled. Once the game is deleted the
+/// list of games is sent into `dispatch`, as is any game started from the same seed.
 #[allow(unused_braces)]
-pub fn game(game_id: api::GameId) -> ViewBuilder<HtmlElement> {
+pub fn game(
+    dispatch: Transmitter<Route>,
+    game_id: api::GameId,
+    client: api::Client,
+    cancel: api::CancelSignal,
+) -> ViewBuilder<HtmlElement> {
     // Create a transmitter to send button clicks into.
     let tx_game: Transmitter<api::GameState> = Transmitter::new();
     let tx_cells: Transmitter<model::CellInteract> = Transmitter::new();
+    let tx_online: Transmitter<Event> = Transmitter::new();
+    let tx_marks: Transmitter<model::CellMarks> = Transmitter::new();
+    // Moves are sent one at a time in the order they were made, starting with any saved by an
+    // earlier visit because they couldn't reach the API, which are sent together
+    let moves = Rc::new(RefCell::new(model::MoveQueue::default()));
+    moves.borrow_mut().restore(offline::load(game_id));
     // Create the upstream `Transmitter` for `tx_game` (i.e. messages sent to `tx_api` will be
-    // passed to `tx_game` if the response is success.
+    // passed to `tx_game` if the response is success. A failure sends the last successful game
+    // again, which puts cells the player changed back to what the API last confirmed.
     let tx_api = tx_game.contra_filter_fold(
         None,
         |current: &mut Option<api::GameState>, r: &Result<api::GameState, api::FetchError>| {
@@ -20,34 +39,411 @@ pub fn game(game_id: api::GameId) -> ViewBuilder<HtmlElement> {
             current.clone()
         },
     );
-    tx_api.send_async(api::get_game(game_id));
-    // Set up to receive board interactions which will trigger future board states through api
-    // responses received in `tx_api`.
-    tx_cells.spawn_recv().respond(move |interaction| {
-        tx_api.send_async(api::patch_game(game_id, interaction.into()));
+    // Successful responses to requests older than the last applied response are dropped so the
+    // board never moves back to an earlier state. Games this tab was answered with are shared with
+    // the other tabs once they are applied.
+    let tx_response = tx_api.contra_filter_fold_shared(
+        moves.clone(),
+        |moves: &mut model::MoveQueue, response: &model::SequencedResponse| {
+            let accepted = accept_response(moves, response);
+            if let Some(Ok(game)) = &accepted {
+                tabs::post_game(game);
+            }
+            accepted
+        },
+    );
+    let tx_log: Transmitter<components::MoveLog> = Transmitter::new();
+    let log_view = components::move_log(&tx_log.spawn_recv());
+    let logger = MoveLogger::new(game_id, client.clone(), cancel.clone(), &tx_game, tx_log);
+    // Games from elsewhere, i.e. pushed by the API or shared by another tab, are sequenced the
+    // same way but never shared, as the other tabs see them too. They may hold moves made
+    // elsewhere, so the moves are read again when they don't lead to the same board.
+    let seen_logger = logger.clone();
+    let tx_seen = tx_api.contra_filter_fold_shared(
+        moves.clone(),
+        move |moves: &mut model::MoveQueue, response: &model::SequencedResponse| {
+            let accepted = accept_response(moves, response);
+            if let Some(Ok(game)) = &accepted {
+                seen_logger.refresh(&game.board);
+            }
+            accepted
+        },
+    );
+    let tx_cached = tx_api.contra_filter_fold_shared(moves.clone(), accept_response);
+    let error_view = game_error(&tx_api);
+    let unsynced_view = game_unsynced(&tx_marks);
+    let board_view = game_board(&tx_game, tx_cells.clone(), moves.clone(), &tx_marks);
+    let tx_replay = replay_game(
+        dispatch.clone(),
+        client.clone(),
+        cancel.clone(),
+        tx_api.clone(),
+    );
+    let seed_view = game_seed(&tx_game, &tx_replay);
+    let actions_view = game_actions(
+        dispatch,
+        game_id,
+        client.clone(),
+        cancel.clone(),
+        tx_api.clone(),
+        &tx_game,
+    );
+    // Show the game as it was last seen while the API is asked for its current state. Taking a
+    // sequence number first means the cached state is dropped if the API answers before it is shown.
+    if let Some(cached) = client.cached_game(game_id) {
+        let sequence = moves.borrow_mut().next_sequence();
+        spawn::send_async(&tx_cached, async move { (sequence, Ok(cached)) });
+    }
+    // The moves are read once the board they lead to is known
+    let sequence = moves.borrow_mut().next_sequence();
+    let get_game = client.get_game(game_id, cancel.clone());
+    let loader = logger.clone();
+    spawn::send_async(&tx_response, async move {
+        let result = get_game.await;
+        match &result {
+            Ok(game) => loader.load(&game.board),
+            Err(_) => loader.load(&[]),
+        }
+        (sequence, result)
+    });
+    // Keep up with moves made elsewhere, e.g. on another device or in another tab. Games from
+    // elsewhere are sequenced like responses so an old one never replaces a newer board.
+    live::watch(
+        game_id,
+        client.clone(),
+        moves.clone(),
+        tx_seen.clone(),
+        cancel.clone(),
+    );
+    let tab_moves = moves.clone();
+    let tx_shared = tx_seen.contra_map(move |game: &api::GameState| {
+        (tab_moves.borrow_mut().next_sequence(), Ok(game.clone()))
     });
+    tabs::watch_game(game_id, tx_shared, &cancel);
+    let submitter = MoveSubmitter {
+        client,
+        game_id,
+        moves,
+        logger,
+        tx_response,
+        tx_marks,
+        cancel,
+    };
+    // Replay saved moves now and again whenever the browser comes back online
+    submitter.resume();
+    let online_submitter = submitter.clone();
+    tx_online
+        .spawn_recv()
+        .respond(move |_| online_submitter.resume());
+    // Set up to receive board interactions which will trigger future board states through api
+    // responses received in `tx_response`.
+    tx_cells
+        .spawn_recv()
+        .respond(move |interaction| submitter.push(interaction.into()));
     builder! {
-        <main class="container">
+        <main class="container" window:online=tx_online>
             <div class="overlay">
                 "This site is only supported in portrait mode."
             </div>
             <div class="game-board" data-game-id=&game_id.to_hyphenated().to_string()>
-                {game_board(&tx_game, tx_cells)}
+                {board_view}
             </div>
             {game_status(&tx_game)}
+            {game_mines(&tx_game)}
+            {game_announcements(&tx_game)}
+            {seed_view}
+            {actions_view}
+            {error_view}
+            {unsynced_view}
+            {log_view}
         </main>
     }
 }
 
+/// Pass on `response` unless it is a game older than the last one applied.
+fn accept_response(
+    moves: &mut model::MoveQueue,
+    (sequence, result): &model::SequencedResponse,
+) -> Option<Result<api::GameState, api::FetchError>> {
+    match result {
+        Ok(_) if !moves.accept(*sequence) => None,
+        _ => Some(result.clone()),
+    }
+}
+
+/// Keeps the list of moves shown beside the board up to date. The moves are read from the API
+/// once, after which the moves this tab makes are added as the API applies them. They are only
+/// read again when the game changes some other way, e.g. through a move made on another device.
+#[derive(Clone)]
+struct MoveLogger {
+    game_id: api::GameId,
+    client: api::Client,
+    cancel: api::CancelSignal,
+    log: Rc<RefCell<components::MoveLog>>,
+    /// The board the moves in `log` lead to
+    known_board: Rc<RefCell<Vec<Vec<api::BoardValue>>>>,
+    /// The most recent request for the moves, and the request the moves in `log` were read from
+    requests: Rc<Cell<(u64, u64)>>,
+    tx_log: Transmitter<components::MoveLog>,
+}
+
+impl MoveLogger {
+    fn new(
+        game_id: api::GameId,
+        client: api::Client,
+        cancel: api::CancelSignal,
+        tx_game: &Transmitter<api::GameState>,
+        tx_log: Transmitter<components::MoveLog>,
+    ) -> Self {
+        let logger = Self {
+            game_id,
+            client,
+            cancel,
+            log: Rc::new(RefCell::new(components::MoveLog::default())),
+            known_board: Rc::new(RefCell::new(Vec::new())),
+            requests: Rc::new(Cell::new((0, 0))),
+            tx_log,
+        };
+        let shown = logger.clone();
+        tx_game
+            .spawn_recv()
+            .respond(move |game: &api::GameState| shown.show(&game.board));
+        logger
+    }
+
+    /// Read every move from the API, which is expected to lead to `board`. Only the response to
+    /// the most recent request is shown.
+    fn load(&self, board: &[Vec<api::BoardValue>]) {
+        self.known_board.replace(board.to_vec());
+        let (latest, loaded) = self.requests.get();
+        let request = latest + 1;
+        self.requests.set((request, loaded));
+        let get_game_moves = self
+            .client
+            .get_game_moves(self.game_id, self.cancel.clone());
+        let logger = self.clone();
+        spawn::spawn_local(async move {
+            match get_game_moves.await {
+                Ok(moves) if logger.requests.get().0 == request => {
+                    logger.requests.set((request, request));
+                    logger.log.borrow_mut().moves = moves;
+                    logger.send();
+                }
+                Ok(_) => (),
+                Err(error) => {
+                    ::log::debug!("unable to load moves for {}: {}", logger.game_id, error)
+                }
+            }
+        });
+    }
+
+    /// Add the moves in `inputs`, which the API has just applied leading to `board`. While the
+    /// moves are still being read, or couldn't be, they are read again instead.
+    fn append(&self, inputs: &[api::GameMoveInput], board: &[Vec<api::BoardValue>]) {
+        let (latest, loaded) = self.requests.get();
+        if latest != loaded {
+            self.load(board);
+            return;
+        }
+        self.known_board.replace(board.to_vec());
+        let created_at = now();
+        self.log
+            .borrow_mut()
+            .moves
+            .extend(inputs.iter().map(|input| api::GameMove {
+                column: input.column,
+                row: input.row,
+                move_type: input.move_type,
+                created_at: created_at.clone(),
+            }));
+        self.send();
+    }
+
+    /// Read the moves again unless they are known to lead to `board`.
+    fn refresh(&self, board: &[Vec<api::BoardValue>]) {
+        if *self.known_board.borrow() != board {
+            self.load(board);
+        }
+    }
+
+    /// Describe the moves with what `board` shows.
+    fn show(&self, board: &[Vec<api::BoardValue>]) {
+        if self.log.borrow().board == board {
+            return;
+        }
+        self.log.borrow_mut().board = board.to_vec();
+        let (_, loaded) = self.requests.get();
+        if loaded > 0 {
+            self.send();
+        }
+    }
+
+    fn send(&self) {
+        let log = self.log.borrow().clone();
+        self.tx_log.send(&log);
+    }
+}
+
+/// The current time as an ISO 8601 timestamp. There is no clock to read outside of the browser.
+fn now() -> String {
+    if cfg!(target_arch = "wasm32") {
+        String::from(js_sys::Date::new_0().to_iso_string())
+    } else {
+        String::new()
+    }
+}
+
+/// Sends the moves for a game to the API, one at a time, keeping moves which can't reach the API
+/// in `localStorage` until they can be replayed together in a single request.
+#[derive(Clone)]
+struct MoveSubmitter {
+    client: api::Client,
+    game_id: api::GameId,
+    moves: Rc<RefCell<model::MoveQueue>>,
+    logger: MoveLogger,
+    tx_response: Transmitter<model::SequencedResponse>,
+    tx_marks: Transmitter<model::CellMarks>,
+    cancel: api::CancelSignal,
+}
+
+impl MoveSubmitter {
+    /// Queue `input` behind any moves already waiting and start sending if nothing is.
+    fn push(&self, input: api::GameMoveInput) {
+        let next = self.moves.borrow_mut().push(input);
+        self.save();
+        self.start(next.into_iter().collect());
+    }
+
+    /// Start sending held moves again.
+    fn resume(&self) {
+        let next = self.moves.borrow_mut().resume();
+        self.save();
+        self.start(next);
+    }
+
+    fn start(&self, batch: Vec<model::QueuedMove>) {
+        if !batch.is_empty() {
+            spawn::spawn_local(self.clone().submit(batch));
+        }
+    }
+
+    /// Keep the moves which haven't synced somewhere durable and mark their cells, along with the
+    /// moves the API refused.
+    fn save(&self) {
+        let unsynced = self.moves.borrow().unsynced();
+        offline::save(self.game_id, &unsynced);
+        // Marks are sent once the cell which made the move has finished handling it
+        let marks = self.moves.borrow().marks();
+        spawn::send_async(&self.tx_marks, async move { marks });
+    }
+
+    /// Send the moves in `first` and every move queued after them to the API, one at a time, until
+    /// there are no moves left or the API can't be reached. Each response is sent into
+    /// `tx_response` after the moves are marked complete so the board shows the moves still
+    /// waiting as pending. Refused moves are kept to be retried, and because the board is shown
+    /// again without them their cells go back to the last state the API confirmed. Moves the API
+    /// can't take together are sent again one at a time.
+    async fn submit(self, first: Vec<model::QueuedMove>) {
+        let mut next = Some(first);
+        while let Some(batch) = next {
+            // Batches are never empty, the last move identifies the batch
+            let sequence = batch[batch.len() - 1].sequence;
+            let inputs: Vec<_> = batch.into_iter().map(|queued| queued.input).collect();
+            let result = self.send(&inputs).await;
+            next = match &result {
+                // Nobody is waiting on the remaining moves, any which haven't synced stay saved
+                Err(api::FetchError::Cancelled) => return,
+                // Hold on to the moves until the browser is back online
+                Err(error) if offline::is_connectivity_error(error) => {
+                    self.moves.borrow_mut().suspend(sequence);
+                    None
+                }
+                // None of the remaining moves can be applied to a finished game
+                Err(api::FetchError::Server(error))
+                    if error.kind == api::ServerErrorKind::GameComplete =>
+                {
+                    self.moves.borrow_mut().clear();
+                    None
+                }
+                // The API doesn't take moves together, the moves stay queued to be sent one at a
+                // time and nothing is shown until they have been
+                Err(api::FetchError::Server(error))
+                    if inputs.len() > 1 && (error.status == 404 || error.status == 405) =>
+                {
+                    let first = self.moves.borrow_mut().unbatch(sequence);
+                    next = first.map(|queued| vec![queued]);
+                    continue;
+                }
+                Err(error) => self
+                    .moves
+                    .borrow_mut()
+                    .reject(sequence, error.clone())
+                    .map(|queued| vec![queued]),
+                Ok(batch) => {
+                    let applied: Vec<_> = inputs
+                        .iter()
+                        .zip(batch.outcomes.iter())
+                        .filter(|(_, outcome)| **outcome == api::MoveOutcome::Applied)
+                        .map(|(input, _)| input.clone())
+                        .collect();
+                    self.logger.append(&applied, &batch.game.board);
+                    self.moves
+                        .borrow_mut()
+                        .complete_batch(sequence, &batch.outcomes)
+                        .map(|queued| vec![queued])
+                }
+            };
+            self.save();
+            let result = result.map(|batch| batch.game);
+            self.tx_response.send(&(sequence, result));
+        }
+    }
+
+    /// Send a single move on its own, or several moves in one request. A single move is always
+    /// applied when the request succeeds.
+    async fn send(
+        &self,
+        inputs: &[api::GameMoveInput],
+    ) -> Result<api::GameMoveBatch, api::FetchError> {
+        let cancel = self.cancel.clone();
+        if let [input] = inputs {
+            let game = self
+                .client
+                .patch_game(self.game_id, input.clone(), cancel)
+                .await?;
+            return Ok(api::GameMoveBatch {
+                game,
+                outcomes: vec![api::MoveOutcome::Applied],
+            });
+        }
+        self.client
+            .patch_game_batch(self.game_id, inputs.to_vec(), cancel)
+            .await
+    }
+}
+
 fn game_board(
     tx_game: &Transmitter<api::GameState>,
     tx_cells: Transmitter<model::CellInteract>,
+    moves: Rc<RefCell<model::MoveQueue>>,
+    tx_marks: &Transmitter<model::CellMarks>,
 ) -> ViewBuilder<HtmlElement> {
+    let rx_marks = tx_marks.spawn_recv();
     let rx_game = tx_game.spawn_recv();
-    let rx_cells = tx_game
+    // Cells take the board as the API sent it, so what input does and what a chord counts follow
+    // the API. Cells with moves waiting on a response are then shown as pending until the move is
+    // confirmed.
+    let (tx_board, rx_cells) = txrx();
+    let tx_optimistic = Transmitter::new();
+    let tx_pending = tx_optimistic.clone();
+    let pending_moves = moves.clone();
+    tx_game
         .spawn_recv()
-        .branch_map(|game_state| model::CellUpdate::All {
-            cells: game_state.board.clone(),
+        .respond(move |game_state: &api::GameState| {
+            tx_board.send(&model::CellUpdate::All {
+                cells: game_state.board.clone(),
+            });
+            tx_pending.send_many(&pending_moves.borrow().pending());
         });
     let rx_state =
         rx_game.branch_filter_fold(None, |current: &mut Option<api::GameState>, game_state| {
@@ -62,7 +458,14 @@ fn game_board(
     // Patch the initial board state into the game board slot
     let rx_patch_game = rx_state.branch_map(move |game_state| Patch::Replace {
         index: 0,
-        value: components::game::board(game_state.board.clone(), &tx_cells, &rx_cells),
+        value: components::game::board(
+            game_state.board.clone(),
+            &moves.borrow().pending(),
+            &tx_cells,
+            &rx_cells,
+            &tx_optimistic,
+            &rx_marks,
+        ),
     });
     builder! {
         <slot name="game-board" patch:children=rx_patch_game>
@@ -92,11 +495,11 @@ fn game_status(tx_game: &Transmitter<api::GameState>) -> ViewBuilder<HtmlElement
     let rx_game_status_view = rx_game_status.branch_map(|status| match status {
         api::GameStatus::Won => Patch::Replace {
             index: 0,
-            value: builder! { <h2>"You did the thing! 🥳"</h2> },
+            value: builder! { <h2>"You did the thing! "<span aria-hidden="true">"🥳"</span></h2> },
         },
         api::GameStatus::Lost => Patch::Replace {
             index: 0,
-            value: builder! { <h2>"BOOM 💥"</h2> },
+            value: builder! { <h2>"BOOM "<span aria-hidden="true">"💥"</span></h2> },
         },
         api::GameStatus::Open => Patch::Replace {
             index: 0,
@@ -110,12 +513,190 @@ fn game_status(tx_game: &Transmitter<api::GameState>) -> ViewBuilder<HtmlElement
     }
 }
 
-impl<T> From<T> for api::GameMoveInput
-where
-    T: AsRef<model::CellInteract>,
-{
-    fn from(interaction: T) -> Self {
-        let interaction = interaction.as_ref();
+/// Show how many mines the game has and how many cells are flagged. Nothing is shown until the
+/// number of mines is known.
+#[allow(unused_braces)]
+fn game_mines(tx_game: &Transmitter<api::GameState>) -> ViewBuilder<HtmlElement> {
+    let rx_mines = Receiver::new();
+    // Only send an update in to the `rx_mines` if the count shown has changed
+    tx_game.wire_filter_fold(&rx_mines, None, |shown, game_state| {
+        let next = game_state.mines.map(|mines| {
+            let flags = game_state
+                .board
+                .iter()
+                .flatten()
+                .filter(|value| **value == api::BoardValue::Flag)
+                .count();
+            (mines, flags)
+        });
+        if *shown == next {
+            None
+        } else {
+            *shown = next;
+            Some(next)
+        }
+    });
+    let rx_mines_view = rx_mines.branch_map(|shown| match shown {
+        Some((mines, flags)) => {
+            let text = format!("{} mines, {} flagged", mines, flags);
+            Patch::Replace {
+                index: 0,
+                value: builder! { <span class="game-mines">{text}</span> },
+            }
+        }
+        None => Patch::Replace {
+            index: 0,
+            value: builder! { <span></span> },
+        },
+    });
+    builder! {
+        <slot name="game-mines" patch:children=rx_mines_view>
+            <span></span>
+        </slot>
+    }
+}
+
+/// Tell screen readers what each change to the game did, including when it is won or lost. Nothing
+/// is announced for the first state of a game.
+#[allow(unused_braces)]
+fn game_announcements(tx_game: &Transmitter<api::GameState>) -> ViewBuilder<HtmlElement> {
+    let rx_announcement = Receiver::new();
+    tx_game.wire_filter_fold(
+        &rx_announcement,
+        None,
+        |shown: &mut Option<api::GameState>, game_state| {
+            let announcement = match shown {
+                Some(previous) if previous.id == game_state.id => {
+                    model::announce(previous, game_state)
+                }
+                _ => None,
+            };
+            shown.replace(game_state.clone());
+            announcement
+        },
+    );
+    builder! {
+        <p class="announcement visually-hidden" role="status" aria-live="polite">
+            {("", rx_announcement)}
+        </p>
+    }
+}
+
+/// Show the seed of the game with a button to play the same board again, which sends the input
+/// for the new game into `tx_replay`. Nothing is shown until the seed of the game is known.
+#[allow(unused_braces)]
+fn game_seed(
+    tx_game: &Transmitter<api::GameState>,
+    tx_replay: &Transmitter<api::GameCreateInput>,
+) -> ViewBuilder<HtmlElement> {
+    let rx_replay = Receiver::new();
+    // Only send an update in to the `rx_replay` if the board to play again has changed
+    tx_game.wire_filter_fold(&rx_replay, None, |replay, game_state| {
+        let next = game_state.replay_input();
+        if *replay == next {
+            None
+        } else {
+            *replay = next;
+            Some(replay.clone())
+        }
+    });
+    let tx_replay = tx_replay.clone();
+    let rx_replay_view = rx_replay.branch_map(move |replay| match replay {
+        Some(input) => {
+            let seed = format!("Seed {}", input.seed.unwrap_or_default());
+            let input = input.clone();
+            let tx_click = tx_replay.contra_map(move |_: &Event| input.clone());
+            Patch::Replace {
+                index: 0,
+                value: builder! {
+                    <span class="game-seed">
+                        <span>{seed}</span>
+                        <button on:click=tx_click>"Play this board again"</button>
+                    </span>
+                },
+            }
+        }
+        None => Patch::Replace {
+            index: 0,
+            value: builder! { <span></span> },
+        },
+    });
+    builder! {
+        <slot name="game-seed" patch:children=rx_replay_view>
+            <span></span>
+        </slot>
+    }
+}
+
+/// Create a new game through `client` from each input sent into the returned `Transmitter`,
+/// sending the new game into `dispatch`. Failures are sent into `tx_api` to be shown with the game.
+fn replay_game(
+    dispatch: Transmitter<Route>,
+    client: api::Client,
+    cancel: api::CancelSignal,
+    tx_api: Transmitter<Result<api::GameState, api::FetchError>>,
+) -> Transmitter<api::GameCreateInput> {
+    let (tx_replay, rx_replay) = txrx();
+    rx_replay.respond(move |input: &api::GameCreateInput| {
+        let create_game = client.create_game(input.clone(), cancel.clone());
+        let dispatch = dispatch.clone();
+        let tx_api = tx_api.clone();
+        spawn::spawn_local(async move {
+            match create_game.await {
+                Ok(created) => dispatch.send(&Route::Game {
+                    game_id: created.id,
+                }),
+                Err(api::FetchError::Cancelled) => (),
+                Err(error) => tx_api.send(&Err(error)),
+            }
+        });
+    });
+    tx_replay
+}
+
+/// Show why the most recent request for the game failed. The message is cleared as soon as a
+/// request succeeds. Cancelled requests are ignored because nobody is waiting on them.
+#[allow(unused_braces)]
+fn game_error(
+    tx_api: &Transmitter<Result<api::GameState, api::FetchError>>,
+) -> ViewBuilder<HtmlElement> {
+    let rx_error = tx_api
+        .spawn_recv()
+        .branch_filter_map(|result| match result {
+            Ok(_) => Some(String::new()),
+            Err(api::FetchError::Cancelled) => None,
+            Err(error) => Some(error.to_string()),
+        });
+    builder! {
+        <p class="error">{("", rx_error)}</p>
+    }
+}
+
+/// Show how many moves are saved because they couldn't reach the API, their cells are marked on
+/// the board.
+#[allow(unused_braces)]
+fn game_unsynced(tx_marks: &Transmitter<model::CellMarks>) -> ViewBuilder<HtmlElement> {
+    let rx_text =
+        tx_marks
+            .spawn_recv()
+            .branch_map(|marks| match marks.count(&model::CellMark::Unsynced) {
+                0 => String::new(),
+                1 => String::from("1 move waiting to sync"),
+                count => format!("{} moves waiting to sync", count),
+            });
+    builder! {
+        <p class="unsynced">{("", rx_text)}</p>
+    }
+}
+
+impl From<model::CellInteract> for api::GameMoveInput {
+    fn from(interaction: model::CellInteract) -> Self {
+        Self::from(&interaction)
+    }
+}
+
+impl From<&model::CellInteract> for api::GameMoveInput {
+    fn from(interaction: &model::CellInteract) -> Self {
         Self {
             column: interaction.column,
             row: interaction.row,
@@ -132,16 +713,16 @@ where
 mod game_board {
     use super::*;
 
-    /// Create a `Vec` of owned `String` values.
-    macro_rules! vec_of_strings {
-        ($($x:expr),*) => (vec![$($x.into()),*]);
+    /// Create a `Vec` of `BoardValue` parsed from `&str` values.
+    macro_rules! vec_of_values {
+        ($($x:expr),*) => (vec![$($x.parse::<model::BoardValue>().unwrap()),*]);
     }
 
     #[test]
     fn starts_empty() {
         let tx_game = Transmitter::new();
         let tx_cells = Transmitter::new();
-        let builder = game_board(&tx_game, tx_cells);
+        let builder = game_board(&tx_game, tx_cells, Rc::default(), &Transmitter::new());
         let ssr = View::from(builder);
         assert_eq!(
             ssr.html_string(),
@@ -151,10 +732,9 @@ mod game_board {
 
     #[test]
     fn updates_board_with_cells() {
-        use std::{cell::RefCell, rc::Rc};
         let tx_game = Transmitter::new();
         let tx_cells = Transmitter::new();
-        let builder = game_board(&tx_game, tx_cells);
+        let builder = game_board(&tx_game, tx_cells, Rc::default(), &Transmitter::new());
         // Set up the ability to look at the most recently received patch
         let patch_receiver = builder.patches.first().unwrap();
         let respond_count = Rc::new(RefCell::new(0));
@@ -170,14 +750,52 @@ mod game_board {
         // Send a game state
         tx_game.send(&api::GameState {
             id: uuid::Uuid::new_v4(),
-            board: vec![vec_of_strings![" ", "1", "F", "M"]],
+            board: vec![vec_of_values![" ", "1", "F", "M"]],
             status: api::GameStatus::Lost,
+            mines: None,
+            seed: None,
         });
         // Test the number of patch receivers
         assert_eq!(builder.patches.len(), 1);
         // Test the number of updates received matches the number sent
         assert_eq!(*respond_count.borrow(), 1);
     }
+
+    #[test]
+    fn shows_waiting_moves_over_board() {
+        let tx_game = Transmitter::new();
+        let moves = Rc::new(RefCell::new(model::MoveQueue::default()));
+        moves.borrow_mut().push(api::GameMoveInput {
+            row: 0,
+            column: 1,
+            move_type: api::GameMoveType::RemoveFlag,
+        });
+        let builder = game_board(
+            &tx_game,
+            Transmitter::new(),
+            moves.clone(),
+            &Transmitter::new(),
+        );
+        let ssr = View::from(builder);
+        let game_id = uuid::Uuid::new_v4();
+        let game = |board| api::GameState {
+            id: game_id,
+            board,
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        };
+        tx_game.send(&game(vec![vec_of_values!["1", "F"]]));
+        assert!(ssr
+            .html_string()
+            .contains("aria-label=\"row 1 column 2, opening\" tabindex=\"-1\">*</td>"));
+        // Once the move is answered the cell shows what the API sent
+        moves.borrow_mut().clear();
+        tx_game.send(&game(vec![vec_of_values!["1", " "]]));
+        assert!(ssr
+            .html_string()
+            .contains("aria-label=\"row 1 column 2, unopened\" tabindex=\"-1\"></td>"));
+    }
 }
 
 #[cfg(test)]
@@ -204,6 +822,8 @@ mod game_status {
             id: uuid::Uuid::new_v4(),
             board: Vec::new(),
             status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
         });
         assert_eq!(
             ssr.html_string(),
@@ -220,10 +840,12 @@ mod game_status {
             id: uuid::Uuid::new_v4(),
             board: Vec::new(),
             status: api::GameStatus::Lost,
+            mines: None,
+            seed: None,
         });
         assert_eq!(
             ssr.html_string(),
-            String::from("<slot name=\"game-status\"><h2>BOOM 💥</h2></slot>")
+            String::from("<slot name=\"game-status\"><h2>BOOM <span aria-hidden=\"true\">💥</span></h2></slot>")
         );
     }
 
@@ -236,10 +858,248 @@ mod game_status {
             id: uuid::Uuid::new_v4(),
             board: Vec::new(),
             status: api::GameStatus::Won,
+            mines: None,
+            seed: None,
         });
         assert_eq!(
             ssr.html_string(),
-            String::from("<slot name=\"game-status\"><h2>You did the thing! 🥳</h2></slot>")
+            String::from("<slot name=\"game-status\"><h2>You did the thing! <span aria-hidden=\"true\">🥳</span></h2></slot>")
+        );
+    }
+}
+
+#[cfg(test)]
+mod game_announcements {
+    use super::*;
+
+    fn game_state(
+        id: api::GameId,
+        cells: Vec<model::BoardValue>,
+        status: api::GameStatus,
+    ) -> api::GameState {
+        api::GameState {
+            id,
+            board: vec![cells],
+            status,
+            mines: None,
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn announces_moves_and_result() {
+        let tx_game = Transmitter::new();
+        let ssr = View::from(game_announcements(&tx_game));
+        let empty =
+            "<p class=\"announcement visually-hidden\" role=\"status\" aria-live=\"polite\"></p>";
+        assert_eq!(ssr.html_string(), empty);
+        let id = uuid::Uuid::new_v4();
+        let closed = vec![model::BoardValue::Closed, model::BoardValue::Closed];
+        tx_game.send(&game_state(id, closed, api::GameStatus::Open));
+        assert_eq!(ssr.html_string(), empty);
+        let flagged = vec![model::BoardValue::Flag, model::BoardValue::Closed];
+        tx_game.send(&game_state(id, flagged, api::GameStatus::Open));
+        assert_eq!(
+            ssr.html_string(),
+            "<p class=\"announcement visually-hidden\" role=\"status\" aria-live=\"polite\">\
+             Flagged row 1 column 1.</p>"
+        );
+        let lost = vec![model::BoardValue::Flag, model::BoardValue::Mine];
+        tx_game.send(&game_state(id, lost, api::GameStatus::Lost));
+        assert_eq!(
+            ssr.html_string(),
+            "<p class=\"announcement visually-hidden\" role=\"status\" aria-live=\"polite\">\
+             Opened row 1 column 2, mine. You hit a mine, game over.</p>"
+        );
+    }
+}
+
+#[cfg(test)]
+mod game_mines {
+    use super::*;
+
+    fn game(mines: Option<usize>) -> api::GameState {
+        api::GameState {
+            id: uuid::Uuid::new_v4(),
+            board: vec![vec!["F".parse().unwrap(), " ".parse().unwrap()]],
+            status: api::GameStatus::Open,
+            mines,
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn stays_empty_without_mines() {
+        let tx = Transmitter::new();
+        let ssr = View::from(game_mines(&tx));
+        tx.send(&game(None));
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<slot name=\"game-mines\"><span></span></slot>")
+        );
+    }
+
+    #[test]
+    fn shows_mines_and_flags() {
+        let tx = Transmitter::new();
+        let ssr = View::from(game_mines(&tx));
+        tx.send(&game(Some(99)));
+        assert_eq!(
+            ssr.html_string(),
+            String::from(
+                "<slot name=\"game-mines\"><span class=\"game-mines\">99 mines, 1 flagged</span></slot>"
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod game_seed {
+    use super::*;
+
+    fn seeded_game(seed: Option<api::GameSeed>) -> api::GameState {
+        api::GameState {
+            id: uuid::Uuid::new_v4(),
+            board: vec![vec![" ".parse().unwrap(); 3]; 2],
+            status: api::GameStatus::Lost,
+            mines: Some(1),
+            seed,
+        }
+    }
+
+    #[test]
+    fn stays_empty_without_seed() {
+        let tx = Transmitter::new();
+        let ssr = View::from(game_seed(&tx, &Transmitter::new()));
+        tx.send(&seeded_game(None));
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<slot name=\"game-seed\"><span></span></slot>")
+        );
+    }
+
+    #[test]
+    fn shows_seed() {
+        let tx = Transmitter::new();
+        let ssr = View::from(game_seed(&tx, &Transmitter::new()));
+        tx.send(&seeded_game(Some(1234)));
+        assert_eq!(
+            ssr.html_string(),
+            String::from(
+                "<slot name=\"game-seed\"><span class=\"game-seed\"><span>Seed 1234</span> \
+                 <button>Play this board again</button></span></slot>"
+            )
+        );
+    }
+
+    #[test]
+    fn replays_board_in_new_game() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_create_game(Ok(api::GameCreated {
+            id: game_id,
+            seed: Some(1234),
+        }));
+        let (dispatch, rx_route) = txrx();
+        let routes = Rc::new(RefCell::new(vec![]));
+        let remote_routes = routes.clone();
+        rx_route.respond(move |route: &Route| remote_routes.borrow_mut().push(*route));
+        let tx_replay = replay_game(
+            dispatch,
+            client.clone(),
+            api::CancelSignal::none(),
+            Transmitter::new(),
+        );
+        let input = seeded_game(Some(1234)).replay_input().unwrap();
+        tx_replay.send(&input);
+        spawn::run_pending();
+        assert_eq!(client.calls(), vec![api::ApiCall::CreateGame(input)]);
+        assert_eq!(*routes.borrow(), vec![Route::Game { game_id }]);
+    }
+}
+
+#[cfg(test)]
+mod game_error {
+    use super::*;
+
+    #[test]
+    fn starts_empty() {
+        let tx = Transmitter::new();
+        let builder = game_error(&tx);
+        let ssr = View::from(builder);
+        assert_eq!(ssr.html_string(), String::from("<p class=\"error\"></p>"));
+    }
+
+    #[test]
+    fn shows_server_error() {
+        let tx = Transmitter::new();
+        let builder = game_error(&tx);
+        let ssr = View::from(builder);
+        tx.send(&Err(api::FetchError::Server(
+            api::ServerError::from_response(
+                409,
+                r#"{"statusCode":409,"message":"Game is already WON."}"#,
+            ),
+        )));
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<p class=\"error\">This game is already finished</p>")
+        );
+    }
+
+    #[test]
+    fn ignores_cancelled() {
+        let tx = Transmitter::new();
+        let builder = game_error(&tx);
+        let ssr = View::from(builder);
+        tx.send(&Err(api::FetchError::Timeout));
+        tx.send(&Err(api::FetchError::Cancelled));
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<p class=\"error\">The request timed out</p>")
+        );
+    }
+
+    #[test]
+    fn clears_on_success() {
+        let tx = Transmitter::new();
+        let builder = game_error(&tx);
+        let ssr = View::from(builder);
+        tx.send(&Err(api::FetchError::Timeout));
+        tx.send(&Ok(api::GameState {
+            id: uuid::Uuid::new_v4(),
+            board: Vec::new(),
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        }));
+        assert_eq!(ssr.html_string(), String::from("<p class=\"error\"></p>"));
+    }
+}
+
+#[cfg(test)]
+mod game_unsynced {
+    use super::*;
+
+    #[test]
+    fn counts_waiting_moves() {
+        let tx = Transmitter::new();
+        let builder = game_unsynced(&tx);
+        let ssr = View::from(builder);
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<p class=\"unsynced\"></p>")
+        );
+        let unsynced = |row| (row, 0, model::CellMark::Unsynced);
+        tx.send(&model::CellMarks::new(vec![unsynced(0), unsynced(1)]));
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<p class=\"unsynced\">2 moves waiting to sync</p>")
+        );
+        tx.send(&model::CellMarks::default());
+        assert_eq!(
+            ssr.html_string(),
+            String::from("<p class=\"unsynced\"></p>")
         );
     }
 }
@@ -274,3 +1134,438 @@ mod cell_interact {
         assert_eq!(api::GameMoveType::Flag, input.move_type);
     }
 }
+
+#[cfg(test)]
+mod game_route {
+    use super::*;
+
+    fn move_logger(game_id: api::GameId, client: api::Client) -> MoveLogger {
+        MoveLogger::new(
+            game_id,
+            client,
+            api::CancelSignal::none(),
+            &Transmitter::new(),
+            Transmitter::new(),
+        )
+    }
+
+    #[test]
+    fn renders_game_from_client() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game(Ok(api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap()]],
+            status: api::GameStatus::Lost,
+            mines: None,
+            seed: None,
+        }));
+        let mut builder = super::game(
+            Transmitter::new(),
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        // Listening on `window` isn't possible outside of the browser
+        builder.events.retain(|cmd| cmd.name != "online");
+        let ssr = View::from(builder);
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![
+                api::ApiCall::GetGame(game_id),
+                api::ApiCall::GetGameMoves(game_id)
+            ]
+        );
+        assert!(ssr
+            .html_string()
+            .contains("<h2>BOOM <span aria-hidden=\"true\">💥</span></h2>"));
+    }
+
+    #[test]
+    fn renders_cached_game_while_fetching() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.cache_game(api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap()]],
+            status: api::GameStatus::Lost,
+            mines: None,
+            seed: None,
+        });
+        let mut builder = super::game(
+            Transmitter::new(),
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        // Listening on `window` isn't possible outside of the browser
+        builder.events.retain(|cmd| cmd.name != "online");
+        let ssr = View::from(builder);
+        // No response is scripted so the request fails as though the API can't be reached
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![
+                api::ApiCall::GetGame(game_id),
+                api::ApiCall::GetGameMoves(game_id)
+            ]
+        );
+        assert!(ssr
+            .html_string()
+            .contains("<h2>BOOM <span aria-hidden=\"true\">💥</span></h2>"));
+    }
+
+    #[test]
+    fn prefers_fetched_game_over_cached() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.cache_game(api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap()]],
+            status: api::GameStatus::Lost,
+            mines: None,
+            seed: None,
+        });
+        client.respond_game(Ok(api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap()]],
+            status: api::GameStatus::Won,
+            mines: None,
+            seed: None,
+        }));
+        let mut builder = super::game(
+            Transmitter::new(),
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        builder.events.retain(|cmd| cmd.name != "online");
+        let ssr = View::from(builder);
+        spawn::run_pending();
+        assert!(ssr
+            .html_string()
+            .contains("<h2>You did the thing! <span aria-hidden=\"true\">🥳</span></h2>"));
+    }
+
+    #[test]
+    fn lists_moves_of_game() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game(Ok(api::GameState {
+            id: game_id,
+            board: vec![vec!["3".parse().unwrap()]],
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        }));
+        client.respond_game_moves(Ok(vec![api::GameMove {
+            column: 0,
+            row: 0,
+            move_type: api::GameMoveType::Open,
+            created_at: String::from("2021-02-14T18:30:05.123Z"),
+        }]));
+        let mut builder = super::game(
+            Transmitter::new(),
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        builder.events.retain(|cmd| cmd.name != "online");
+        let ssr = View::from(builder);
+        spawn::run_pending();
+        assert!(ssr
+            .html_string()
+            .contains("Opened row 1, column 1, 3 mines nearby</li>"));
+    }
+
+    #[test]
+    fn renders_pushed_game() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game(Ok(api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap()]],
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        }));
+        let mut builder = super::game(
+            Transmitter::new(),
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        builder.events.retain(|cmd| cmd.name != "online");
+        let ssr = View::from(builder);
+        spawn::run_pending();
+        let frame = api::GameFrame {
+            version: 1,
+            game: api::GameState {
+                id: game_id,
+                board: vec![vec![" ".parse().unwrap()]],
+                status: api::GameStatus::Lost,
+                mines: None,
+                seed: None,
+            },
+        };
+        client.socket(0).send(&api::SocketEvent::Frame(
+            serde_json::to_string(&frame).unwrap(),
+        ));
+        assert!(ssr
+            .html_string()
+            .contains("<h2>BOOM <span aria-hidden=\"true\">💥</span></h2>"));
+    }
+
+    #[test]
+    fn reads_moves_again_when_changed_elsewhere() {
+        let game_id = uuid::Uuid::new_v4();
+        let game = |board: &str| api::GameState {
+            id: game_id,
+            board: vec![vec![board.parse().unwrap()]],
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        };
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game(Ok(game(" ")));
+        let mut builder = super::game(
+            Transmitter::new(),
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        builder.events.retain(|cmd| cmd.name != "online");
+        let _ssr = View::from(builder);
+        spawn::run_pending();
+        let push = |version, board| {
+            let frame = api::GameFrame {
+                version,
+                game: game(board),
+            };
+            client.socket(0).send(&api::SocketEvent::Frame(
+                serde_json::to_string(&frame).unwrap(),
+            ));
+            spawn::run_pending();
+        };
+        // The same board is already described by the moves which were read
+        push(1, " ");
+        push(2, "F");
+        assert_eq!(
+            client.calls(),
+            vec![
+                api::ApiCall::GetGame(game_id),
+                api::ApiCall::GetGameMoves(game_id),
+                api::ApiCall::GetGameMoves(game_id)
+            ]
+        );
+    }
+
+    #[test]
+    fn replays_held_moves_together() {
+        let game_id = uuid::Uuid::new_v4();
+        let open = |column| api::GameMoveInput {
+            column,
+            row: 0,
+            move_type: api::GameMoveType::Open,
+        };
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_batch(Ok(api::GameMoveBatch {
+            game: api::GameState {
+                id: game_id,
+                board: vec![vec!["M".parse().unwrap(), " ".parse().unwrap()]],
+                status: api::GameStatus::Lost,
+                mines: None,
+                seed: None,
+            },
+            outcomes: vec![api::MoveOutcome::Applied, api::MoveOutcome::Rejected],
+        }));
+        let moves = Rc::new(RefCell::new(model::MoveQueue::default()));
+        moves.borrow_mut().restore(vec![open(0), open(1)]);
+        let (tx_response, rx_response) = txrx();
+        let responses = Rc::new(RefCell::new(vec![]));
+        let remote_responses = responses.clone();
+        rx_response.respond(move |response: &model::SequencedResponse| {
+            remote_responses.borrow_mut().push(response.clone())
+        });
+        let (tx_log, rx_log) = txrx();
+        let log = Rc::new(RefCell::new(components::MoveLog::default()));
+        let remote_log = log.clone();
+        rx_log.respond(move |sent: &components::MoveLog| *remote_log.borrow_mut() = sent.clone());
+        let logger = MoveLogger::new(
+            game_id,
+            client.clone(),
+            api::CancelSignal::none(),
+            &Transmitter::new(),
+            tx_log,
+        );
+        let submitter = MoveSubmitter {
+            client: client.clone(),
+            game_id,
+            moves: moves.clone(),
+            logger,
+            tx_response,
+            tx_marks: Transmitter::new(),
+            cancel: api::CancelSignal::none(),
+        };
+        submitter.resume();
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![api::ApiCall::PatchGameBatch(
+                game_id,
+                vec![open(0), open(1)]
+            )]
+        );
+        let responses = responses.borrow();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].0, 2);
+        assert_eq!(
+            responses[0].1.as_ref().unwrap().status,
+            api::GameStatus::Lost
+        );
+        assert!(moves.borrow().unsynced().is_empty());
+        // The move which wasn't applied is offered to be sent again
+        assert_eq!(
+            moves.borrow().rejected(),
+            &[model::RejectedMove {
+                input: open(1),
+                reason: model::RejectReason::NotApplied,
+            }]
+        );
+        // Only the applied move is added to the log, without asking the API for every move
+        let log = log.borrow();
+        assert_eq!(log.moves.len(), 1);
+        assert_eq!((log.moves[0].row, log.moves[0].column), (0, 0));
+    }
+
+    #[test]
+    fn sends_held_moves_one_at_a_time_without_batches() {
+        let game_id = uuid::Uuid::new_v4();
+        let open = |column| api::GameMoveInput {
+            column,
+            row: 0,
+            move_type: api::GameMoveType::Open,
+        };
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_batch(Err(api::FetchError::Server(
+            api::ServerError::from_response(405, ""),
+        )));
+        client.respond_game(Ok(api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap(), "1".parse().unwrap()]],
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        }));
+        // The second move can't reach the API
+        let moves = Rc::new(RefCell::new(model::MoveQueue::default()));
+        moves.borrow_mut().restore(vec![open(0), open(1)]);
+        let submitter = MoveSubmitter {
+            client: client.clone(),
+            game_id,
+            moves: moves.clone(),
+            logger: move_logger(game_id, client.clone()),
+            tx_response: Transmitter::new(),
+            tx_marks: Transmitter::new(),
+            cancel: api::CancelSignal::none(),
+        };
+        submitter.resume();
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![
+                api::ApiCall::PatchGameBatch(game_id, vec![open(0), open(1)]),
+                api::ApiCall::PatchGame(game_id, open(0)),
+                api::ApiCall::PatchGame(game_id, open(1)),
+            ]
+        );
+        assert_eq!(moves.borrow().unsynced(), vec![open(1)]);
+        assert!(moves.borrow().rejected().is_empty());
+    }
+
+    #[test]
+    fn marks_held_moves() {
+        let game_id = uuid::Uuid::new_v4();
+        let open = api::GameMoveInput {
+            column: 1,
+            row: 0,
+            move_type: api::GameMoveType::Open,
+        };
+        // No response is scripted so the move can't reach the API
+        let client = Rc::new(api::MemoryApi::default());
+        let (tx_marks, rx_marks) = txrx();
+        let marks = Rc::new(RefCell::new(model::CellMarks::default()));
+        let remote_marks = marks.clone();
+        rx_marks.respond(move |sent: &model::CellMarks| *remote_marks.borrow_mut() = sent.clone());
+        let submitter = MoveSubmitter {
+            client: client.clone(),
+            game_id,
+            moves: Rc::default(),
+            logger: move_logger(game_id, client),
+            tx_response: Transmitter::new(),
+            tx_marks,
+            cancel: api::CancelSignal::none(),
+        };
+        submitter.push(open);
+        spawn::run_pending();
+        assert_eq!(marks.borrow().get(0, 1), Some(&model::CellMark::Unsynced));
+        assert_eq!(marks.borrow().get(0, 0), None);
+    }
+
+    #[test]
+    fn keeps_rejected_move_to_retry() {
+        let game_id = uuid::Uuid::new_v4();
+        let open = api::GameMoveInput {
+            column: 0,
+            row: 0,
+            move_type: api::GameMoveType::Open,
+        };
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game(Err(api::FetchError::ParseError));
+        let (tx_marks, rx_marks) = txrx();
+        let marks = Rc::new(RefCell::new(model::CellMarks::default()));
+        let remote_marks = marks.clone();
+        rx_marks.respond(move |sent: &model::CellMarks| *remote_marks.borrow_mut() = sent.clone());
+        let moves = Rc::new(RefCell::new(model::MoveQueue::default()));
+        let submitter = MoveSubmitter {
+            client: client.clone(),
+            game_id,
+            moves: moves.clone(),
+            logger: move_logger(game_id, client.clone()),
+            tx_response: Transmitter::new(),
+            tx_marks,
+            cancel: api::CancelSignal::none(),
+        };
+        submitter.push(open.clone());
+        spawn::run_pending();
+        // The cell is marked with the refused move
+        assert_eq!(
+            marks.borrow().get(0, 0),
+            Some(&model::CellMark::Rejected(model::RejectedMove {
+                input: open.clone(),
+                reason: model::RejectReason::Failed(api::FetchError::ParseError),
+            }))
+        );
+        // The cell isn't left pending
+        assert!(moves.borrow().pending().is_empty());
+        client.respond_game(Ok(api::GameState {
+            id: game_id,
+            board: vec![vec!["1".parse().unwrap()]],
+            status: api::GameStatus::Won,
+            mines: None,
+            seed: None,
+        }));
+        // Retrying from the cell makes the same move again
+        submitter.push(open.clone());
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![
+                api::ApiCall::PatchGame(game_id, open.clone()),
+                api::ApiCall::PatchGame(game_id, open)
+            ]
+        );
+        assert_eq!(*marks.borrow(), model::CellMarks::default());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{api, components, model, spawn, Route};
+use mogwai::prelude::*;
+
+/// Controls for abandoning or deleting the game identified by `game_id`, tracking the game through
+/// `tx_game`. Abandoning shows the game as lost straight away by sending it into `tx_api`, putting
+/// back the game as it was if the request fails. Deleting returns to the list of games once the
+/// game is gone. Failures are sent into `tx_api` to be shown with the game.
+pub fn game_actions(
+    dispatch: Transmitter<Route>,
+    game_id: api::GameId,
+    client: api::Client,
+    cancel: api::CancelSignal,
+    tx_api: Transmitter<Result<api::GameState, api::FetchError>>,
+    tx_game: &Transmitter<api::GameState>,
+) -> ViewBuilder<HtmlElement> {
+    let component = GameActions {
+        cancel,
+        client,
+        confirming: None,
+        dispatch,
+        game: None,
+        game_id,
+        rx_game: tx_game.spawn_recv(),
+        tx_api,
+    };
+    Gizmo::from(component).view_builder()
+}
+
+struct GameActions {
+    cancel: api::CancelSignal,
+    client: api::Client,
+    /// Action waiting on the player to confirm it
+    confirming: Option<model::GameAction>,
+    dispatch: Transmitter<Route>,
+    /// Most recent state of the game, put back if abandoning it fails
+    game: Option<api::GameState>,
+    game_id: api::GameId,
+    rx_game: Receiver<api::GameState>,
+    tx_api: Transmitter<Result<api::GameState, api::FetchError>>,
+}
+
+#[derive(Clone, Debug)]
+enum GameActionsModel {
+    Event(model::GameActionEvent),
+    Game(api::GameState),
+}
+
+#[derive(Clone, Debug)]
+struct GameActionsView {
+    status: api::GameStatus,
+    confirming: Option<model::GameAction>,
+}
+
+impl GameActions {
+    fn status(&self) -> api::GameStatus {
+        self.game
+            .as_ref()
+            .map(|game| game.status)
+            .unwrap_or_default()
+    }
+
+    fn view_msg(&self) -> GameActionsView {
+        GameActionsView {
+            status: self.status(),
+            confirming: self.confirming,
+        }
+    }
+
+    fn perform(&self, action: model::GameAction) {
+        let tx_api = self.tx_api.clone();
+        match action {
+            model::GameAction::Abandon => {
+                let previous = self.game.clone();
+                if let Some(game) = &previous {
+                    let mut abandoned = game.clone();
+                    abandoned.status = api::GameStatus::Lost;
+                    tx_api.send(&Ok(abandoned));
+                }
+                let abandon_game = self.client.abandon_game(self.game_id, self.cancel.clone());
+                spawn::spawn_local(async move {
+                    match abandon_game.await {
+                        Ok(game) => tx_api.send(&Ok(game)),
+                        Err(api::FetchError::Cancelled) => (),
+                        Err(error) => {
+                            if let Some(previous) = previous {
+                                tx_api.send(&Ok(previous));
+                            }
+                            tx_api.send(&Err(error));
+                        }
+                    }
+                });
+            }
+            model::GameAction::Delete => {
+                let delete_game = self.client.delete_game(self.game_id, self.cancel.clone());
+                let dispatch = self.dispatch.clone();
+                spawn::spawn_local(async move {
+                    match delete_game.await {
+                        Ok(()) => dispatch.send(&Route::GameList),
+                        Err(api::FetchError::Cancelled) => (),
+                        Err(error) => tx_api.send(&Err(error)),
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl Component for GameActions {
+    type ModelMsg = GameActionsModel;
+    type ViewMsg = GameActionsView;
+    type DomNode = HtmlElement;
+
+    fn bind(&self, in_sub: &Subscriber<Self::ModelMsg>, _out_sub: &Subscriber<Self::ViewMsg>) {
+        in_sub.subscribe_map(&self.rx_game, |game: &api::GameState| {
+            GameActionsModel::Game(game.clone())
+        });
+    }
+
+    fn update(
+        &mut self,
+        msg: &GameActionsModel,
+        tx: &Transmitter<GameActionsView>,
+        _sub: &Subscriber<GameActionsModel>,
+    ) {
+        match msg {
+            GameActionsModel::Event(model::GameActionEvent::Ask(action)) => {
+                self.confirming = Some(*action);
+            }
+            GameActionsModel::Event(model::GameActionEvent::Dismiss) => self.confirming = None,
+            GameActionsModel::Event(model::GameActionEvent::Confirm) => {
+                if let Some(action) = self.confirming.take() {
+                    self.perform(action);
+                }
+            }
+            GameActionsModel::Game(game) => {
+                let status = self.status();
+                self.game = Some(game.clone());
+                // Nothing to show unless the actions available changed
+                if status == game.status {
+                    return;
+                }
+            }
+        }
+        tx.send(&self.view_msg());
+    }
+
+    #[allow(unused_braces)]
+    fn view(
+        &self,
+        tx: &Transmitter<GameActionsModel>,
+        rx: &Receiver<GameActionsView>,
+    ) -> ViewBuilder<HtmlElement> {
+        let tx_event =
+            tx.contra_map(|event: &model::GameActionEvent| GameActionsModel::Event(*event));
+        let contents = components::game_actions(self.status(), self.confirming, &tx_event);
+        let rx_patch = rx.branch_map(move |msg| Patch::Replace {
+            index: 0,
+            value: components::game_actions(msg.status, msg.confirming, &tx_event),
+        });
+        builder! {
+            <slot name="game-actions" patch:children=rx_patch>
+                {contents}
+            </slot>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Everything sent into a `Transmitter`, in the order it was sent.
+    type Recorded<T> = Rc<RefCell<Vec<T>>>;
+
+    fn open_game(game_id: api::GameId) -> api::GameState {
+        api::GameState {
+            id: game_id,
+            board: vec![vec![" ".parse().unwrap()]],
+            status: api::GameStatus::Open,
+            mines: None,
+            seed: None,
+        }
+    }
+
+    /// Start the controls for an open game, returning them along with everything sent into
+    /// `tx_api` and `dispatch`.
+    fn start(
+        client: Rc<api::MemoryApi>,
+        game_id: api::GameId,
+    ) -> (
+        Gizmo<GameActions>,
+        Recorded<Result<api::GameState, api::FetchError>>,
+        Recorded<Route>,
+    ) {
+        let (tx_api, rx_api) = txrx();
+        let responses = Rc::new(RefCell::new(vec![]));
+        let remote_responses = responses.clone();
+        rx_api.respond(move |response: &Result<api::GameState, api::FetchError>| {
+            remote_responses.borrow_mut().push(response.clone())
+        });
+        let (dispatch, rx_route) = txrx();
+        let routes = Rc::new(RefCell::new(vec![]));
+        let remote_routes = routes.clone();
+        rx_route.respond(move |route: &Route| remote_routes.borrow_mut().push(*route));
+        let tx_game = Transmitter::new();
+        let component = Gizmo::from(GameActions {
+            cancel: api::CancelSignal::none(),
+            client,
+            confirming: None,
+            dispatch,
+            game: None,
+            game_id,
+            rx_game: tx_game.spawn_recv(),
+            tx_api,
+        });
+        tx_game.send(&open_game(game_id));
+        (component, responses, routes)
+    }
+
+    #[test]
+    fn puts_game_back_when_abandon_fails() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        let (component, responses, _) = start(client.clone(), game_id);
+        let ssr = View::from(component.view_builder());
+        component.send(&GameActionsModel::Event(model::GameActionEvent::Ask(
+            model::GameAction::Abandon,
+        )));
+        assert!(ssr.html_string().contains("Abandon this game?"));
+        component.send(&GameActionsModel::Event(model::GameActionEvent::Confirm));
+        spawn::run_pending();
+        assert_eq!(client.calls(), vec![api::ApiCall::AbandonGame(game_id)]);
+        let statuses: Vec<_> = responses
+            .borrow()
+            .iter()
+            .map(|response| response.clone().map(|game| game.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Ok(api::GameStatus::Lost),
+                Ok(api::GameStatus::Open),
+                Err(api::FetchError::FetchError)
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_deleted_game() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_delete_game(Ok(()));
+        let (component, responses, routes) = start(client.clone(), game_id);
+        component.send(&GameActionsModel::Event(model::GameActionEvent::Ask(
+            model::GameAction::Delete,
+        )));
+        component.send(&GameActionsModel::Event(model::GameActionEvent::Confirm));
+        spawn::run_pending();
+        assert_eq!(client.calls(), vec![api::ApiCall::DeleteGame(game_id)]);
+        assert!(responses.borrow().is_empty());
+        assert_eq!(*routes.borrow(), vec![Route::GameList]);
+    }
+}
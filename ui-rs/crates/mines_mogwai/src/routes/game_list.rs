@@ -1,62 +1,301 @@
-use crate::{api, Route};
+use crate::{api, components, model, spawn, tabs, Route};
 use mogwai::prelude::*;
 use std::rc::Rc;
 
-/// Create a `ViewBuilder` to represent a list of games.
-pub fn game_list(dispatch: Transmitter<Route>) -> ViewBuilder<HtmlElement> {
-    let component = GameList::new(dispatch, vec![]);
+/// The statuses games can be filtered by, `None` shows every game.
+const FILTERS: [(&str, Option<api::GameStatus>); 4] = [
+    ("All", None),
+    ("Open", Some(api::GameStatus::Open)),
+    ("Won", Some(api::GameStatus::Won)),
+    ("Lost", Some(api::GameStatus::Lost)),
+];
+
+/// Create a `ViewBuilder` to represent a list of games retrieved through `client`, a page at a
+/// time. The last known first page is shown until the request for it completes, requests are
+/// aborted once `cancel` is cancelled. The list is requested again whenever another tab creates or
+/// deletes a game. Games which are abandoned or deleted are changed in the list right away and put
+/// back if the request fails.
+pub fn game_list(
+    dispatch: Transmitter<Route>,
+    client: api::Client,
+    cancel: api::CancelSignal,
+) -> ViewBuilder<HtmlElement> {
+    let query = api::GameListQuery::default();
+    let page = client.cached_game_list(&query).unwrap_or_default();
+    let component = GameList::new(dispatch, client, cancel, query, page);
     Gizmo::from(component).view_builder()
 }
 
 struct GameList {
+    cancel: api::CancelSignal,
+    client: api::Client,
     dispatch: Transmitter<Route>,
-    game_ids: Rc<Vec<api::GameId>>,
+    /// Which games are listed, always for the first page
+    query: api::GameListQuery,
+    /// Every game on the pages loaded so far
+    summaries: Rc<Vec<api::GameSummary>>,
+    /// Query for the page after the last one loaded, `None` once the last page is loaded
+    next: Option<api::GameListQuery>,
+    /// Number of the most recent request, pages answering earlier requests are dropped
+    request: u64,
+    /// Action waiting on the player to confirm it, along with the game it is for
+    confirming: Option<(api::GameId, model::GameAction)>,
+    /// Why the most recent action failed
+    error: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 enum GameListModel {
-    Navigate { game_id: api::GameId },
-    ReplaceList { game_ids: Rc<Vec<api::GameId>> },
+    Action {
+        game_id: api::GameId,
+        event: model::GameActionEvent,
+    },
+    Filter {
+        status: Option<api::GameStatus>,
+    },
+    LoadMore,
+    Navigate {
+        game_id: api::GameId,
+    },
+    ReceivePage {
+        request: u64,
+        append: bool,
+        page: Rc<api::GameSummaryPage>,
+    },
+    Refresh,
+    /// Put `summary` back in the list, after the game identified by `after` (or first when `None`
+    /// or that game is gone) unless it is already there, because the action taken on it failed
+    /// with `error`
+    Restore {
+        after: Option<api::GameId>,
+        summary: Rc<api::GameSummary>,
+        error: String,
+    },
 }
 
 #[derive(Clone, Debug)]
 struct GameListView {
-    game_ids: Rc<Vec<api::GameId>>,
+    status: Option<api::GameStatus>,
+    summaries: Rc<Vec<api::GameSummary>>,
+    has_more: bool,
+    confirming: Option<(api::GameId, model::GameAction)>,
+    error: Option<String>,
 }
 
 impl GameList {
-    fn new(dispatch: Transmitter<Route>, game_ids: Vec<api::GameId>) -> Self {
+    fn new(
+        dispatch: Transmitter<Route>,
+        client: api::Client,
+        cancel: api::CancelSignal,
+        query: api::GameListQuery,
+        page: api::GameSummaryPage,
+    ) -> Self {
         Self {
+            cancel,
+            client,
             dispatch,
-            game_ids: Rc::new(game_ids),
+            next: query.next(&page),
+            query,
+            summaries: Rc::new(page.items),
+            request: 0,
+            confirming: None,
+            error: None,
+        }
+    }
+
+    /// Request the page described by `query`, tagged with `request`. The page is added to the end
+    /// of the list when `append` is set, otherwise it replaces the list. The list shown is kept if
+    /// the request fails.
+    fn load(
+        &self,
+        tx: &Transmitter<GameListModel>,
+        query: &api::GameListQuery,
+        request: u64,
+        append: bool,
+    ) {
+        let get_game_list = self.client.get_game_list(query, self.cancel.clone());
+        let tx_list = tx.contra_filter_map(|msg: &Option<GameListModel>| msg.clone());
+        spawn::send_async(&tx_list, async move {
+            match get_game_list.await {
+                Ok(page) => Some(GameListModel::ReceivePage {
+                    request,
+                    append,
+                    page: Rc::new(page),
+                }),
+                Err(error) => {
+                    ::log::warn!("unable to load games: {}", error);
+                    None
+                }
+            }
+        });
+    }
+
+    /// Start a new request for the page described by `query`, superseding any earlier request.
+    fn reload(
+        &mut self,
+        sub: &Subscriber<GameListModel>,
+        query: &api::GameListQuery,
+        append: bool,
+    ) {
+        self.request += 1;
+        let (tx_model, rx_model) = txrx();
+        sub.subscribe(&rx_model);
+        self.load(&tx_model, query, self.request, append);
+    }
+
+    /// Take `action` on the game identified by `game_id`. The list shows the result of the action
+    /// straight away and is put back the way it was if the request fails.
+    fn perform(
+        &mut self,
+        sub: &Subscriber<GameListModel>,
+        game_id: api::GameId,
+        action: model::GameAction,
+    ) {
+        let index = match self.summaries.iter().position(|s| s.id == game_id) {
+            Some(index) => index,
+            None => return,
+        };
+        let mut summaries = self.summaries.as_ref().clone();
+        let summary = summaries[index].clone();
+        let after = index.checked_sub(1).map(|before| summaries[before].id);
+        let request: api::ApiFuture<()> = match action {
+            model::GameAction::Abandon => {
+                summaries[index].status = api::GameStatus::Lost;
+                // Abandoned games no longer belong in a list of open, or won, games
+                if matches!(self.query.status, Some(status) if status != api::GameStatus::Lost) {
+                    summaries.remove(index);
+                }
+                let abandon_game = self.client.abandon_game(game_id, self.cancel.clone());
+                Box::pin(async move { abandon_game.await.map(|_| ()) })
+            }
+            model::GameAction::Delete => {
+                summaries.remove(index);
+                self.client.delete_game(game_id, self.cancel.clone())
+            }
+        };
+        self.summaries = Rc::new(summaries);
+        self.error = None;
+        let (tx_model, rx_model) = txrx();
+        sub.subscribe(&rx_model);
+        let tx_restore = tx_model.contra_filter_map(|msg: &Option<GameListModel>| msg.clone());
+        spawn::send_async(&tx_restore, async move {
+            match request.await {
+                Ok(()) | Err(api::FetchError::Cancelled) => None,
+                Err(error) => {
+                    ::log::warn!("unable to {:?} {}: {}", action, game_id, error);
+                    Some(GameListModel::Restore {
+                        after,
+                        summary: Rc::new(summary),
+                        error: format!("Unable to {} game: {}", action.verb(), error),
+                    })
+                }
+            }
+        });
+    }
+
+    fn view_msg(&self) -> GameListView {
+        GameListView {
+            status: self.query.status,
+            summaries: self.summaries.clone(),
+            has_more: self.next.is_some(),
+            confirming: self.confirming,
+            error: self.error.clone(),
+        }
+    }
+
+    #[allow(unused_braces)]
+    fn contents(tx: &Transmitter<GameListModel>, view: &GameListView) -> ViewBuilder<HtmlElement> {
+        let mut contents = builder! { <div /> };
+        contents.with(GameList::filter_nav(tx, view.status));
+        if let Some(error) = &view.error {
+            let error = error.clone();
+            contents.with(builder! { <p class="error">{error}</p> });
+        }
+        contents.with(GameList::game_ul(tx, &view.summaries, view.confirming));
+        if view.has_more {
+            let handler = tx.contra_map(|_: &Event| GameListModel::LoadMore);
+            contents.with(builder! {
+                <button class="load-more" on:click=handler>"Load more"</button>
+            });
+        }
+        contents
+    }
+
+    #[allow(unused_braces)]
+    fn filter_nav(
+        tx: &Transmitter<GameListModel>,
+        selected: Option<api::GameStatus>,
+    ) -> ViewBuilder<HtmlElement> {
+        let mut nav = builder! { <nav class="game-filter" /> };
+        for (label, status) in FILTERS.iter() {
+            let status = *status;
+            let class_name = if status == selected { "active" } else { "" };
+            let handler = tx.contra_map(move |_: &Event| GameListModel::Filter { status });
+            nav.with(builder! {
+                <button class=class_name on:click=handler>{*label}</button>
+            });
         }
+        nav
     }
 
     fn game_ul(
         tx: &Transmitter<GameListModel>,
-        game_ids: &Vec<api::GameId>,
+        summaries: &[api::GameSummary],
+        confirming: Option<(api::GameId, model::GameAction)>,
     ) -> ViewBuilder<HtmlElement> {
         let mut game_ul = builder! { <ul /> };
-        let game_links = game_ids
-            .iter()
-            .map(|game_id| GameList::game_li(&tx, game_id));
-        for game_li in game_links {
-            game_ul.with(game_li);
+        if summaries.is_empty() {
+            game_ul.with(builder! { <li class="empty">"No games"</li> });
+        }
+        for summary in summaries.iter() {
+            let confirming = confirming
+                .filter(|(game_id, _)| *game_id == summary.id)
+                .map(|(_, action)| action);
+            game_ul.with(GameList::game_li(tx, summary, confirming));
         }
         game_ul
     }
 
     #[allow(unused_braces)]
-    fn game_li(tx: &Transmitter<GameListModel>, game_id: &api::GameId) -> ViewBuilder<HtmlElement> {
+    fn game_li(
+        tx: &Transmitter<GameListModel>,
+        summary: &api::GameSummary,
+        confirming: Option<model::GameAction>,
+    ) -> ViewBuilder<HtmlElement> {
+        let game_id = summary.id;
         let game_href = format!("/game/{}", game_id);
-        let game_id = *game_id;
         let handler: Transmitter<Event> = tx.contra_map(move |e: &Event| {
             e.prevent_default();
             GameListModel::Navigate { game_id }
         });
+        let (status, badge_class) = match summary.status {
+            api::GameStatus::Open => ("Open", "badge badge-open"),
+            api::GameStatus::Won => ("Won", "badge badge-won"),
+            api::GameStatus::Lost => ("Lost", "badge badge-lost"),
+        };
+        let size = format!("{} × {}", summary.columns, summary.rows);
+        let moves = match summary.move_count {
+            1 => String::from("1 move"),
+            count => format!("{} moves", count),
+        };
+        let updated_at = summary.updated_at.clone();
+        // Only the date part of the ISO 8601 timestamp
+        let updated_on = String::from(summary.updated_at.get(..10).unwrap_or(&summary.updated_at));
+        let tx_action =
+            tx.contra_map(
+                move |event: &model::GameActionEvent| GameListModel::Action {
+                    game_id,
+                    event: *event,
+                },
+            );
         builder! {
             <li>
                 <a href=game_href on:click=handler>{game_id.to_hyphenated().to_string()}</a>
+                <span class=badge_class>{status}</span>
+                <span class="game-size">{size}</span>
+                <span class="game-moves">{moves}</span>
+                <time datetime=updated_at>{updated_on}</time>
+                {components::game_actions(summary.status, confirming, &tx_action)}
             </li>
         }
     }
@@ -68,35 +307,101 @@ impl Component for GameList {
     type DomNode = HtmlElement;
 
     fn bind(&self, in_sub: &Subscriber<Self::ModelMsg>, _out_sub: &Subscriber<Self::ViewMsg>) {
-        in_sub.send_async(async {
-            match crate::api::get_game_list().await {
-                Ok(ids) => GameListModel::ReplaceList {
-                    game_ids: Rc::new(ids),
-                },
-                Err(_) => GameListModel::ReplaceList {
-                    game_ids: Rc::new(vec![]),
-                },
-            }
-        });
+        let (tx_model, rx_model) = txrx();
+        in_sub.subscribe(&rx_model);
+        self.load(&tx_model, &self.query, self.request, false);
+        tabs::watch_game_list(
+            tx_model.contra_map(|_: &api::GameId| GameListModel::Refresh),
+            &self.cancel,
+        );
     }
 
     fn update(
         &mut self,
         msg: &GameListModel,
         tx: &Transmitter<GameListView>,
-        _sub: &Subscriber<GameListModel>,
+        sub: &Subscriber<GameListModel>,
     ) {
         match msg {
-            GameListModel::ReplaceList { game_ids } => {
-                self.game_ids = game_ids.clone();
-                tx.send(&GameListView {
-                    game_ids: self.game_ids.clone(),
-                });
+            GameListModel::Action { game_id, event } => {
+                match event {
+                    model::GameActionEvent::Ask(action) => {
+                        self.confirming = Some((*game_id, *action));
+                    }
+                    model::GameActionEvent::Dismiss => self.confirming = None,
+                    model::GameActionEvent::Confirm => match self.confirming.take() {
+                        Some((confirmed_id, action)) if confirmed_id == *game_id => {
+                            self.perform(sub, *game_id, action);
+                        }
+                        _ => (),
+                    },
+                }
+                tx.send(&self.view_msg());
+            }
+            GameListModel::Filter { status } => {
+                self.query = api::GameListQuery {
+                    status: *status,
+                    ..self.query.clone()
+                };
+                // Show what is known about the newly filtered list while it loads
+                let page = self
+                    .client
+                    .cached_game_list(&self.query)
+                    .unwrap_or_default();
+                self.next = self.query.next(&page);
+                self.summaries = Rc::new(page.items);
+                tx.send(&self.view_msg());
+                let query = self.query.clone();
+                self.reload(sub, &query, false);
+            }
+            GameListModel::LoadMore => {
+                if let Some(next) = self.next.clone() {
+                    self.reload(sub, &next, true);
+                }
+            }
+            GameListModel::Refresh => {
+                let query = self.query.clone();
+                self.reload(sub, &query, false);
+            }
+            GameListModel::ReceivePage {
+                request,
+                append,
+                page,
+            } => {
+                if *request != self.request {
+                    return;
+                }
+                let mut summaries = if *append {
+                    self.summaries.as_ref().clone()
+                } else {
+                    vec![]
+                };
+                summaries.extend(page.items.iter().cloned());
+                self.summaries = Rc::new(summaries);
+                self.next = self.query.next(page);
+                tx.send(&self.view_msg());
+            }
+            GameListModel::Restore {
+                after,
+                summary,
+                error,
+            } => {
+                let mut summaries = self.summaries.as_ref().clone();
+                match summaries.iter().position(|s| s.id == summary.id) {
+                    Some(position) => summaries[position] = summary.as_ref().clone(),
+                    None => {
+                        let index = after
+                            .and_then(|after| summaries.iter().position(|s| s.id == after))
+                            .map_or(0, |position| position + 1);
+                        summaries.insert(index, summary.as_ref().clone())
+                    }
+                }
+                self.summaries = Rc::new(summaries);
+                self.error = Some(error.clone());
+                tx.send(&self.view_msg());
             }
             GameListModel::Navigate { game_id } => {
-                self.dispatch.send(&Route::Game {
-                    game_id: game_id.clone(),
-                });
+                self.dispatch.send(&Route::Game { game_id: *game_id });
             }
         }
     }
@@ -108,10 +413,10 @@ impl Component for GameList {
         rx: &Receiver<GameListView>,
     ) -> ViewBuilder<HtmlElement> {
         let dispatch = tx.clone();
-        let contents = GameList::game_ul(&dispatch, self.game_ids.as_ref());
+        let contents = GameList::contents(&dispatch, &self.view_msg());
         let rx_patch = rx.branch_map(move |msg| Patch::Replace {
             index: 0,
-            value: GameList::game_ul(&dispatch, &msg.game_ids),
+            value: GameList::contents(&dispatch, msg),
         });
         builder! {
             <main class="game-list">
@@ -122,3 +427,263 @@ impl Component for GameList {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(status: api::GameStatus) -> api::GameSummary {
+        api::GameSummary {
+            id: uuid::Uuid::new_v4(),
+            status,
+            rows: 5,
+            columns: 10,
+            created_at: String::from("2021-02-14T18:30:05.123Z"),
+            updated_at: String::from("2021-02-15T09:00:00.000Z"),
+            move_count: 1,
+        }
+    }
+
+    fn page(items: Vec<api::GameSummary>, next_cursor: Option<&str>) -> api::GameSummaryPage {
+        api::GameSummaryPage {
+            items,
+            next_cursor: next_cursor.map(String::from),
+        }
+    }
+
+    fn start(client: Rc<api::MemoryApi>) -> (Gizmo<GameList>, View<HtmlElement>) {
+        let component = Gizmo::from(GameList::new(
+            Transmitter::new(),
+            client,
+            api::CancelSignal::none(),
+            api::GameListQuery::default(),
+            api::GameSummaryPage::default(),
+        ));
+        let ssr = View::from(component.view_builder());
+        spawn::run_pending();
+        (component, ssr)
+    }
+
+    #[test]
+    fn lists_games_from_client() {
+        let game = summary(api::GameStatus::Won);
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(vec![game.clone()], None)));
+        let builder = game_list(
+            Transmitter::new(),
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        let ssr = View::from(builder);
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![api::ApiCall::GetGameList(api::GameListQuery::default())]
+        );
+        let html = ssr.html_string();
+        assert!(html.contains(&format!("<a href=\"/game/{}\">{}</a>", game.id, game.id)));
+        assert!(html.contains("<span class=\"badge badge-won\">Won</span>"));
+        assert!(html.contains("<span class=\"game-size\">10 × 5</span>"));
+        assert!(html.contains("<span class=\"game-moves\">1 move</span>"));
+        assert!(!html.contains("Load more"));
+    }
+
+    #[test]
+    fn refreshes_list() {
+        let first = summary(api::GameStatus::Open);
+        let second = summary(api::GameStatus::Open);
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(vec![first.clone()], None)));
+        client.respond_game_list(Ok(page(vec![first, second.clone()], None)));
+        let (component, ssr) = start(client.clone());
+        assert!(!ssr.html_string().contains(&second.id.to_string()));
+        component.send(&GameListModel::Refresh);
+        spawn::run_pending();
+        assert_eq!(client.calls().len(), 2);
+        assert!(ssr.html_string().contains(&second.id.to_string()));
+    }
+
+    #[test]
+    fn filters_by_status() {
+        let open = summary(api::GameStatus::Open);
+        let lost = summary(api::GameStatus::Lost);
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(vec![open.clone(), lost.clone()], None)));
+        client.respond_game_list(Ok(page(vec![lost.clone()], None)));
+        let (component, ssr) = start(client.clone());
+        component.send(&GameListModel::Filter {
+            status: Some(api::GameStatus::Lost),
+        });
+        spawn::run_pending();
+        let lost_query = api::GameListQuery {
+            status: Some(api::GameStatus::Lost),
+            ..api::GameListQuery::default()
+        };
+        assert_eq!(client.calls()[1], api::ApiCall::GetGameList(lost_query));
+        let html = ssr.html_string();
+        assert!(html.contains("<button class=\"active\">Lost</button>"));
+        assert!(html.contains(&lost.id.to_string()));
+        assert!(!html.contains(&open.id.to_string()));
+    }
+
+    #[test]
+    fn loads_more_on_demand() {
+        let first = summary(api::GameStatus::Open);
+        let second = summary(api::GameStatus::Won);
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(vec![first.clone()], Some("page-2"))));
+        client.respond_game_list(Ok(page(vec![second.clone()], None)));
+        let (component, ssr) = start(client.clone());
+        assert!(ssr.html_string().contains("Load more"));
+        component.send(&GameListModel::LoadMore);
+        spawn::run_pending();
+        let next_query = api::GameListQuery {
+            cursor: Some(String::from("page-2")),
+            ..api::GameListQuery::default()
+        };
+        assert_eq!(client.calls()[1], api::ApiCall::GetGameList(next_query));
+        let html = ssr.html_string();
+        assert!(html.contains(&first.id.to_string()));
+        assert!(html.contains(&second.id.to_string()));
+        assert!(!html.contains("Load more"));
+    }
+
+    #[test]
+    fn drops_superseded_pages() {
+        let stale = summary(api::GameStatus::Open);
+        let client = Rc::new(api::MemoryApi::default());
+        let (component, ssr) = start(client);
+        component.send(&GameListModel::ReceivePage {
+            request: 0,
+            append: false,
+            page: Rc::new(page(vec![stale.clone()], None)),
+        });
+        assert!(ssr.html_string().contains(&stale.id.to_string()));
+        component.send(&GameListModel::Refresh);
+        component.send(&GameListModel::ReceivePage {
+            request: 0,
+            append: false,
+            page: Rc::new(page(vec![], None)),
+        });
+        assert!(ssr.html_string().contains(&stale.id.to_string()));
+    }
+
+    #[test]
+    fn deletes_game_once_confirmed() {
+        let first = summary(api::GameStatus::Open);
+        let second = summary(api::GameStatus::Won);
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(vec![first.clone(), second.clone()], None)));
+        let (component, ssr) = start(client.clone());
+        let game_id = first.id;
+        component.send(&GameListModel::Action {
+            game_id,
+            event: model::GameActionEvent::Ask(model::GameAction::Delete),
+        });
+        assert!(ssr.html_string().contains("Delete this game?"));
+        // The request is left unanswered so the list can be checked before it fails
+        component.send(&GameListModel::Action {
+            game_id,
+            event: model::GameActionEvent::Confirm,
+        });
+        assert!(!ssr.html_string().contains(&game_id.to_string()));
+        spawn::run_pending();
+        assert_eq!(client.calls()[1], api::ApiCall::DeleteGame(game_id));
+        let html = ssr.html_string();
+        let restored = html.find(&game_id.to_string()).unwrap();
+        assert!(restored < html.find(&second.id.to_string()).unwrap());
+        assert!(html.contains("Unable to delete game"));
+    }
+
+    #[test]
+    fn restores_games_in_order_after_several_fail() {
+        let games: Vec<_> = (0..3).map(|_| summary(api::GameStatus::Open)).collect();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(games.clone(), None)));
+        let (component, ssr) = start(client.clone());
+        for game in [&games[1], &games[0]] {
+            for event in [
+                model::GameActionEvent::Ask(model::GameAction::Delete),
+                model::GameActionEvent::Confirm,
+            ] {
+                component.send(&GameListModel::Action {
+                    game_id: game.id,
+                    event,
+                });
+            }
+        }
+        spawn::run_pending();
+        let html = ssr.html_string();
+        let positions: Vec<_> = games
+            .iter()
+            .map(|game| html.find(&game.id.to_string()).unwrap())
+            .collect();
+        assert!(positions[0] < positions[1] && positions[1] < positions[2]);
+    }
+
+    #[test]
+    fn abandons_game_once_confirmed() {
+        let game = summary(api::GameStatus::Open);
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_game_list(Ok(page(vec![game.clone()], None)));
+        client.respond_game(Ok(api::GameState {
+            id: game.id,
+            board: vec![],
+            status: api::GameStatus::Lost,
+            mines: None,
+            seed: None,
+        }));
+        let (component, ssr) = start(client.clone());
+        for event in [
+            model::GameActionEvent::Ask(model::GameAction::Abandon),
+            model::GameActionEvent::Confirm,
+        ] {
+            component.send(&GameListModel::Action {
+                game_id: game.id,
+                event,
+            });
+        }
+        spawn::run_pending();
+        assert_eq!(client.calls()[1], api::ApiCall::AbandonGame(game.id));
+        let html = ssr.html_string();
+        assert!(html.contains("<span class=\"badge badge-lost\">Lost</span>"));
+        assert!(!html.contains("error"));
+    }
+
+    #[test]
+    fn keeps_list_when_request_fails() {
+        let game = summary(api::GameStatus::Open);
+        let client = Rc::new(api::MemoryApi::default());
+        client.cache_game_list(
+            api::GameListQuery::default(),
+            page(vec![game.clone()], None),
+        );
+        let builder = game_list(
+            Transmitter::new(),
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        let ssr = View::from(builder);
+        spawn::run_pending();
+        assert!(ssr.html_string().contains(&game.id.to_string()));
+    }
+
+    #[test]
+    fn lists_cached_games_before_response() {
+        let game = summary(api::GameStatus::Open);
+        let client = Rc::new(api::MemoryApi::default());
+        client.cache_game_list(
+            api::GameListQuery::default(),
+            page(vec![game.clone()], None),
+        );
+        let builder = game_list(
+            Transmitter::new(),
+            client.clone(),
+            api::CancelSignal::none(),
+        );
+        let ssr = View::from(builder);
+        assert!(ssr
+            .html_string()
+            .contains(&format!("<a href=\"/game/{}\">{}</a>", game.id, game.id)));
+    }
+}
@@ -1,15 +1,27 @@
-use crate::{api, Route};
+use super::input_value;
+use crate::{api, spawn, Route};
 use mogwai::prelude::*;
 
-/// Defines how to build the view for the home screen.
+/// Defines how to build the view for the home screen. Games are created through `client` and the
+/// requests are aborted once `cancel` is cancelled. New games place their mines with `seed` unless
+/// the player changes it.
 #[allow(unused_braces)]
-pub fn home(dispatch: Transmitter<Route>) -> ViewBuilder<HtmlElement> {
+pub fn home(
+    dispatch: Transmitter<Route>,
+    seed: Option<api::GameSeed>,
+    client: api::Client,
+    cancel: api::CancelSignal,
+) -> ViewBuilder<HtmlElement> {
     // Create a transmitter to send button clicks into.
     let tx_click = Transmitter::new();
     let rx_org = Receiver::new();
     let main_component = Gizmo::from(Main {
+        cancel,
+        client,
         difficulty: Difficulty::Medium,
         dispatch,
+        mines: Difficulty::Medium.mines().map(api::MineSetting::Count),
+        seed,
     });
     builder! {
         <main class="container">
@@ -21,10 +33,16 @@ pub fn home(dispatch: Transmitter<Route>) -> ViewBuilder<HtmlElement> {
 }
 
 /// Holds the state for showing a "Create New Game" button with the button generating a game with
-/// variable size (i.e. difficulty).
+/// variable size (i.e. difficulty) and number of mines.
 struct Main {
+    cancel: api::CancelSignal,
+    client: api::Client,
     dispatch: Transmitter<Route>,
     difficulty: Difficulty,
+    /// Exact number of mines or density to place them at, the server picks a density when `None`
+    mines: Option<api::MineSetting>,
+    /// Seed to place the mines with, the server picks one when `None`
+    seed: Option<api::GameSeed>,
 }
 
 impl Component for Main {
@@ -41,17 +59,23 @@ impl Component for Main {
         use MainModel::*;
         match msg {
             Create => {
+                let input = self.create_input();
+                if let Err(error) = input.validate() {
+                    tx.send(&MainView::InvalidInput(error));
+                    return;
+                }
                 tx.send(&MainView::Creating);
                 let api_tx =
                     tx.contra_map(|r: &Result<api::GameCreated, api::FetchError>| match r {
                         Ok(response) => MainView::CreateGameSuccess(response.id),
-                        Err(err) => MainView::CreateGameError(*err),
+                        Err(err) => MainView::CreateGameError(err.clone()),
                     });
-                api_tx.send_async(api::create_game(self.difficulty.into()));
+                let create_game = self.client.create_game(input, self.cancel.clone());
+                spawn::send_async(&api_tx, create_game);
                 let dispatch = self.dispatch.clone();
                 api_tx
                     .spawn_recv()
-                    .branch_filter_map(|r| r.ok())
+                    .branch_filter_map(|r| r.as_ref().ok().copied())
                     .respond(move |response| {
                         dispatch.send(&Route::Game {
                             game_id: response.id,
@@ -60,8 +84,26 @@ impl Component for Main {
             }
             SetDifficulty(difficulty) if *difficulty != self.difficulty => {
                 self.difficulty = *difficulty;
-                tx.send(&MainView::DifficultyChanged(*difficulty))
+                self.mines = difficulty.mines().map(api::MineSetting::Count);
+                tx.send(&MainView::DifficultyChanged(*difficulty));
+                tx.send(&MainView::MinesChanged(difficulty.mines()));
+                tx.send(&MainView::DensityChanged(None));
+            }
+            // Only one of the number of mines and the density is sent, so typing either clears the
+            // other
+            SetMines(mines) => {
+                if mines.is_some() && matches!(self.mines, Some(api::MineSetting::Density(_))) {
+                    tx.send(&MainView::DensityChanged(None));
+                }
+                self.mines = mines.map(api::MineSetting::Count);
+            }
+            SetDensity(density) => {
+                if density.is_some() && matches!(self.mines, Some(api::MineSetting::Count(_))) {
+                    tx.send(&MainView::MinesChanged(None));
+                }
+                self.mines = density.map(api::MineSetting::Density);
             }
+            SetSeed(seed) => self.seed = *seed,
             _ => (),
         }
     }
@@ -88,6 +130,31 @@ impl Component for Main {
             format!("{}", initial_cols),
             rx_size.branch_map(|(_, cols)| format!("{}", cols)),
         );
+        let (initial_mines, initial_density) = match self.mines {
+            Some(api::MineSetting::Count(mines)) => (Some(mines), None),
+            Some(api::MineSetting::Density(density)) => (None, Some(density)),
+            None => (None, None),
+        };
+        let mines = (
+            mines_value(initial_mines),
+            rx.branch_filter_map(|msg| match msg {
+                MainView::MinesChanged(mines) => Some(mines_value(*mines)),
+                _ => None,
+            }),
+        );
+        let density = (
+            density_value(initial_density),
+            rx.branch_filter_map(|msg| match msg {
+                MainView::DensityChanged(density) => Some(density_value(*density)),
+                _ => None,
+            }),
+        );
+        let rx_error = rx.branch_filter_map(|msg| match msg {
+            MainView::InvalidInput(error) => Some(error.to_string()),
+            MainView::CreateGameError(error) => Some(error.to_string()),
+            MainView::Creating => Some(String::new()),
+            _ => None,
+        });
         builder! {
             <section>
                 <h1>"Letʼs Play Minesweeper"</h1>
@@ -97,6 +164,7 @@ impl Component for Main {
                         {self.li_difficulty(tx, &rx_difficulty, Difficulty::Small)}
                         {self.li_difficulty(tx, &rx_difficulty, Difficulty::Medium)}
                         {self.li_difficulty(tx, &rx_difficulty, Difficulty::Large)}
+                        {self.li_difficulty(tx, &rx_difficulty, Difficulty::Expert)}
                     </ol>
                 </nav>
                 <form
@@ -109,6 +177,47 @@ impl Component for Main {
                 >
                     <input type="hidden" name="rows" value=rows />
                     <input type="hidden" name="cols" value=cols />
+                    <label>
+                        "Mines"
+                        <input
+                            type="number"
+                            name="mines"
+                            min="1"
+                            placeholder="Default"
+                            value=mines
+                            on:input=tx.contra_map(|e: &Event| {
+                                MainModel::SetMines(input_value(e).trim().parse().ok())
+                            })
+                        />
+                    </label>
+                    <label>
+                        "Density"
+                        <input
+                            type="number"
+                            name="density"
+                            min="0"
+                            max="1"
+                            step="any"
+                            placeholder="Default"
+                            value=density
+                            on:input=tx.contra_map(|e: &Event| {
+                                MainModel::SetDensity(input_value(e).trim().parse().ok())
+                            })
+                        />
+                    </label>
+                    <label>
+                        "Seed"
+                        <input
+                            type="number"
+                            name="seed"
+                            min="0"
+                            placeholder="Random"
+                            value=seed_value(self.seed)
+                            on:input=tx.contra_map(|e: &Event| {
+                                MainModel::SetSeed(input_value(e).trim().parse().ok())
+                            })
+                        />
+                    </label>
                     <button
                         type="submit"
                         boolean:disabled={rx.branch_map(|msg| msg == &MainView::Creating)}
@@ -116,6 +225,7 @@ impl Component for Main {
                         "Start New Game"
                     </button>
                 </form>
+                <p class="error">{("", rx_error)}</p>
             </section>
         }
     }
@@ -127,12 +237,22 @@ enum MainModel {
     /// The `Create` message indicates a new game should be created with the currently set
     /// `Difficulty`.
     Create,
-    /// The `SetDifficulty` message is sent to update the currently set `Difficulty`.
+    /// The `SetDifficulty` message is sent to update the currently set `Difficulty`, which also
+    /// resets the number of mines to the one for the `Difficulty`.
     SetDifficulty(Difficulty),
+    /// The `SetMines` message is sent as the number of mines is typed, `None` when the field is
+    /// empty or isn't a number.
+    SetMines(Option<usize>),
+    /// The `SetDensity` message is sent as the density of mines is typed, `None` when the field is
+    /// empty or isn't a number.
+    SetDensity(Option<f64>),
+    /// The `SetSeed` message is sent as the seed is typed, `None` when the field is empty or isn't
+    /// a seed.
+    SetSeed(Option<api::GameSeed>),
 }
 
 /// The "view" events used to trigger changes to how the `Main` `Component` is rendered.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 enum MainView {
     /// The `Creating` event informs the view that a request to create a new game is in flight.
     Creating,
@@ -140,11 +260,20 @@ enum MainView {
     /// successfully, creating a game with the passed `api::GameId`.
     CreateGameSuccess(api::GameId),
     /// The `CreateGameError` event indicates the request to create a new game resulted in a
-    /// failure. Details about the failure are indicated by the `api::model::FetchError`.
-    CreateGameError(api::model::FetchError),
+    /// failure. Details about the failure are indicated by the `api::FetchError`.
+    CreateGameError(api::FetchError),
     /// The `DifficultyChanged` event is triggered with the currently selected `Difficulty` has
     /// been updated in the `Component`.
     DifficultyChanged(Difficulty),
+    /// The `MinesChanged` event is triggered when the number of mines is reset by choosing a
+    /// `Difficulty` or cleared by typing a density.
+    MinesChanged(Option<usize>),
+    /// The `DensityChanged` event is triggered when the density is cleared by choosing a
+    /// `Difficulty` or typing a number of mines.
+    DensityChanged(Option<f64>),
+    /// The `InvalidInput` event indicates the game described can't be created, so no request was
+    /// made.
+    InvalidInput(api::GameCreateError),
 }
 
 /// Defines preset difficulty values for the games.
@@ -153,6 +282,18 @@ enum Difficulty {
     Small,
     Medium,
     Large,
+    /// The classic expert board, with an exact number of mines
+    Expert,
+}
+
+impl Difficulty {
+    /// Exact number of mines for the `Difficulty`, `None` leaves it to the server.
+    fn mines(&self) -> Option<usize> {
+        match self {
+            Difficulty::Expert => Some(99),
+            _ => None,
+        }
+    }
 }
 
 /// Use the `Display` trait to describe how `Difficulty` should be turned into "human readable"
@@ -171,6 +312,7 @@ impl From<Difficulty> for (usize, usize) {
             Difficulty::Small => (5, 5),
             Difficulty::Medium => (10, 10),
             Difficulty::Large => (25, 25),
+            Difficulty::Expert => (16, 30),
         }
     }
 }
@@ -184,15 +326,47 @@ impl From<&Difficulty> for (usize, usize) {
     }
 }
 
-/// Define the conversion from `Difficulty` to `GameCreateInput` in terms of columns and rows.
-impl From<Difficulty> for api::model::GameCreateInput {
+/// Define the conversion from `Difficulty` to `GameCreateInput` in terms of columns, rows and
+/// mines.
+impl From<Difficulty> for api::GameCreateInput {
     fn from(difficulty: Difficulty) -> Self {
         let (rows, columns) = difficulty.into();
-        Self { columns, rows }
+        Self {
+            columns,
+            rows,
+            mines: difficulty.mines().map(api::MineSetting::Count),
+            seed: None,
+        }
     }
 }
 
+/// The value shown in the mines field, empty when the server picks the number of mines.
+fn mines_value(mines: Option<usize>) -> String {
+    mines.map(|mines| mines.to_string()).unwrap_or_default()
+}
+
+/// The value shown in the density field, empty when the server picks the density.
+fn density_value(density: Option<f64>) -> String {
+    density
+        .map(|density| density.to_string())
+        .unwrap_or_default()
+}
+
+/// The value shown in the seed field, empty when the server picks the seed.
+fn seed_value(seed: Option<api::GameSeed>) -> String {
+    seed.map(|seed| seed.to_string()).unwrap_or_default()
+}
+
 impl Main {
+    /// The game to create with the currently set `Difficulty`, number of mines and seed.
+    fn create_input(&self) -> api::GameCreateInput {
+        api::GameCreateInput {
+            mines: self.mines,
+            seed: self.seed,
+            ..self.difficulty.into()
+        }
+    }
+
     /// Display an `<li>` which handles click events to set the difficulty related to the button to
     /// create games.
     #[allow(unused_braces)]
@@ -290,3 +464,145 @@ fn star_title(rx_org: Receiver<String>) -> ViewBuilder<HtmlElement> {
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn navigates_to_created_game() {
+        let game_id = uuid::Uuid::new_v4();
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_create_game(Ok(api::GameCreated {
+            id: game_id,
+            seed: None,
+        }));
+        let (dispatch, rx_route) = txrx();
+        let routes = Rc::new(RefCell::new(vec![]));
+        let remote_routes = routes.clone();
+        rx_route.respond(move |route: &Route| remote_routes.borrow_mut().push(*route));
+        let main = Gizmo::from(Main {
+            cancel: api::CancelSignal::none(),
+            client: client.clone(),
+            difficulty: Difficulty::Small,
+            dispatch,
+            mines: None,
+            seed: None,
+        });
+        main.send(&MainModel::Create);
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![api::ApiCall::CreateGame(api::GameCreateInput {
+                columns: 5,
+                rows: 5,
+                mines: None,
+                seed: None,
+            })]
+        );
+        assert_eq!(*routes.borrow(), vec![Route::Game { game_id }]);
+    }
+
+    #[test]
+    fn creates_expert_game_with_mine_count() {
+        let client = Rc::new(api::MemoryApi::default());
+        let main = Gizmo::from(Main {
+            cancel: api::CancelSignal::none(),
+            client: client.clone(),
+            difficulty: Difficulty::Small,
+            dispatch: Transmitter::new(),
+            mines: None,
+            seed: None,
+        });
+        let ssr = View::from(main.view_builder());
+        main.send(&MainModel::SetDifficulty(Difficulty::Expert));
+        assert!(ssr.html_string().contains("value=\"99\""));
+        main.send(&MainModel::Create);
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![api::ApiCall::CreateGame(api::GameCreateInput {
+                columns: 30,
+                rows: 16,
+                mines: Some(api::MineSetting::Count(99)),
+                seed: None,
+            })]
+        );
+    }
+
+    #[test]
+    fn rejects_too_many_mines() {
+        let client = Rc::new(api::MemoryApi::default());
+        let main = Gizmo::from(Main {
+            cancel: api::CancelSignal::none(),
+            client: client.clone(),
+            difficulty: Difficulty::Small,
+            dispatch: Transmitter::new(),
+            mines: None,
+            seed: None,
+        });
+        let ssr = View::from(main.view_builder());
+        main.send(&MainModel::SetMines(Some(25)));
+        main.send(&MainModel::Create);
+        spawn::run_pending();
+        assert!(client.calls().is_empty());
+        assert!(ssr.html_string().contains("Choose between 1 and 24 mines"));
+    }
+
+    #[test]
+    fn creates_game_with_density() {
+        let client = Rc::new(api::MemoryApi::default());
+        let main = Gizmo::from(Main {
+            cancel: api::CancelSignal::none(),
+            client: client.clone(),
+            difficulty: Difficulty::Small,
+            dispatch: Transmitter::new(),
+            mines: Some(api::MineSetting::Count(3)),
+            seed: None,
+        });
+        let ssr = View::from(main.view_builder());
+        assert!(ssr.html_string().contains("value=\"3\""));
+        main.send(&MainModel::SetDensity(Some(0.2)));
+        assert!(!ssr.html_string().contains("value=\"3\""));
+        main.send(&MainModel::Create);
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![api::ApiCall::CreateGame(api::GameCreateInput {
+                columns: 5,
+                rows: 5,
+                mines: Some(api::MineSetting::Density(0.2)),
+                seed: None,
+            })]
+        );
+    }
+
+    #[test]
+    fn creates_game_from_seed() {
+        let client = Rc::new(api::MemoryApi::default());
+        let main = Gizmo::from(Main {
+            cancel: api::CancelSignal::none(),
+            client: client.clone(),
+            difficulty: Difficulty::Small,
+            dispatch: Transmitter::new(),
+            mines: None,
+            seed: Some(1234),
+        });
+        let ssr = View::from(main.view_builder());
+        assert!(ssr.html_string().contains("value=\"1234\""));
+        main.send(&MainModel::Create);
+        main.send(&MainModel::SetSeed(None));
+        main.send(&MainModel::Create);
+        spawn::run_pending();
+        let seeds: Vec<_> = client
+            .calls()
+            .into_iter()
+            .map(|call| match call {
+                api::ApiCall::CreateGame(input) => input.seed,
+                call => panic!("unexpected call {:?}", call),
+            })
+            .collect();
+        assert_eq!(seeds, vec![Some(1234), None]);
+    }
+}
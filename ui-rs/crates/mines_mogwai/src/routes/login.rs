@@ -0,0 +1,214 @@
+use super::input_value;
+use crate::{api, auth, spawn, Route};
+use mogwai::prelude::*;
+use std::convert::TryFrom;
+
+/// Defines how to build the view for the login screen. After logging in the user is sent back to
+/// the `Route` they were on when they were asked to log in. Requests to log in are made through
+/// `client` and aborted once `cancel` is cancelled.
+pub fn login(
+    dispatch: Transmitter<Route>,
+    client: api::Client,
+    cancel: api::CancelSignal,
+) -> ViewBuilder<HtmlElement> {
+    let component = Gizmo::from(Login {
+        cancel,
+        client,
+        dispatch,
+        password: String::new(),
+        username: String::new(),
+    });
+    component.view_builder()
+}
+
+/// Holds the credentials entered into the login form.
+struct Login {
+    cancel: api::CancelSignal,
+    client: api::Client,
+    dispatch: Transmitter<Route>,
+    password: String,
+    username: String,
+}
+
+impl Component for Login {
+    type ModelMsg = LoginModel;
+    type ViewMsg = LoginView;
+    type DomNode = HtmlElement;
+
+    fn update(
+        &mut self,
+        msg: &Self::ModelMsg,
+        tx: &Transmitter<Self::ViewMsg>,
+        _sub: &Subscriber<Self::ModelMsg>,
+    ) {
+        match msg {
+            LoginModel::SetPassword(password) => self.password = password.clone(),
+            LoginModel::SetUsername(username) => self.username = username.clone(),
+            LoginModel::Submit => {
+                tx.send(&LoginView::LoggingIn);
+                let input = api::LoginInput {
+                    username: self.username.clone(),
+                    password: self.password.clone(),
+                };
+                let api_tx = tx.contra_map(|r: &Result<api::AuthToken, api::FetchError>| match r {
+                    Ok(_) => LoginView::LoggedIn,
+                    Err(err) => LoginView::LoginError(err.clone()),
+                });
+                let login = self.client.login(input, self.cancel.clone());
+                spawn::send_async(&api_tx, async move {
+                    let user = login.await?;
+                    api::AuthToken::try_from(user)
+                });
+                let dispatch = self.dispatch.clone();
+                api_tx
+                    .spawn_recv()
+                    .branch_filter_map(|r| r.as_ref().ok().cloned())
+                    .respond(move |token| {
+                        auth::set(token.clone());
+                        dispatch.send(&auth::take_return_to());
+                    });
+            }
+        }
+    }
+
+    #[allow(unused_braces)]
+    fn view(
+        &self,
+        tx: &Transmitter<Self::ModelMsg>,
+        rx: &Receiver<Self::ViewMsg>,
+    ) -> ViewBuilder<Self::DomNode> {
+        let rx_error = rx.branch_map(|msg| match msg {
+            LoginView::LoginError(api::FetchError::Server(error))
+                if error.kind == api::ServerErrorKind::Unauthorized =>
+            {
+                String::from("Incorrect username or password")
+            }
+            LoginView::LoginError(error) => error.to_string(),
+            _ => String::new(),
+        });
+        builder! {
+            <section>
+                <h1>"Log In"</h1>
+                <form
+                    method="POST"
+                    action="/auth/login"
+                    on:submit=tx.contra_map(|e: &Event| {
+                        e.prevent_default();
+                        LoginModel::Submit
+                    })
+                >
+                    <label>
+                        "Username"
+                        <input
+                            type="text"
+                            name="username"
+                            autocomplete="username"
+                            on:input=tx.contra_map(|e: &Event| LoginModel::SetUsername(input_value(e)))
+                        />
+                    </label>
+                    <label>
+                        "Password"
+                        <input
+                            type="password"
+                            name="password"
+                            autocomplete="current-password"
+                            on:input=tx.contra_map(|e: &Event| LoginModel::SetPassword(input_value(e)))
+                        />
+                    </label>
+                    <button
+                        type="submit"
+                        boolean:disabled={rx.branch_map(|msg| msg == &LoginView::LoggingIn)}
+                    >
+                        "Log In"
+                    </button>
+                </form>
+                <p class="error">{("", rx_error)}</p>
+            </section>
+        }
+    }
+}
+
+/// The "model" messages to describe the change to effect within the `Component`.
+#[derive(Clone, Debug)]
+enum LoginModel {
+    /// The `SetPassword` message is sent as the password is typed.
+    SetPassword(String),
+    /// The `SetUsername` message is sent as the username is typed.
+    SetUsername(String),
+    /// The `Submit` message indicates the entered credentials should be used to log in.
+    Submit,
+}
+
+/// The "view" events used to trigger changes to how the `Login` `Component` is rendered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum LoginView {
+    /// The `LoggingIn` event informs the view that a request to log in is in flight.
+    LoggingIn,
+    /// The `LoggedIn` event indicates the credentials were accepted.
+    LoggedIn,
+    /// The `LoginError` event indicates the request to log in failed. Details about the failure
+    /// are indicated by the `api::FetchError`.
+    LoginError(api::FetchError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn stays_on_login_when_rejected() {
+        let client = Rc::new(api::MemoryApi::default());
+        client.respond_login(Err(api::FetchError::Server(
+            api::ServerError::from_response(401, ""),
+        )));
+        let (dispatch, rx_route) = txrx();
+        let routes = Rc::new(RefCell::new(vec![]));
+        let remote_routes = routes.clone();
+        rx_route.respond(move |route: &Route| remote_routes.borrow_mut().push(*route));
+        let login = Gizmo::from(Login {
+            cancel: api::CancelSignal::none(),
+            client: client.clone(),
+            dispatch,
+            password: String::new(),
+            username: String::new(),
+        });
+        login.send(&LoginModel::SetUsername(String::from("john")));
+        login.send(&LoginModel::SetPassword(String::from("changeme")));
+        login.send(&LoginModel::Submit);
+        spawn::run_pending();
+        assert_eq!(
+            client.calls(),
+            vec![api::ApiCall::Login(api::LoginInput {
+                username: String::from("john"),
+                password: String::from("changeme"),
+            })]
+        );
+        assert!(routes.borrow().is_empty());
+    }
+
+    #[test]
+    fn shows_login_error() {
+        let component = Login {
+            cancel: api::CancelSignal::none(),
+            client: Rc::new(api::MemoryApi::default()),
+            dispatch: Transmitter::new(),
+            password: String::new(),
+            username: String::new(),
+        };
+        let rx = Receiver::new();
+        let tx = rx.new_trns();
+        let ssr = View::from(component.view(&Transmitter::new(), &rx));
+        assert!(ssr.html_string().contains("<p class=\"error\"></p>"));
+        tx.send(&LoginView::LoginError(api::FetchError::Server(
+            api::ServerError::from_response(401, ""),
+        )));
+        assert!(ssr
+            .html_string()
+            .contains("<p class=\"error\">Incorrect username or password</p>"));
+        tx.send(&LoginView::LoginError(api::FetchError::Timeout));
+        assert!(ssr
+            .html_string()
+            .contains("<p class=\"error\">The request timed out</p>"));
+    }
+}
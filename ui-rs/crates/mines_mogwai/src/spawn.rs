@@ -0,0 +1,129 @@
+use mogwai::prelude::Transmitter;
+use std::future::Future;
+
+/// Run `future` in the background. In the browser it is driven by the JS event loop, elsewhere it
+/// waits until `run_pending` is called.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+/// Run `future` in the background. In the browser it is driven by the JS event loop, elsewhere it
+/// waits until `run_pending` is called.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    use futures::task::LocalSpawnExt;
+    native::SPAWNER.with(|spawner| {
+        if let Err(error) = spawner.spawn_local(future) {
+            ::log::error!("unable to spawn future: {}", error);
+        }
+    });
+}
+
+/// Send the output of `future` into `tx` once it is ready. Unlike `Transmitter::send_async` this
+/// also works outside of the browser.
+pub fn send_async<A, F>(tx: &Transmitter<A>, future: F)
+where
+    A: 'static,
+    F: Future<Output = A> + 'static,
+{
+    let tx = tx.clone();
+    spawn_local(async move {
+        let a = future.await;
+        tx.send(&a);
+    });
+}
+
+/// Wait for about `millis` milliseconds. Outside of the browser the wait lasts until `run_timers`
+/// is called.
+#[cfg(target_arch = "wasm32")]
+pub async fn wait(millis: f64) {
+    mogwai::utils::wait_approximately(millis).await;
+}
+
+/// Wait for about `millis` milliseconds. Outside of the browser the wait lasts until `run_timers`
+/// is called.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn wait(_millis: f64) {
+    native::Timer::new().await
+}
+
+/// Run spawned futures until none of them can make progress, finish every wait they are stuck on
+/// and run them again.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub fn run_timers() {
+    run_pending();
+    native::Timer::fire_all();
+    run_pending();
+}
+
+/// Run every spawned future until none of them can make progress. Futures spawned while running
+/// are run as well.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub fn run_pending() {
+    native::POOL.with(|pool| {
+        // When already running anything new is picked up by the running pool
+        if let Ok(mut pool) = pool.try_borrow_mut() {
+            pool.run_until_stalled();
+        }
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use futures::executor::{LocalPool, LocalSpawner};
+    use std::{
+        cell::{Cell, RefCell},
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, Waker},
+    };
+
+    thread_local! {
+        pub static POOL: RefCell<LocalPool> = RefCell::new(LocalPool::new());
+        pub static SPAWNER: LocalSpawner = POOL.with(|pool| pool.borrow().spawner());
+        /// Number of times every pending `Timer` has been fired
+        static FIRED: Cell<u64> = Cell::new(0);
+        /// Wakers of the `Timer`s waiting to be fired
+        static WAITING: RefCell<Vec<Waker>> = RefCell::new(vec![]);
+    }
+
+    /// Completes once `fire_all` is called after it was created.
+    pub struct Timer {
+        started: u64,
+    }
+
+    impl Timer {
+        pub fn new() -> Self {
+            Self {
+                started: FIRED.with(Cell::get),
+            }
+        }
+
+        #[cfg(test)]
+        pub fn fire_all() {
+            FIRED.with(|fired| fired.set(fired.get() + 1));
+            let waiting = WAITING.with(|waiting| waiting.replace(vec![]));
+            waiting.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    impl Future for Timer {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<()> {
+            if FIRED.with(Cell::get) > self.started {
+                Poll::Ready(())
+            } else {
+                WAITING.with(|waiting| waiting.borrow_mut().push(ctx.waker().clone()));
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{api, spawn};
+use mogwai::prelude::Transmitter;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{BroadcastChannel, MessageEvent};
+
+/// Prefix of the channel names games are shared between tabs on.
+const GAME_CHANNEL_PREFIX: &str = "mines:game:";
+/// Name of the channel newly created, and deleted, games are announced on.
+const GAME_LIST_CHANNEL: &str = "mines:games";
+
+/// Share `game` with every other tab showing it.
+pub fn post_game(game: &api::GameState) {
+    post(&game_channel(game.id), game);
+}
+
+/// Tell every other tab a game identified by `game_id` has been created.
+pub fn post_game_created(game_id: api::GameId) {
+    post(GAME_LIST_CHANNEL, &game_id);
+}
+
+/// Tell every other tab the game identified by `game_id` has been deleted.
+pub fn post_game_deleted(game_id: api::GameId) {
+    post(GAME_LIST_CHANNEL, &game_id);
+}
+
+/// Send the game identified by `game_id` into `tx` whenever another tab shares it, until `cancel`
+/// is cancelled.
+pub fn watch_game(
+    game_id: api::GameId,
+    tx: Transmitter<api::GameState>,
+    cancel: &api::CancelSignal,
+) {
+    watch(&game_channel(game_id), tx, cancel);
+}
+
+/// Send the id of every game another tab creates or deletes into `tx`, until `cancel` is cancelled.
+pub fn watch_game_list(tx: Transmitter<api::GameId>, cancel: &api::CancelSignal) {
+    watch(GAME_LIST_CHANNEL, tx, cancel);
+}
+
+fn game_channel(game_id: api::GameId) -> String {
+    format!("{}{}", GAME_CHANNEL_PREFIX, game_id.to_hyphenated())
+}
+
+thread_local! {
+    /// The channels this tab is listening on, by name. Posts go through the same channel so this
+    /// tab never receives what it shared itself.
+    static CHANNELS: RefCell<HashMap<String, Channel>> = RefCell::new(HashMap::new());
+    static NEXT_LISTENER: Cell<u64> = Cell::new(0);
+}
+
+/// A channel this tab listens on, open for as long as any route is listening to it.
+struct Channel {
+    channel: BroadcastChannel,
+    listeners: Rc<RefCell<Vec<Listener>>>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+}
+
+/// A route listening on a channel, until its `CancelSignal` is cancelled.
+struct Listener {
+    id: u64,
+    send: Rc<dyn Fn(&str)>,
+    _hook: api::CancelHook,
+}
+
+fn post<T: Serialize>(name: &str, value: &T) {
+    // There are no other tabs outside of the browser, e.g. when running tests
+    if !cfg!(target_arch = "wasm32") {
+        return;
+    }
+    let json = match serde_json::to_string(value) {
+        Ok(json) => JsValue::from(json),
+        Err(error) => {
+            ::log::error!("unable to share on {}: {}", name, error);
+            return;
+        }
+    };
+    let posted = CHANNELS.with(|channels| {
+        channels
+            .borrow()
+            .get(name)
+            .map(|open| open.channel.post_message(&json))
+    });
+    let posted = match posted {
+        Some(posted) => posted,
+        // Nothing in this tab listens on the channel, so a channel just for this post will do
+        None => match BroadcastChannel::new(name) {
+            Ok(channel) => {
+                let posted = channel.post_message(&json);
+                channel.close();
+                posted
+            }
+            Err(error) => {
+                ::log::warn!("unable to open channel {}: {:?}", name, error);
+                return;
+            }
+        },
+    };
+    if let Err(error) = posted {
+        ::log::warn!("unable to share on {}: {:?}", name, error);
+    }
+}
+
+fn watch<T: DeserializeOwned + 'static>(
+    name: &str,
+    tx: Transmitter<T>,
+    cancel: &api::CancelSignal,
+) {
+    // A signal which can never be cancelled would keep the channel open for good
+    if !cfg!(target_arch = "wasm32") || !cancel.can_cancel() || cancel.is_cancelled() {
+        return;
+    }
+    let id = NEXT_LISTENER.with(|next| next.replace(next.get() + 1));
+    let channel_name = String::from(name);
+    let send = Rc::new(move |json: &str| match serde_json::from_str(json) {
+        Ok(value) => tx.send(&value),
+        Err(error) => ::log::warn!("unreadable message on {}: {}", channel_name, error),
+    });
+    let unwatch_name = String::from(name);
+    let hook = cancel.on_cancel(move || unwatch(&unwatch_name, id));
+    let listener = Listener {
+        id,
+        send,
+        _hook: hook,
+    };
+    CHANNELS.with(|channels| {
+        let mut channels = channels.borrow_mut();
+        if let Some(open) = channels.get(name) {
+            open.listeners.borrow_mut().push(listener);
+            return;
+        }
+        match open(name) {
+            Ok(open) => {
+                open.listeners.borrow_mut().push(listener);
+                channels.insert(String::from(name), open);
+            }
+            Err(error) => ::log::warn!("unable to open channel {}: {:?}", name, error),
+        }
+    });
+}
+
+fn open(name: &str) -> Result<Channel, JsValue> {
+    let channel = BroadcastChannel::new(name)?;
+    let listeners: Rc<RefCell<Vec<Listener>>> = Rc::new(RefCell::new(Vec::new()));
+    let receivers = listeners.clone();
+    let on_message: Closure<dyn FnMut(MessageEvent)> =
+        Closure::wrap(Box::new(move |event: MessageEvent| {
+            let json = event.data().as_string().unwrap_or_default();
+            // A listener may stop listening while handling the message
+            let sends: Vec<_> = receivers
+                .borrow()
+                .iter()
+                .map(|listener| listener.send.clone())
+                .collect();
+            for send in sends {
+                send(&json);
+            }
+        }));
+    channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    Ok(Channel {
+        channel,
+        listeners,
+        _on_message: on_message,
+    })
+}
+
+/// Stop the listener `id` receiving from the channel `name`, closing the channel once nothing
+/// listens on it.
+fn unwatch(name: &str, id: u64) {
+    let closed = CHANNELS.with(|channels| {
+        let mut channels = channels.borrow_mut();
+        let open = channels.get(name)?;
+        let mut listeners = open.listeners.borrow_mut();
+        let index = listeners.iter().position(|listener| listener.id == id)?;
+        let listener = listeners.remove(index);
+        let empty = listeners.is_empty();
+        drop(listeners);
+        let channel = if empty { channels.remove(name) } else { None };
+        Some((listener, channel))
+    });
+    if let Some((listener, channel)) = closed {
+        if let Some(channel) = &channel {
+            channel.channel.set_onmessage(None);
+            channel.channel.close();
+        }
+        // The listener's hook is what called this, so it is dropped once it has returned
+        spawn::spawn_local(async move { drop(listener) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_channel_by_game() {
+        let game_id = uuid::Uuid::parse_str("0b4b1c2e-7a36-4c8e-9a4f-2f1f5f0d9c11").unwrap();
+        assert_eq!(
+            game_channel(game_id),
+            "mines:game:0b4b1c2e-7a36-4c8e-9a4f-2f1f5f0d9c11"
+        );
+    }
+}
@@ -1,71 +1,85 @@
-use anyhow::Error;
-use dotenv_codegen::dotenv;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use yew::callback::Callback;
-use yew::format::{Json, Nothing};
-use yew::services::fetch::{FetchService, FetchTask, Request, Response};
+use mines_api::browser::fetch::{self, FetchRequest, Fetched};
 
-const API_BASE_URL: &str = dotenv!("UI_BASE_API_URL");
+use crate::auth;
+use crate::config::api_base_url;
 
-pub type GameBoard = Vec<Vec<String>>;
-pub type GameId = String;
+pub use mines_api::*;
 
-#[derive(Serialize, Deserialize)]
-pub struct GameCreateResponse {
-    pub id: GameId,
+pub type GameBoard = Vec<Vec<BoardValue>>;
+pub type GameListResponse = Vec<GameId>;
+
+/// Create a new game defined by `input`.
+pub async fn create_game(input: &GameCreateInput) -> Result<GameCreated, FetchError> {
+    let url = format!("{}/game", api_base_url());
+    fetch(&url, "POST", Some(input), true).await
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct GameDetailResponse {
-    pub board: GameBoard,
-    pub id: GameId,
-    pub status: GameStatus,
+/// Fetch the identifiers of every game.
+pub async fn get_game_ids() -> Result<GameListResponse, FetchError> {
+    let url = format!("{}/game", api_base_url());
+    fetch::<(), _>(&url, "GET", None, true).await
 }
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
-pub enum GameStatus {
-    #[serde(rename = "OPEN")]
-    Open,
-    #[serde(rename = "WON")]
-    Won,
-    #[serde(rename = "LOST")]
-    Lost,
+/// Fetch the game identified by `game_id`.
+pub async fn get_game(game_id: GameId) -> Result<GameState, FetchError> {
+    fetch::<(), _>(&game_url(game_id), "GET", None, true).await
 }
 
-impl Default for GameStatus {
-    fn default() -> Self {
-        GameStatus::Open
-    }
+/// Add a move defined by `input` to the game identified by `game_id`.
+pub async fn patch_game(game_id: GameId, input: &GameMoveInput) -> Result<GameState, FetchError> {
+    fetch(&game_url(game_id), "PATCH", Some(input), true).await
 }
 
-pub type GameListResponse = Vec<GameId>;
+/// Give up on the open game identified by `game_id`, which marks it as lost.
+pub async fn abandon_game(game_id: GameId) -> Result<GameState, FetchError> {
+    let url = format!("{}/abandon", game_url(game_id));
+    fetch::<(), _>(&url, "POST", None, true).await
+}
 
-pub type FetchResponse<T> = Response<Json<Result<T, Error>>>;
-type FetchCallback<T> = Callback<FetchResponse<T>>;
+/// Remove the game identified by `game_id` entirely.
+pub async fn delete_game(game_id: GameId) -> Result<(), FetchError> {
+    fetch::<(), _>(&game_url(game_id), "DELETE", None, true).await
+}
 
-pub fn create_new_game(callback: FetchCallback<GameCreateResponse>) -> FetchTask {
-    let body = json!({ "columns": 5, "rows": 5 });
-    let req = Request::post(format!("{}/game", API_BASE_URL))
-        .header("Content-Type", "application/json")
-        .body(Json(&body))
-        .unwrap();
-    FetchService::fetch(req, callback).unwrap()
+/// Exchange the credentials in `input` for an access token. Sent without any existing token.
+pub async fn login(input: &LoginInput) -> Result<TokenizedUser, FetchError> {
+    let url = format!("{}/auth/login", api_base_url());
+    fetch(&url, "POST", Some(input), false).await
 }
 
-pub fn fetch_game_ids(callback: FetchCallback<GameListResponse>) -> FetchTask {
-    let req = Request::get(format!("{}/game", API_BASE_URL))
-        .body(Nothing)
-        .unwrap();
-    FetchService::fetch(req, callback).unwrap()
+fn game_url(game_id: GameId) -> String {
+    format!("{}/game/{}", api_base_url(), game_id)
 }
 
-pub fn fetch_game_details(
-    game_id: String,
-    callback: FetchCallback<GameDetailResponse>,
-) -> FetchTask {
-    let req = Request::get(format!("{}/game/{}", API_BASE_URL, game_id))
-        .body(Nothing)
-        .unwrap();
-    FetchService::fetch(req, callback).unwrap()
+/// Make a request to `url` and parse the body of the response as `T`. When `authorize` is set the
+/// logged in user's token is sent with the request and a rejected token sends the user to log in.
+async fn fetch<B, T>(
+    url: &str,
+    method: &str,
+    body: Option<&B>,
+    authorize: bool,
+) -> Result<T, FetchError>
+where
+    B: serde::Serialize,
+    T: serde::de::DeserializeOwned,
+{
+    let json_body = body.map(fetch::to_body).transpose()?;
+    let request = FetchRequest {
+        method,
+        body: json_body.as_deref(),
+        authorize,
+        ..FetchRequest::default()
+    };
+    match fetch::fetch(url, &request).await {
+        Ok(Fetched::Body { text, .. }) => fetch::parse(&text),
+        // Only possible when asking whether a cached body is current
+        Ok(Fetched::NotModified) => Err(FetchError::ParseError),
+        Err(FetchError::Server(error)) => {
+            if authorize && error.kind == ServerErrorKind::Unauthorized {
+                auth::unauthorized();
+            }
+            Err(FetchError::Server(error))
+        }
+        Err(error) => Err(error),
+    }
 }
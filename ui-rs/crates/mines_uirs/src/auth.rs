@@ -0,0 +1,43 @@
+use mines_api::browser::auth;
+use mines_api::AuthToken;
+use std::cell::RefCell;
+use yew_router::agent::{RouteAgentDispatcher, RouteRequest};
+use yew_router::route::Route;
+
+use crate::routes::Routes;
+
+thread_local! {
+    static RETURN_TO: RefCell<Option<String>> = RefCell::new(None);
+}
+
+pub use auth::{clear, token};
+
+/// Store `token` so it is sent with all subsequent API requests, including after a reload.
+pub fn set(token: AuthToken) {
+    if let Err(error) = auth::set(token) {
+        yew::services::ConsoleService::error(&format!("unable to save auth token: {}", error));
+    }
+}
+
+/// The `Route` to show after logging in, the home route if the user wasn't sent to log in from
+/// somewhere else.
+pub fn take_return_to() -> Route {
+    let path = RETURN_TO.with(|current| current.borrow_mut().take());
+    match path {
+        Some(path) => Route::new_no_state(path),
+        None => Routes::Home.into(),
+    }
+}
+
+/// Log the user out and ask them to log in again because the API rejected the `AuthToken`. The
+/// current page is remembered so the user can be sent back to it after logging in.
+pub fn unauthorized() {
+    clear();
+    let path = window().location().pathname().ok();
+    RETURN_TO.with(|current| current.replace(path));
+    RouteAgentDispatcher::<()>::new().send(RouteRequest::ChangeRoute(Routes::Login.into()));
+}
+
+fn window() -> web_sys::Window {
+    web_sys::window().expect("no global `window` exists")
+}
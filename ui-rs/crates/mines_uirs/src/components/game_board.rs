@@ -4,15 +4,23 @@ pub struct GameCell {
     pub column: usize,
     pub row: usize,
     pub value: String,
+    /// Called when the cell is clicked
+    pub onclick: Callback<MouseEvent>,
 }
 
 pub fn game_board_cell(props: GameCell) -> Html {
-    let GameCell { column, row, value } = props;
+    let GameCell {
+        column,
+        row,
+        value,
+        onclick,
+    } = props;
     html! {
         <td
             key=format!("{}::{}", column, row)
             valign="middle"
             align="center"
+            onclick=onclick
         >
             {value}
         </td>
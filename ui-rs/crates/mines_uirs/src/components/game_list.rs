@@ -1,18 +1,19 @@
 use yew::prelude::*;
 use yew_router::agent::{RouteAgentDispatcher, RouteRequest};
 
+use crate::api::GameId;
 use crate::routes::Routes;
 use crate::util::{RENDER, SKIP_RENDER};
 
 pub struct GameList {
-    game_ids: Vec<String>,
+    game_ids: Vec<GameId>,
     link: ComponentLink<Self>,
     dispatch: RouteAgentDispatcher,
 }
 
 #[derive(Clone, Properties)]
 pub struct Props {
-    pub game_ids: Vec<String>,
+    pub game_ids: Vec<GameId>,
 }
 
 impl Component for GameList {
@@ -51,19 +52,19 @@ impl Component for GameList {
 }
 
 impl GameList {
-    fn view_game_link(&self, id: &str) -> Html {
-        let msg = String::from(id);
+    fn view_game_link(&self, id: &GameId) -> Html {
+        let msg = *id;
         html! {
             <li>
                 <a href={format!("/game/{}", &id)} onclick=self.link.callback(move |e: MouseEvent| {
                     e.prevent_default();
-                    msg.clone()
+                    msg
                 })>
-                    {&id}
+                    {id}
                 </a>
             </li>
         }
     }
 }
 
-type GameListMsg = String;
+type GameListMsg = GameId;
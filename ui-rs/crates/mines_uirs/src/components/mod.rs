@@ -1,11 +1,14 @@
 mod error_message;
 mod game_board;
 mod game_list;
+mod session_nav;
 
 use yew::prelude::*;
 
 pub use error_message::ErrorMessage;
+pub use game_board::{game_board_cell, GameCell};
 pub use game_list::GameList;
+pub use session_nav::SessionNav;
 
 pub fn game_status_view(status: crate::api::GameStatus) -> Html {
     use crate::api::GameStatus::*;
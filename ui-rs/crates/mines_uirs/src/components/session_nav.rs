@@ -0,0 +1,76 @@
+use yew::prelude::*;
+use yew_router::agent::{RouteAgentDispatcher, RouteRequest};
+
+use crate::auth;
+use crate::routes::Routes;
+use crate::util::RENDER;
+
+/// Navigation for logging in or, once logged in, logging out.
+pub struct SessionNav {
+    /// Send navigation updates
+    dispatch: RouteAgentDispatcher,
+    /// Pathway to send messages to self
+    link: ComponentLink<Self>,
+}
+
+impl Component for SessionNav {
+    type Message = SessionNavMsg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            dispatch: RouteAgentDispatcher::new(),
+            link,
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        // Logging in or out always comes with a change of route so check the session again
+        RENDER
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        let route = match message {
+            SessionNavMsg::Login => Routes::Login,
+            SessionNavMsg::Logout => {
+                auth::clear();
+                Routes::Home
+            }
+        };
+        self.dispatch.send(RouteRequest::ChangeRoute(route.into()));
+        RENDER
+    }
+
+    fn view(&self) -> Html {
+        match auth::token() {
+            Some(token) => html! {
+                <nav>
+                    <span>{token.username()}</span>
+                    <a href="/" onclick=self.link.callback(|e: MouseEvent| {
+                        e.prevent_default();
+                        SessionNavMsg::Logout
+                    })>
+                        {"Log Out"}
+                    </a>
+                </nav>
+            },
+            None => html! {
+                <nav>
+                    <a href="/login" onclick=self.link.callback(|e: MouseEvent| {
+                        e.prevent_default();
+                        SessionNavMsg::Login
+                    })>
+                        {"Log In"}
+                    </a>
+                </nav>
+            },
+        }
+    }
+}
+
+pub enum SessionNavMsg {
+    /// Go to the login route
+    Login,
+    /// Forget the logged in user and go to the home route
+    Logout,
+}
@@ -0,0 +1,20 @@
+use dotenv_codegen::dotenv;
+use mines_api::browser::config;
+use mines_api::{Config, ConfigError};
+
+/// The API base URL from the build environment. Only used when no runtime configuration is found.
+const FALLBACK_API_BASE_URL: &str = dotenv!("UI_BASE_API_URL");
+
+pub use config::set;
+
+/// Resolve the `Config` for this page, falling back to `UI_BASE_API_URL` from the build
+/// environment when the page doesn't provide one.
+pub async fn load() -> Result<Config, ConfigError> {
+    config::load(FALLBACK_API_BASE_URL).await
+}
+
+/// The base URL of the game API from the stored `Config`, or the build environment value if no
+/// `Config` has been stored.
+pub fn api_base_url() -> String {
+    config::api_base_url(FALLBACK_API_BASE_URL)
+}
@@ -1,5 +1,7 @@
 mod api;
+mod auth;
 mod components;
+mod config;
 mod routes;
 mod util;
 
@@ -45,14 +47,33 @@ impl Component for Root {
 impl Root {
     fn switch(switch: Routes) -> Html {
         yew::services::ConsoleService::info(&format!("Route: {:?}", switch));
-        match switch {
+        let route = match switch {
             Routes::Home => html! { <routes::HomeRoute initial_game_ids=vec![] /> },
             Routes::Game(game_id) => html! { <routes::GameRoute game_id=game_id /> },
+            Routes::Login => html! { <routes::LoginRoute /> },
+        };
+        html! {
+            <>
+                <components::SessionNav />
+                {route}
+            </>
         }
     }
 }
 
 #[wasm_bindgen(start)]
-pub fn run_app() {
+pub async fn run_app() -> Result<(), JsValue> {
+    // Resolve where the API lives before anything is rendered which might talk to it
+    match config::load().await {
+        Ok(config) => {
+            yew::services::ConsoleService::info(&format!("Using API at {}", config.api_base_url()));
+            config::set(config);
+        }
+        Err(error) => {
+            yew::services::ConsoleService::error(&format!("{}", error));
+            return Err(format!("Unable to start @mines/uirs. {}", error).into());
+        }
+    }
     App::<Root>::new().mount_to_body();
+    Ok(())
 }
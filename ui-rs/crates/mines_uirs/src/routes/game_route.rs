@@ -1,27 +1,29 @@
-use anyhow::Error;
-use yew::format::Json;
+use mines_api::browser::input::is_flag_click;
+use std::collections::VecDeque;
 use yew::prelude::*;
-use yew::services::fetch::FetchTask;
 use yew_router::agent::{RouteAgentDispatcher, RouteRequest};
 
 use crate::api;
-use crate::components::{game_status_view, ErrorMessage};
-use crate::util::{RENDER, SKIP_RENDER};
+use crate::components::{game_board_cell, game_status_view, ErrorMessage, GameCell};
+use crate::util::{confirm, send_future, RENDER, SKIP_RENDER};
 
 pub struct GameRoute {
     board: api::GameBoard,
     /// Send navigation updates
     dispatch: RouteAgentDispatcher,
-    /// Error representing a non-200 status response from the server
-    error: Option<Error>,
+    /// Error from the most recent request
+    error: Option<api::FetchError>,
     /// The identifier for the game being viewed
     game_id: api::GameId,
     /// Pathway to send messages to self
     link: ComponentLink<Self>,
     /// Status of the current game
     status: api::GameStatus,
-    /// In flight HTTP request
-    task: Option<FetchTask>,
+    /// Whether an HTTP request is in flight
+    loading: bool,
+    /// Cells clicked while a request was in flight, as (row, column, flag), made in order once it
+    /// finishes
+    queued: VecDeque<(usize, usize, bool)>,
 }
 
 impl Component for GameRoute {
@@ -29,7 +31,7 @@ impl Component for GameRoute {
     type Properties = Props;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        link.send_message(GameRouteMsg::GetGame(props.game_id.clone()));
+        link.send_message(GameRouteMsg::GetGame(props.game_id));
         GameRoute {
             board: Vec::new(),
             dispatch: RouteAgentDispatcher::new(),
@@ -37,7 +39,8 @@ impl Component for GameRoute {
             game_id: props.game_id,
             link,
             status: api::GameStatus::default(),
-            task: None,
+            loading: false,
+            queued: VecDeque::new(),
         }
     }
 
@@ -53,6 +56,37 @@ impl Component for GameRoute {
     fn update(&mut self, message: Self::Message) -> ShouldRender {
         use GameRouteMsg::*;
         match message {
+            Abandon => {
+                if self.loading
+                    || self.status != api::GameStatus::Open
+                    || !confirm("Abandon this game?")
+                {
+                    return SKIP_RENDER;
+                }
+                self.loading = true;
+                let game_id = self.game_id;
+                send_future(&self.link, async move {
+                    match api::abandon_game(game_id).await {
+                        Ok(details) => GetGameSuccess(details),
+                        Err(error) => GetGameError(error),
+                    }
+                });
+                RENDER
+            }
+            Delete => {
+                if self.loading || !confirm("Delete this game?") {
+                    return SKIP_RENDER;
+                }
+                self.loading = true;
+                let game_id = self.game_id;
+                send_future(&self.link, async move {
+                    match api::delete_game(game_id).await {
+                        Ok(()) => BackToList,
+                        Err(error) => GetGameError(error),
+                    }
+                });
+                RENDER
+            }
             BackToList => {
                 self.dispatch.send(RouteRequest::ChangeRoute(
                     crate::routes::Routes::Home.into(),
@@ -60,32 +94,52 @@ impl Component for GameRoute {
                 SKIP_RENDER
             }
             GetGame(game_id) => {
-                if self.task.is_some() {
+                if self.loading {
                     return SKIP_RENDER;
                 }
-                let handler = self.link.callback(handle_fetch_response);
-                self.task = Some(api::fetch_game_details(game_id, handler));
+                self.loading = true;
+                send_future(&self.link, async move {
+                    match api::get_game(game_id).await {
+                        Ok(details) => GetGameSuccess(details),
+                        Err(error) => GetGameError(error),
+                    }
+                });
                 RENDER
             }
+            Move { row, column, flag } => {
+                if self.loading {
+                    self.queued.push_back((row, column, flag));
+                    SKIP_RENDER
+                } else {
+                    self.start_move(row, column, flag)
+                }
+            }
             GetGameSuccess(details) => {
                 if self.game_id == details.id {
                     self.board = details.board;
                     self.status = details.status;
                 }
                 self.error = None;
-                self.task = None;
+                self.loading = false;
+                while let Some((row, column, flag)) = self.queued.pop_front() {
+                    if self.start_move(row, column, flag) {
+                        break;
+                    }
+                }
                 RENDER
             }
             GetGameError(error) => {
+                // Clicks made after a failed request were aimed at a board which may be stale
+                self.queued.clear();
                 self.error = Some(error);
-                self.task = None;
+                self.loading = false;
                 RENDER
             }
         }
     }
 
     fn view(&self) -> Html {
-        let error_message = self.error.as_ref().map(|e| format!("{:?}", e));
+        let error_message = self.error.as_ref().map(|error| error.to_string());
         html! {
             <>
                 <h1>{format!("Game: {}", self.game_id)}</h1>
@@ -94,11 +148,14 @@ impl Component for GameRoute {
                         {"Back to List"}
                     </a>
                 </p>
-                <ErrorMessage is_loading={self.task.is_some()} message={error_message} />
+                <ErrorMessage is_loading={self.loading} message={error_message} />
                 <table>
-                    {format!("{:?}", self.board)}
+                    <tbody>
+                        {for self.board.iter().enumerate().map(|(row, cells)| self.view_row(row, cells))}
+                    </tbody>
                 </table>
                 {game_status_view(self.status)}
+                {self.view_actions()}
             </>
         }
     }
@@ -109,16 +166,96 @@ impl GameRoute {
         e.prevent_default();
         GameRouteMsg::BackToList
     }
+
+    /// Start a request for the move a click on the cell at `row` and `column` asks for, judged
+    /// against the board as it is now. Returns whether a request was started, which it isn't once
+    /// the game is over or when the cell can't be moved on.
+    fn start_move(&mut self, row: usize, column: usize, flag: bool) -> bool {
+        let value = self.board.get(row).and_then(|cells| cells.get(column));
+        let move_type = match value.and_then(|value| api::GameMoveType::for_cell(*value, flag)) {
+            Some(move_type) if self.status == api::GameStatus::Open => move_type,
+            _ => return false,
+        };
+        self.loading = true;
+        let game_id = self.game_id;
+        let input = api::GameMoveInput {
+            column,
+            row,
+            move_type,
+        };
+        send_future(&self.link, async move {
+            match api::patch_game(game_id, &input).await {
+                Ok(details) => GameRouteMsg::GetGameSuccess(details),
+                Err(error) => GameRouteMsg::GetGameError(error),
+            }
+        });
+        true
+    }
+
+    /// Buttons to abandon the game, while it is open, and to delete it.
+    fn view_actions(&self) -> Html {
+        let abandon = if self.status == api::GameStatus::Open {
+            html! {
+                <button disabled={self.loading} onclick=self.link.callback(|_| GameRouteMsg::Abandon)>
+                    {"Abandon"}
+                </button>
+            }
+        } else {
+            html! { <></> }
+        };
+        html! {
+            <p>
+                {abandon}
+                <button disabled={self.loading} onclick=self.link.callback(|_| GameRouteMsg::Delete)>
+                    {"Delete"}
+                </button>
+            </p>
+        }
+    }
+
+    fn view_row(&self, row: usize, cells: &[api::BoardValue]) -> Html {
+        html! {
+            <tr>
+                {for cells.iter().enumerate().map(|(column, value)| self.view_cell(row, column, *value))}
+            </tr>
+        }
+    }
+
+    /// Clicking a cell opens it, or flags it while Alt or Ctrl is held. Clicking a flag removes it.
+    fn view_cell(&self, row: usize, column: usize, value: api::BoardValue) -> Html {
+        let onclick = self.link.callback(move |e: MouseEvent| GameRouteMsg::Move {
+            row,
+            column,
+            flag: is_flag_click(&e),
+        });
+        game_board_cell(GameCell {
+            column,
+            row,
+            value: value.to_string(),
+            onclick,
+        })
+    }
 }
 
 pub enum GameRouteMsg {
+    /// Start a request to give up on the game, once the player confirms it
+    Abandon,
     BackToList,
+    /// Start a request to delete the game, once the player confirms it
+    Delete,
     /// Start a request to retrieve a game
     GetGame(api::GameId),
-    /// Received a successful response from fetching a game
-    GetGameSuccess(api::GameDetailResponse),
-    /// Receive an error response from the request to fetch a game
-    GetGameError(Error),
+    /// Received a successful response from fetching a game or adding a move to it
+    GetGameSuccess(api::GameState),
+    /// Receive an error response from the request to fetch a game or add a move to it
+    GetGameError(api::FetchError),
+    /// The cell at `row` and `column` was clicked, asking to flag it when `flag` is set. The move
+    /// waits for any request in flight to finish before it is made.
+    Move {
+        row: usize,
+        column: usize,
+        flag: bool,
+    },
 }
 
 #[derive(Clone, Properties)]
@@ -126,13 +263,3 @@ pub struct Props {
     /// The initial list of identifiers for games
     pub game_id: api::GameId,
 }
-
-/// Create `GameRouteMsg` from the given `FetchResponse`. The `response` is broken into parts and
-/// use to read the id of the created game or to respond with an error.
-fn handle_fetch_response(response: api::FetchResponse<api::GameDetailResponse>) -> GameRouteMsg {
-    let (_, Json(data)) = response.into_parts();
-    match data {
-        Ok(response) => GameRouteMsg::GetGameSuccess(response),
-        Err(err) => GameRouteMsg::GetGameError(err),
-    }
-}
@@ -1,24 +1,21 @@
-use anyhow::Error;
-use yew::format::Json;
 use yew::prelude::*;
-use yew::services::fetch::FetchTask;
 use yew_router::agent::{RouteAgentDispatcher, RouteRequest};
 
 use crate::api;
 use crate::components::{ErrorMessage, GameList};
-use crate::util::{RENDER, SKIP_RENDER};
+use crate::util::{send_future, RENDER, SKIP_RENDER};
 
 pub struct HomeRoute {
     /// Send navigation updates
     dispatch: RouteAgentDispatcher,
-    /// Error representing a non-200 status response from the server
-    error: Option<Error>,
+    /// Error from the most recent request
+    error: Option<api::FetchError>,
     /// List of Game identifiers to render
     game_ids: Vec<api::GameId>,
     /// Pathway to send messages to self
     link: ComponentLink<Self>,
-    /// In flight HTTP request
-    task: Option<FetchTask>,
+    /// Whether an HTTP request is in flight
+    loading: bool,
 }
 
 impl Component for HomeRoute {
@@ -32,7 +29,7 @@ impl Component for HomeRoute {
             error: None,
             game_ids: props.initial_game_ids,
             link,
-            task: None,
+            loading: false,
         }
     }
 
@@ -43,16 +40,27 @@ impl Component for HomeRoute {
     fn update(&mut self, message: Self::Message) -> ShouldRender {
         match message {
             HomeRouteMsg::CreateGame => {
-                if self.task.is_some() {
+                if self.loading {
                     return SKIP_RENDER;
                 }
-                let handler = self.link.callback(handle_create_response);
-                self.task = Some(api::create_new_game(handler));
+                let input = api::GameCreateInput {
+                    columns: 5,
+                    rows: 5,
+                    mines: None,
+                    seed: None,
+                };
+                self.loading = true;
+                send_future(&self.link, async move {
+                    match api::create_game(&input).await {
+                        Ok(created) => HomeRouteMsg::CreateGameSuccess(created.id),
+                        Err(error) => HomeRouteMsg::CreateGameError(error),
+                    }
+                });
                 RENDER
             }
             HomeRouteMsg::CreateGameSuccess(game_id) => {
                 self.error = None;
-                self.task = None;
+                self.loading = false;
                 self.dispatch.send(RouteRequest::ChangeRoute(
                     crate::routes::Routes::Game(game_id).into(),
                 ));
@@ -60,38 +68,40 @@ impl Component for HomeRoute {
             }
             HomeRouteMsg::CreateGameError(error) => {
                 self.error = Some(error);
-                self.task = None;
+                self.loading = false;
                 RENDER
             }
             HomeRouteMsg::ListGames => {
-                let handler = self.link.callback(handle_list_response);
-                self.task = Some(api::fetch_game_ids(handler));
+                self.loading = true;
+                send_future(&self.link, async {
+                    match api::get_game_ids().await {
+                        Ok(game_ids) => HomeRouteMsg::ListGamesSuccess(game_ids),
+                        Err(error) => HomeRouteMsg::ListGamesError(error),
+                    }
+                });
                 RENDER
             }
             HomeRouteMsg::ListGamesSuccess(game_ids) => {
                 self.error = None;
                 self.game_ids = game_ids;
-                self.task = None;
+                self.loading = false;
                 RENDER
             }
             HomeRouteMsg::ListGamesError(error) => {
                 self.error = Some(error);
-                self.task = None;
+                self.loading = false;
                 RENDER
             }
         }
     }
 
     fn view(&self) -> Html {
-        let error_message = self
-            .error
-            .as_ref()
-            .map(|_| String::from("Error loading games."));
+        let error_message = self.error.as_ref().map(|error| error.to_string());
         html! {
             <>
                 <h1>{"Let's Play Minesweeper"}</h1>
-                <ErrorMessage is_loading={self.task.is_some()} message={error_message} />
-                <button disabled={self.task.is_some()} onclick=self.link.callback(Self::create_game)>
+                <ErrorMessage is_loading={self.loading} message={error_message} />
+                <button disabled={self.loading} onclick=self.link.callback(Self::create_game)>
                     {"Start New Game"}
                 </button>
                 <GameList game_ids={self.game_ids.clone()} />
@@ -112,13 +122,13 @@ pub enum HomeRouteMsg {
     /// Received a successful response from creating a new game
     CreateGameSuccess(api::GameId),
     /// Receive an error response from the request to create a new game
-    CreateGameError(Error),
+    CreateGameError(api::FetchError),
     /// Start a request for games
     ListGames,
     /// Received a successful response containing game ids
     ListGamesSuccess(Vec<api::GameId>),
     /// Received an error response from the request for game ids
-    ListGamesError(Error),
+    ListGamesError(api::FetchError),
 }
 
 #[derive(Clone, Properties)]
@@ -126,23 +136,3 @@ pub struct Props {
     /// The initial list of identifiers for games
     pub initial_game_ids: Vec<api::GameId>,
 }
-
-/// Create `HomeRouteMsg` from the given `FetchResponse`. The `response` is broken into parts and
-/// use to read the id of the created game or to respond with an error.
-fn handle_create_response(response: api::FetchResponse<api::GameCreateResponse>) -> HomeRouteMsg {
-    let (_, Json(data)) = response.into_parts();
-    match data {
-        Ok(response) => HomeRouteMsg::CreateGameSuccess(response.id),
-        Err(err) => HomeRouteMsg::CreateGameError(err),
-    }
-}
-
-/// Create `HomeRouteMsg` from the given `FetchResponse`. The `response` is broken into parts and
-/// use to construct a list of game ids or to respond with an error.
-fn handle_list_response(response: api::FetchResponse<Vec<api::GameId>>) -> HomeRouteMsg {
-    let (_, Json(data)) = response.into_parts();
-    match data {
-        Ok(game_ids) => HomeRouteMsg::ListGamesSuccess(game_ids),
-        Err(err) => HomeRouteMsg::ListGamesError(err),
-    }
-}
@@ -0,0 +1,164 @@
+use std::convert::TryFrom;
+use yew::prelude::*;
+use yew_router::agent::{RouteAgentDispatcher, RouteRequest};
+
+use crate::api;
+use crate::auth;
+use crate::components::ErrorMessage;
+use crate::util::{send_future, RENDER, SKIP_RENDER};
+
+pub struct LoginRoute {
+    /// Send navigation updates
+    dispatch: RouteAgentDispatcher,
+    /// Error representing a failure to log in
+    error: Option<api::FetchError>,
+    /// Pathway to send messages to self
+    link: ComponentLink<Self>,
+    /// Password as it has been typed
+    password: String,
+    /// Whether an HTTP request is in flight
+    loading: bool,
+    /// Username as it has been typed
+    username: String,
+}
+
+impl Component for LoginRoute {
+    type Message = LoginRouteMsg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        LoginRoute {
+            dispatch: RouteAgentDispatcher::new(),
+            error: None,
+            link,
+            password: String::new(),
+            loading: false,
+            username: String::new(),
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        SKIP_RENDER
+    }
+
+    fn update(&mut self, message: Self::Message) -> ShouldRender {
+        match message {
+            LoginRouteMsg::Login => {
+                if self.loading {
+                    return SKIP_RENDER;
+                }
+                let input = api::LoginInput {
+                    username: self.username.clone(),
+                    password: self.password.clone(),
+                };
+                self.loading = true;
+                send_future(&self.link, async move {
+                    let token = api::login(&input).await.and_then(api::AuthToken::try_from);
+                    match token {
+                        Ok(token) => LoginRouteMsg::LoginSuccess(token),
+                        Err(error) => LoginRouteMsg::LoginError(error),
+                    }
+                });
+                RENDER
+            }
+            LoginRouteMsg::LoginSuccess(token) => {
+                auth::set(token);
+                self.error = None;
+                self.loading = false;
+                self.dispatch
+                    .send(RouteRequest::ChangeRoute(auth::take_return_to()));
+                SKIP_RENDER
+            }
+            LoginRouteMsg::LoginError(error) => {
+                self.error = Some(error);
+                self.loading = false;
+                RENDER
+            }
+            LoginRouteMsg::SetPassword(password) => {
+                self.password = password;
+                SKIP_RENDER
+            }
+            LoginRouteMsg::SetUsername(username) => {
+                self.username = username;
+                SKIP_RENDER
+            }
+        }
+    }
+
+    fn view(&self) -> Html {
+        let error_message = self.error.as_ref().map(login_error_message);
+        html! {
+            <>
+                <h1>{"Log In"}</h1>
+                <ErrorMessage is_loading={self.loading} message={error_message} />
+                <form onsubmit=self.link.callback(Self::submit)>
+                    {self.username_view()}
+                    {self.password_view()}
+                    <button type="submit" disabled={self.loading}>{"Log In"}</button>
+                </form>
+            </>
+        }
+    }
+}
+
+impl LoginRoute {
+    fn username_view(&self) -> Html {
+        html! {
+            <label>
+                {"Username"}
+                <input
+                    type="text"
+                    autocomplete="username"
+                    value=&self.username
+                    oninput=self.link.callback(|e: InputData| LoginRouteMsg::SetUsername(e.value))
+                />
+            </label>
+        }
+    }
+
+    fn password_view(&self) -> Html {
+        html! {
+            <label>
+                {"Password"}
+                <input
+                    type="password"
+                    autocomplete="current-password"
+                    value=&self.password
+                    oninput=self.link.callback(|e: InputData| LoginRouteMsg::SetPassword(e.value))
+                />
+            </label>
+        }
+    }
+
+    fn submit(e: FocusEvent) -> LoginRouteMsg {
+        e.prevent_default();
+        LoginRouteMsg::Login
+    }
+}
+
+pub enum LoginRouteMsg {
+    /// Start a request to log in with the entered credentials
+    Login,
+    /// Received an access token from logging in
+    LoginSuccess(api::AuthToken),
+    /// Receive an error response from the request to log in
+    LoginError(api::FetchError),
+    /// The password was typed
+    SetPassword(String),
+    /// The username was typed
+    SetUsername(String),
+}
+
+/// Describe why logging in failed. A rejected login means the credentials were wrong rather than
+/// that the user needs to log in.
+fn login_error_message(error: &api::FetchError) -> String {
+    match error.server_error() {
+        Some(error) if error.kind == api::ServerErrorKind::Unauthorized => {
+            String::from("Incorrect username or password.")
+        }
+        Some(error) if error.is_server_failure() => {
+            format!("Login failed with {}.", error.status)
+        }
+        _ => error.to_string(),
+    }
+}
@@ -1,15 +1,19 @@
 mod game_route;
 mod home_route;
+mod login_route;
 
 use yew_router::prelude::*;
 
 pub use game_route::GameRoute;
 pub use home_route::HomeRoute;
+pub use login_route::LoginRoute;
 
 #[derive(Clone, Debug, Switch)]
 pub enum Routes {
     #[to = "/game/{id}"]
     Game(crate::api::GameId),
+    #[to = "/login"]
+    Login,
     #[to = "/"]
     Home,
 }
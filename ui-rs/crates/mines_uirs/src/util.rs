@@ -1,6 +1,25 @@
-use yew::html::ShouldRender;
+use std::future::Future;
+use yew::html::{Component, ComponentLink, ShouldRender};
 
 /// Indicates Yew should trigger a render
 pub const RENDER: ShouldRender = true;
 /// Indicates Yew should _not_ trigger a render
 pub const SKIP_RENDER: ShouldRender = false;
+
+/// Run `future` in the background and send the message it resolves to into the component behind
+/// `link`.
+pub fn send_future<C, F>(link: &ComponentLink<C>, future: F)
+where
+    C: Component,
+    F: Future<Output = C::Message> + 'static,
+{
+    let link = link.clone();
+    wasm_bindgen_futures::spawn_local(async move { link.send_message(future.await) });
+}
+
+/// Ask the player to confirm `question`, `false` when the question can't be asked.
+pub fn confirm(question: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(question).ok())
+        .unwrap_or(false)
+}